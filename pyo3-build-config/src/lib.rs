@@ -146,6 +146,11 @@ pub fn print_feature_cfgs() {
     if rustc_minor_version >= 59 {
         println!("cargo:rustc-cfg=thread_local_const_init");
     }
+
+    // Enable capturing std::backtrace::Backtrace on Rust 1.65 and greater
+    if rustc_minor_version >= 65 {
+        println!("cargo:rustc-cfg=std_backtrace");
+    }
 }
 
 /// Private exports used in PyO3's build.rs
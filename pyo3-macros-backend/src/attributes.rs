@@ -12,27 +12,47 @@ pub mod kw {
     syn::custom_keyword!(args);
     syn::custom_keyword!(annotation);
     syn::custom_keyword!(attribute);
+    syn::custom_keyword!(base);
+    syn::custom_keyword!(clone);
+    syn::custom_keyword!(context_manager);
+    syn::custom_keyword!(dataclass);
+    syn::custom_keyword!(deepcopy_skip);
+    syn::custom_keyword!(default);
     syn::custom_keyword!(dict);
+    syn::custom_keyword!(eq);
     syn::custom_keyword!(extends);
     syn::custom_keyword!(freelist);
     syn::custom_keyword!(from_py_with);
     syn::custom_keyword!(frozen);
-    syn::custom_keyword!(gc);
+    syn::custom_keyword!(generic);
     syn::custom_keyword!(get);
     syn::custom_keyword!(get_all);
+    syn::custom_keyword!(group);
+    syn::custom_keyword!(hash);
     syn::custom_keyword!(item);
     syn::custom_keyword!(from_item_all);
     syn::custom_keyword!(mapping);
+    syn::custom_keyword!(metaclass);
     syn::custom_keyword!(module);
     syn::custom_keyword!(name);
+    syn::custom_keyword!(ops);
+    syn::custom_keyword!(ord);
+    syn::custom_keyword!(overridable);
     syn::custom_keyword!(pass_module);
+    syn::custom_keyword!(pickle);
+    syn::custom_keyword!(pymethods_groups);
+    syn::custom_keyword!(repr);
     syn::custom_keyword!(sequence);
+    syn::custom_keyword!(serde);
     syn::custom_keyword!(set);
     syn::custom_keyword!(set_all);
     syn::custom_keyword!(signature);
+    syn::custom_keyword!(str);
     syn::custom_keyword!(subclass);
     syn::custom_keyword!(text_signature);
     syn::custom_keyword!(transparent);
+    syn::custom_keyword!(traverse);
+    syn::custom_keyword!(traverse_skip);
     syn::custom_keyword!(unsendable);
     syn::custom_keyword!(weakref);
 }
@@ -119,6 +139,8 @@ impl ToTokens for TextSignatureAttributeValue {
 
 pub type ExtendsAttribute = KeywordAttribute<kw::extends, Path>;
 pub type FreelistAttribute = KeywordAttribute<kw::freelist, Box<Expr>>;
+pub type GroupAttribute = KeywordAttribute<kw::group, Ident>;
+pub type MetaclassAttribute = KeywordAttribute<kw::metaclass, Path>;
 pub type ModuleAttribute = KeywordAttribute<kw::module, LitStr>;
 pub type NameAttribute = KeywordAttribute<kw::name, NameLitStr>;
 pub type TextSignatureAttribute = KeywordAttribute<kw::text_signature, TextSignatureAttributeValue>;
@@ -142,6 +164,208 @@ impl<K: ToTokens, V: ToTokens> ToTokens for KeywordAttribute<K, V> {
 
 pub type FromPyWithAttribute = KeywordAttribute<kw::from_py_with, LitStrValue<ExprPath>>;
 
+/// `default` or `default = expr`, the default value of a `dataclass` field.
+#[derive(Clone, Debug)]
+pub struct DefaultAttribute {
+    pub kw: kw::default,
+    pub value: Option<Expr>,
+}
+
+impl Parse for DefaultAttribute {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let kw = input.parse()?;
+        let value = if input.peek(Token![=]) {
+            let _: Token![=] = input.parse()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        Ok(DefaultAttribute { kw, value })
+    }
+}
+
+impl ToTokens for DefaultAttribute {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.kw.to_tokens(tokens);
+        if let Some(value) = &self.value {
+            Token![=](self.kw.span).to_tokens(tokens);
+            value.to_tokens(tokens);
+        }
+    }
+}
+
+/// `repr` or `repr = "template"`, generating `__repr__` from `Debug` or the template.
+#[derive(Clone, Debug)]
+pub struct ReprAttribute {
+    pub kw: kw::repr,
+    pub template: Option<LitStr>,
+}
+
+impl Parse for ReprAttribute {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let kw = input.parse()?;
+        let template = if input.peek(Token![=]) {
+            let _: Token![=] = input.parse()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        Ok(ReprAttribute { kw, template })
+    }
+}
+
+impl ToTokens for ReprAttribute {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.kw.to_tokens(tokens);
+        if let Some(template) = &self.template {
+            Token![=](self.kw.span).to_tokens(tokens);
+            template.to_tokens(tokens);
+        }
+    }
+}
+
+/// `pickle` or `pickle(serde)`, generating `__getstate__`, `__setstate__` and `__reduce__`.
+#[derive(Clone, Debug)]
+pub struct PickleAttribute {
+    pub kw: kw::pickle,
+    pub serde: Option<kw::serde>,
+}
+
+impl Parse for PickleAttribute {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let kw = input.parse()?;
+        let serde = if input.peek(syn::token::Paren) {
+            let content;
+            syn::parenthesized!(content in input);
+            Some(content.parse()?)
+        } else {
+            None
+        };
+        Ok(PickleAttribute { kw, serde })
+    }
+}
+
+impl ToTokens for PickleAttribute {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.kw.to_tokens(tokens);
+        if let Some(serde) = &self.serde {
+            tokens.extend(quote::quote_spanned!(self.kw.span=> (#serde)));
+        }
+    }
+}
+
+/// `context_manager` or `context_manager(async)`, generating `__enter__` and `__exit__`, or
+/// `__aenter__` and `__aexit__`.
+#[derive(Clone, Debug)]
+pub struct ContextManagerAttribute {
+    pub kw: kw::context_manager,
+    pub asyncness: Option<Token![async]>,
+}
+
+impl Parse for ContextManagerAttribute {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let kw = input.parse()?;
+        let asyncness = if input.peek(syn::token::Paren) {
+            let content;
+            syn::parenthesized!(content in input);
+            Some(content.parse()?)
+        } else {
+            None
+        };
+        Ok(ContextManagerAttribute { kw, asyncness })
+    }
+}
+
+impl ToTokens for ContextManagerAttribute {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.kw.to_tokens(tokens);
+        if let Some(asyncness) = &self.asyncness {
+            tokens.extend(quote::quote_spanned!(self.kw.span=> (#asyncness)));
+        }
+    }
+}
+
+/// `ops(add, mul(Self, f64), neg, ...)`, generating number protocol methods from `std::ops` impls.
+#[derive(Clone, Debug)]
+pub struct OpsAttribute {
+    pub kw: kw::ops,
+    pub ops: Punctuated<OpAttribute, Comma>,
+}
+
+impl Parse for OpsAttribute {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let kw = input.parse()?;
+        let content;
+        syn::parenthesized!(content in input);
+        let ops = content.parse_terminated(OpAttribute::parse, Token![,])?;
+        Ok(OpsAttribute { kw, ops })
+    }
+}
+
+impl ToTokens for OpsAttribute {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.kw.to_tokens(tokens);
+        let ops = &self.ops;
+        tokens.extend(quote::quote_spanned!(self.kw.span=> (#ops)));
+    }
+}
+
+/// An operator in `ops(...)`, named after its `std::ops` trait method, optionally followed by
+/// the types of the other operand, e.g. `mul(Self, f64)`.
+#[derive(Clone, Debug)]
+pub struct OpAttribute {
+    pub name: Ident,
+    pub operands: Option<Punctuated<syn::Type, Comma>>,
+}
+
+impl Parse for OpAttribute {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let name = input.parse()?;
+        let operands = if input.peek(syn::token::Paren) {
+            let content;
+            syn::parenthesized!(content in input);
+            Some(content.parse_terminated(syn::Type::parse, Token![,])?)
+        } else {
+            None
+        };
+        Ok(OpAttribute { name, operands })
+    }
+}
+
+impl ToTokens for OpAttribute {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.name.to_tokens(tokens);
+        if let Some(operands) = &self.operands {
+            tokens.extend(quote::quote!((#operands)));
+        }
+    }
+}
+
+/// `pymethods_groups(a, b, ...)`, listing the `#[pymethods(group = ...)]` blocks of a class.
+#[derive(Clone, Debug)]
+pub struct PyMethodsGroupsAttribute {
+    pub kw: kw::pymethods_groups,
+    pub groups: Punctuated<Ident, Comma>,
+}
+
+impl Parse for PyMethodsGroupsAttribute {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let kw = input.parse()?;
+        let content;
+        syn::parenthesized!(content in input);
+        let groups = content.parse_terminated(Ident::parse, Token![,])?;
+        Ok(PyMethodsGroupsAttribute { kw, groups })
+    }
+}
+
+impl ToTokens for PyMethodsGroupsAttribute {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.kw.to_tokens(tokens);
+        let groups = &self.groups;
+        tokens.extend(quote::quote_spanned!(self.kw.span=> (#groups)));
+    }
+}
+
 /// For specifying the path to the pyo3 crate.
 pub type CrateAttribute = KeywordAttribute<Token![crate], LitStrValue<Path>>;
 
@@ -16,14 +16,18 @@ mod method;
 mod module;
 mod params;
 mod pyclass;
+mod pyexception;
 mod pyfunction;
 mod pyimpl;
 mod pymethod;
+mod pytrait;
 mod quotes;
 
 pub use frompyobject::build_derive_from_pyobject;
 pub use module::{process_functions_in_module, pymodule_impl, PyModuleOptions};
 pub use pyclass::{build_py_class, build_py_enum, PyClassArgs};
+pub use pyexception::build_derive_py_exception;
 pub use pyfunction::{build_py_function, PyFunctionOptions};
 pub use pyimpl::{build_py_methods, PyClassMethodsType};
+pub use pytrait::{build_py_trait, PyTraitArgs};
 pub use utils::get_doc;
@@ -122,7 +122,7 @@ pub fn impl_arg_params(
     // create array of arguments, and then parse
     Ok((
         quote! {
-                const DESCRIPTION: _pyo3::impl_::extract_argument::FunctionDescription = _pyo3::impl_::extract_argument::FunctionDescription {
+                const DESCRIPTION: _pyo3::impl_::extract_argument::FunctionDescription<'static> = _pyo3::impl_::extract_argument::FunctionDescription {
                     cls_name: #cls_name,
                     func_name: stringify!(#python_name),
                     positional_parameter_names: &[#(#positional_parameter_names),*],
@@ -2,22 +2,23 @@ use std::borrow::Cow;
 
 use crate::attributes::kw::frozen;
 use crate::attributes::{
-    self, kw, take_pyo3_options, CrateAttribute, ExtendsAttribute, FreelistAttribute,
-    ModuleAttribute, NameAttribute, NameLitStr, TextSignatureAttribute,
-    TextSignatureAttributeValue,
+    self, kw, take_pyo3_options, ContextManagerAttribute, CrateAttribute, DefaultAttribute,
+    ExtendsAttribute, FreelistAttribute, MetaclassAttribute, ModuleAttribute, NameAttribute,
+    NameLitStr, OpsAttribute, PickleAttribute, PyMethodsGroupsAttribute, ReprAttribute,
+    TextSignatureAttribute, TextSignatureAttributeValue,
 };
 use crate::deprecations::{Deprecation, Deprecations};
 use crate::konst::{ConstAttributes, ConstSpec};
 use crate::method::FnSpec;
-use crate::pyimpl::{gen_py_const, PyClassMethodsType};
+use crate::pyimpl::{add_shared_proto_slots, gen_py_const, pymethods_group_fn, PyClassMethodsType};
 use crate::pymethod::{
-    impl_py_getter_def, impl_py_setter_def, MethodAndMethodDef, MethodAndSlotDef, PropertyType,
-    SlotDef, __INT__, __REPR__, __RICHCMP__,
+    self, impl_py_getter_def, impl_py_setter_def, GeneratedPyMethod, MethodAndMethodDef,
+    MethodAndSlotDef, PropertyType, SlotDef, __HASH__, __INT__, __REPR__, __RICHCMP__, __STR__,
 };
 use crate::utils::{self, get_pyo3_crate, PythonDoc};
 use crate::PyFunctionOptions;
 use proc_macro2::{Span, TokenStream};
-use quote::quote;
+use quote::{format_ident, quote, quote_spanned, ToTokens};
 use syn::ext::IdentExt;
 use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
@@ -58,18 +59,32 @@ impl PyClassArgs {
 #[derive(Default)]
 pub struct PyClassPyO3Options {
     pub krate: Option<CrateAttribute>,
+    pub clone: Option<kw::clone>,
+    pub context_manager: Option<ContextManagerAttribute>,
+    pub dataclass: Option<kw::dataclass>,
     pub dict: Option<kw::dict>,
+    pub eq: Option<kw::eq>,
     pub extends: Option<ExtendsAttribute>,
     pub get_all: Option<kw::get_all>,
     pub freelist: Option<FreelistAttribute>,
     pub frozen: Option<kw::frozen>,
+    pub generic: Option<kw::generic>,
+    pub hash: Option<kw::hash>,
     pub mapping: Option<kw::mapping>,
+    pub metaclass: Option<MetaclassAttribute>,
     pub module: Option<ModuleAttribute>,
     pub name: Option<NameAttribute>,
+    pub ops: Option<OpsAttribute>,
+    pub ord: Option<kw::ord>,
+    pub pickle: Option<PickleAttribute>,
+    pub pymethods_groups: Option<PyMethodsGroupsAttribute>,
+    pub repr: Option<ReprAttribute>,
     pub sequence: Option<kw::sequence>,
     pub set_all: Option<kw::set_all>,
+    pub str: Option<kw::str>,
     pub subclass: Option<kw::subclass>,
     pub text_signature: Option<TextSignatureAttribute>,
+    pub traverse: Option<kw::traverse>,
     pub unsendable: Option<kw::unsendable>,
     pub weakref: Option<kw::weakref>,
 
@@ -78,18 +93,32 @@ pub struct PyClassPyO3Options {
 
 enum PyClassPyO3Option {
     Crate(CrateAttribute),
+    Clone(kw::clone),
+    ContextManager(ContextManagerAttribute),
+    Dataclass(kw::dataclass),
     Dict(kw::dict),
+    Eq(kw::eq),
     Extends(ExtendsAttribute),
     Freelist(FreelistAttribute),
     Frozen(kw::frozen),
+    Generic(kw::generic),
     GetAll(kw::get_all),
+    Hash(kw::hash),
     Mapping(kw::mapping),
+    Metaclass(MetaclassAttribute),
     Module(ModuleAttribute),
     Name(NameAttribute),
+    Ops(OpsAttribute),
+    Ord(kw::ord),
+    Pickle(PickleAttribute),
+    PyMethodsGroups(PyMethodsGroupsAttribute),
+    Repr(ReprAttribute),
     Sequence(kw::sequence),
     SetAll(kw::set_all),
+    Str(kw::str),
     Subclass(kw::subclass),
     TextSignature(TextSignatureAttribute),
+    Traverse(kw::traverse),
     Unsendable(kw::unsendable),
     Weakref(kw::weakref),
 }
@@ -99,30 +128,58 @@ impl Parse for PyClassPyO3Option {
         let lookahead = input.lookahead1();
         if lookahead.peek(Token![crate]) {
             input.parse().map(PyClassPyO3Option::Crate)
+        } else if lookahead.peek(kw::clone) {
+            input.parse().map(PyClassPyO3Option::Clone)
+        } else if lookahead.peek(kw::context_manager) {
+            input.parse().map(PyClassPyO3Option::ContextManager)
+        } else if lookahead.peek(kw::dataclass) {
+            input.parse().map(PyClassPyO3Option::Dataclass)
         } else if lookahead.peek(kw::dict) {
             input.parse().map(PyClassPyO3Option::Dict)
+        } else if lookahead.peek(kw::eq) {
+            input.parse().map(PyClassPyO3Option::Eq)
         } else if lookahead.peek(kw::extends) {
             input.parse().map(PyClassPyO3Option::Extends)
         } else if lookahead.peek(attributes::kw::freelist) {
             input.parse().map(PyClassPyO3Option::Freelist)
         } else if lookahead.peek(attributes::kw::frozen) {
             input.parse().map(PyClassPyO3Option::Frozen)
+        } else if lookahead.peek(attributes::kw::generic) {
+            input.parse().map(PyClassPyO3Option::Generic)
         } else if lookahead.peek(attributes::kw::get_all) {
             input.parse().map(PyClassPyO3Option::GetAll)
+        } else if lookahead.peek(attributes::kw::hash) {
+            input.parse().map(PyClassPyO3Option::Hash)
         } else if lookahead.peek(attributes::kw::mapping) {
             input.parse().map(PyClassPyO3Option::Mapping)
+        } else if lookahead.peek(attributes::kw::metaclass) {
+            input.parse().map(PyClassPyO3Option::Metaclass)
         } else if lookahead.peek(attributes::kw::module) {
             input.parse().map(PyClassPyO3Option::Module)
         } else if lookahead.peek(kw::name) {
             input.parse().map(PyClassPyO3Option::Name)
+        } else if lookahead.peek(attributes::kw::ops) {
+            input.parse().map(PyClassPyO3Option::Ops)
+        } else if lookahead.peek(attributes::kw::ord) {
+            input.parse().map(PyClassPyO3Option::Ord)
+        } else if lookahead.peek(attributes::kw::pickle) {
+            input.parse().map(PyClassPyO3Option::Pickle)
+        } else if lookahead.peek(attributes::kw::pymethods_groups) {
+            input.parse().map(PyClassPyO3Option::PyMethodsGroups)
+        } else if lookahead.peek(attributes::kw::repr) {
+            input.parse().map(PyClassPyO3Option::Repr)
         } else if lookahead.peek(attributes::kw::sequence) {
             input.parse().map(PyClassPyO3Option::Sequence)
         } else if lookahead.peek(attributes::kw::set_all) {
             input.parse().map(PyClassPyO3Option::SetAll)
+        } else if lookahead.peek(attributes::kw::str) {
+            input.parse().map(PyClassPyO3Option::Str)
         } else if lookahead.peek(attributes::kw::subclass) {
             input.parse().map(PyClassPyO3Option::Subclass)
         } else if lookahead.peek(attributes::kw::text_signature) {
             input.parse().map(PyClassPyO3Option::TextSignature)
+        } else if lookahead.peek(attributes::kw::traverse) {
+            input.parse().map(PyClassPyO3Option::Traverse)
         } else if lookahead.peek(attributes::kw::unsendable) {
             input.parse().map(PyClassPyO3Option::Unsendable)
         } else if lookahead.peek(attributes::kw::weakref) {
@@ -165,16 +222,39 @@ impl PyClassPyO3Options {
 
         match option {
             PyClassPyO3Option::Crate(krate) => set_option!(krate),
+            PyClassPyO3Option::Clone(clone) => set_option!(clone),
+            PyClassPyO3Option::ContextManager(context_manager) => set_option!(context_manager),
+            PyClassPyO3Option::Dataclass(dataclass) => set_option!(dataclass),
             PyClassPyO3Option::Dict(dict) => set_option!(dict),
+            PyClassPyO3Option::Eq(eq) => set_option!(eq),
             PyClassPyO3Option::Extends(extends) => set_option!(extends),
             PyClassPyO3Option::Freelist(freelist) => set_option!(freelist),
             PyClassPyO3Option::Frozen(frozen) => set_option!(frozen),
+            PyClassPyO3Option::Traverse(traverse) => set_option!(traverse),
+            PyClassPyO3Option::Generic(generic) => set_option!(generic),
             PyClassPyO3Option::GetAll(get_all) => set_option!(get_all),
+            PyClassPyO3Option::Hash(hash) => set_option!(hash),
             PyClassPyO3Option::Mapping(mapping) => set_option!(mapping),
+            PyClassPyO3Option::Metaclass(metaclass) => set_option!(metaclass),
             PyClassPyO3Option::Module(module) => set_option!(module),
             PyClassPyO3Option::Name(name) => set_option!(name),
+            PyClassPyO3Option::Ops(ops) => set_option!(ops),
+            PyClassPyO3Option::Ord(ord) => set_option!(ord),
+            PyClassPyO3Option::Pickle(pickle) => set_option!(pickle),
+            PyClassPyO3Option::PyMethodsGroups(pymethods_groups) => {
+                let mut seen = std::collections::HashSet::new();
+                for group in &pymethods_groups.groups {
+                    ensure_spanned!(
+                        seen.insert(group.unraw()),
+                        group.span() => format!("group `{}` is listed more than once", group)
+                    );
+                }
+                set_option!(pymethods_groups)
+            }
+            PyClassPyO3Option::Repr(repr) => set_option!(repr),
             PyClassPyO3Option::Sequence(sequence) => set_option!(sequence),
             PyClassPyO3Option::SetAll(set_all) => set_option!(set_all),
+            PyClassPyO3Option::Str(str) => set_option!(str),
             PyClassPyO3Option::Subclass(subclass) => set_option!(subclass),
             PyClassPyO3Option::TextSignature(text_signature) => {
                 self.deprecations
@@ -212,6 +292,7 @@ pub fn build_py_class(
             For an explanation, see https://pyo3.rs/latest/class.html#no-generic-parameters"
     );
 
+    let has_named_fields = matches!(class.fields, syn::Fields::Named(_));
     let mut field_options: Vec<(&syn::Field, FieldPyO3Options)> = match &mut class.fields {
         syn::Fields::Named(fields) => fields
             .named
@@ -257,6 +338,48 @@ pub fn build_py_class(
         }
     }
 
+    if let Some(dataclass) = args.options.dataclass {
+        ensure_spanned!(
+            has_named_fields,
+            dataclass.span() => "`dataclass` requires a struct with named fields"
+        );
+        // Like a Python dataclass, the fields are readable and, unless the class is frozen,
+        // writable attributes.
+        for (_, options) in &mut field_options {
+            options
+                .get
+                .get_or_insert(Annotated::Struct(kw::get_all(dataclass.span)));
+            if args.options.frozen.is_none() {
+                options
+                    .set
+                    .get_or_insert(Annotated::Struct(kw::set_all(dataclass.span)));
+            }
+        }
+    } else if let Some(default) = field_options
+        .iter()
+        .find_map(|(_, options)| options.default.as_ref())
+    {
+        bail_spanned!(default.kw.span() => "`default` can only be used on the fields of a `dataclass`");
+    }
+
+    if args.options.traverse.is_none() {
+        if let Some(traverse_skip) = field_options
+            .iter()
+            .find_map(|(_, options)| options.traverse_skip.as_ref())
+        {
+            bail_spanned!(traverse_skip.span() => "`traverse_skip` can only be used on the fields of a `traverse` class");
+        }
+    }
+
+    if args.options.clone.is_none() {
+        if let Some(deepcopy_skip) = field_options
+            .iter()
+            .find_map(|(_, options)| options.deepcopy_skip.as_ref())
+        {
+            bail_spanned!(deepcopy_skip.span() => "`deepcopy_skip` can only be used on the fields of a `clone` class");
+        }
+    }
+
     impl_class(&class.ident, &args, doc, field_options, methods_type, krate)
 }
 
@@ -279,12 +402,18 @@ struct FieldPyO3Options {
     get: Option<Annotated<kw::get, kw::get_all>>,
     set: Option<Annotated<kw::set, kw::set_all>>,
     name: Option<NameAttribute>,
+    default: Option<DefaultAttribute>,
+    traverse_skip: Option<kw::traverse_skip>,
+    deepcopy_skip: Option<kw::deepcopy_skip>,
 }
 
 enum FieldPyO3Option {
     Get(attributes::kw::get),
     Set(attributes::kw::set),
     Name(NameAttribute),
+    Default(DefaultAttribute),
+    TraverseSkip(kw::traverse_skip),
+    DeepcopySkip(kw::deepcopy_skip),
 }
 
 impl Parse for FieldPyO3Option {
@@ -296,6 +425,12 @@ impl Parse for FieldPyO3Option {
             input.parse().map(FieldPyO3Option::Set)
         } else if lookahead.peek(attributes::kw::name) {
             input.parse().map(FieldPyO3Option::Name)
+        } else if lookahead.peek(attributes::kw::default) {
+            input.parse().map(FieldPyO3Option::Default)
+        } else if lookahead.peek(attributes::kw::traverse_skip) {
+            input.parse().map(FieldPyO3Option::TraverseSkip)
+        } else if lookahead.peek(attributes::kw::deepcopy_skip) {
+            input.parse().map(FieldPyO3Option::DeepcopySkip)
         } else {
             Err(lookahead.error())
         }
@@ -308,6 +443,9 @@ impl FieldPyO3Options {
             get: None,
             set: None,
             name: None,
+            default: None,
+            traverse_skip: None,
+            deepcopy_skip: None,
         };
 
         for option in take_pyo3_options(attrs)? {
@@ -327,6 +465,27 @@ impl FieldPyO3Options {
                         return Err(syn::Error::new(options.name.span(), UNIQUE_NAME));
                     }
                 }
+                FieldPyO3Option::Default(default) => {
+                    ensure_spanned!(
+                        options.default.is_none(),
+                        default.kw.span() => "`default` may only be specified once"
+                    );
+                    options.default = Some(default);
+                }
+                FieldPyO3Option::TraverseSkip(traverse_skip) => {
+                    ensure_spanned!(
+                        options.traverse_skip.is_none(),
+                        traverse_skip.span() => "`traverse_skip` may only be specified once"
+                    );
+                    options.traverse_skip = Some(traverse_skip);
+                }
+                FieldPyO3Option::DeepcopySkip(deepcopy_skip) => {
+                    ensure_spanned!(
+                        options.deepcopy_skip.is_none(),
+                        deepcopy_skip.span() => "`deepcopy_skip` may only be specified once"
+                    );
+                    options.deepcopy_skip = Some(deepcopy_skip);
+                }
             }
         }
 
@@ -352,15 +511,39 @@ fn impl_class(
 ) -> syn::Result<TokenStream> {
     let pytypeinfo_impl = impl_pytypeinfo(cls, args, Some(&args.options.deprecations));
 
-    let py_class_impl = PyClassImplsBuilder::new(
+    let (mut default_methods, mut default_slots) = impl_derived_slots(cls, args)?;
+    let mut option_methods = impl_copy(args, &field_options);
+    option_methods.extend(impl_traverse(args, &field_options)?);
+    option_methods.extend(impl_ops(args)?);
+    option_methods.extend(impl_context_manager(args)?);
+    option_methods.extend(impl_generic(args));
+    if args.options.dataclass.is_some() {
+        option_methods.extend(impl_dataclass(&field_options)?);
+    }
+    if let Some((pickle_methods, helpers)) =
+        impl_pickle(cls, args, PickleState::Fields(&field_options))?
+    {
+        option_methods.extend(pickle_methods);
+        default_methods.extend(helpers);
+    }
+    let mut slot_fragments = Vec::new();
+    let mut default_method_defs = gen_option_methods(
         cls,
-        args,
-        methods_type,
-        descriptors_to_items(cls, args.options.frozen, field_options)?,
-        vec![],
-    )
-    .doc(doc)
-    .impl_all()?;
+        option_methods,
+        &mut default_methods,
+        &mut default_slots,
+        &mut slot_fragments,
+    )?;
+    default_method_defs.extend(descriptors_to_items(
+        cls,
+        args.options.frozen,
+        field_options,
+    )?);
+
+    let py_class_impl =
+        PyClassImplsBuilder::new(cls, args, methods_type, default_method_defs, default_slots)
+            .doc(doc)
+            .impl_all()?;
 
     Ok(quote! {
         const _: () = {
@@ -369,6 +552,14 @@ fn impl_class(
             #pytypeinfo_impl
 
             #py_class_impl
+
+            #(#slot_fragments)*
+
+            #[doc(hidden)]
+            #[allow(non_snake_case)]
+            impl #cls {
+                #(#default_methods)*
+            }
         };
     })
 }
@@ -447,6 +638,10 @@ pub fn build_py_enum(
         bail_spanned!(extends.span() => "enums can't extend from other classes");
     } else if let Some(subclass) = &args.options.subclass {
         bail_spanned!(subclass.span() => "enums can't be inherited by other classes");
+    } else if let Some(traverse) = &args.options.traverse {
+        bail_spanned!(traverse.span() => "enums can't hold Python objects, so `traverse` can't be used on them");
+    } else if let Some(generic) = &args.options.generic {
+        bail_spanned!(generic.span() => "enums can't be parameterized, so `generic` can't be used on them");
     } else if enum_.variants.is_empty() {
         bail_spanned!(enum_.brace_token.span.join() => "#[pyclass] can't be used on enums without any variants");
     }
@@ -508,7 +703,8 @@ fn impl_enum(
     let variants = enum_.variants;
     let pytypeinfo = impl_pytypeinfo(cls, args, None);
 
-    let (default_repr, default_repr_slot) = {
+    // `repr` replaces the default `Class.Variant` representation.
+    let (default_repr, default_repr_slot) = if args.options.repr.is_none() {
         let variants_repr = variants.iter().map(|variant| {
             let variant_name = variant.ident;
             // Assuming all variants are unit variants because they are the only type we support.
@@ -527,7 +723,9 @@ fn impl_enum(
             }
         };
         let repr_slot = generate_default_protocol_slot(&ty, &mut repr_impl, &__REPR__).unwrap();
-        (repr_impl, repr_slot)
+        (Some(repr_impl), Some(repr_slot))
+    } else {
+        (None, None)
     };
 
     let repr_type = &enum_.repr_type;
@@ -549,7 +747,29 @@ fn impl_enum(
         (int_impl, int_slot)
     };
 
-    let (default_richcmp, default_richcmp_slot) = {
+    let (mut derived_methods, mut derived_slots) = impl_derived_slots(cls, args)?;
+    let mut default_method_defs =
+        enum_default_methods(cls, variants.iter().map(|v| (v.ident, v.python_name())));
+    let mut option_methods = impl_copy(args, &[]);
+    option_methods.extend(impl_ops(args)?);
+    option_methods.extend(impl_context_manager(args)?);
+    if let Some((pickle_methods, helpers)) =
+        impl_pickle(cls, args, PickleState::Variants(&variants))?
+    {
+        option_methods.extend(pickle_methods);
+        derived_methods.extend(helpers);
+    }
+    let mut slot_fragments = Vec::new();
+    default_method_defs.extend(gen_option_methods(
+        cls,
+        option_methods,
+        &mut derived_methods,
+        &mut derived_slots,
+        &mut slot_fragments,
+    )?);
+
+    // `eq` replaces the default comparison with integers by the one generated from `PartialEq`.
+    let (default_richcmp, default_richcmp_slot) = if args.options.eq.is_none() {
         let mut richcmp_impl: syn::ImplItemFn = syn::parse_quote! {
             fn __pyo3__richcmp__(
                 &self,
@@ -588,20 +808,22 @@ fn impl_enum(
         };
         let richcmp_slot =
             generate_default_protocol_slot(&ty, &mut richcmp_impl, &__RICHCMP__).unwrap();
-        (richcmp_impl, richcmp_slot)
+        (Some(richcmp_impl), Some(richcmp_slot))
+    } else {
+        (None, None)
     };
 
-    let default_slots = vec![default_repr_slot, default_int_slot, default_richcmp_slot];
+    let default_slots = default_repr_slot
+        .into_iter()
+        .chain(Some(default_int_slot))
+        .chain(default_richcmp_slot)
+        .chain(derived_slots)
+        .collect();
 
-    let pyclass_impls = PyClassImplsBuilder::new(
-        cls,
-        args,
-        methods_type,
-        enum_default_methods(cls, variants.iter().map(|v| (v.ident, v.python_name()))),
-        default_slots,
-    )
-    .doc(doc)
-    .impl_all()?;
+    let pyclass_impls =
+        PyClassImplsBuilder::new(cls, args, methods_type, default_method_defs, default_slots)
+            .doc(doc)
+            .impl_all()?;
 
     Ok(quote! {
         const _: () = {
@@ -611,17 +833,1047 @@ fn impl_enum(
 
             #pyclass_impls
 
+            #(#slot_fragments)*
+
             #[doc(hidden)]
             #[allow(non_snake_case)]
             impl #cls {
                 #default_repr
                 #default_int
                 #default_richcmp
+                #(#derived_methods)*
+            }
+        };
+    })
+}
+
+/// Generates the `#[pymethods]` items of methods implementing pyclass options, adding the
+/// methods themselves to `methods`, any slots to `slots` and the trait impls of slot fragments,
+/// such as `__add__` and `__radd__`, and of marker traits to `fragments`.
+fn gen_option_methods(
+    cls: &syn::Ident,
+    option_methods: Vec<syn::ImplItemFn>,
+    methods: &mut Vec<syn::ImplItemFn>,
+    slots: &mut Vec<MethodAndSlotDef>,
+    fragments: &mut Vec<TokenStream>,
+) -> Result<Vec<MethodAndMethodDef>> {
+    let ty: syn::Type = syn::parse_quote!(#cls);
+    let mut method_defs = Vec::new();
+    let mut implemented_fragments = std::collections::HashSet::new();
+    for mut method in option_methods {
+        let options = PyFunctionOptions::from_attrs(&mut method.attrs)?;
+        match pymethod::gen_py_method(&ty, &mut method.sig, &mut method.attrs, options)? {
+            GeneratedPyMethod::Method(method_def) => method_defs.push(method_def),
+            GeneratedPyMethod::MethodAndTraitImpl(method_def, trait_impl) => {
+                method_defs.push(method_def);
+                fragments.push(trait_impl);
+            }
+            GeneratedPyMethod::Proto(slot_def) => slots.push(slot_def),
+            GeneratedPyMethod::SlotTraitImpl(name, fragment) => {
+                implemented_fragments.insert(name);
+                fragments.push(fragment);
+            }
+        }
+        methods.push(method);
+    }
+    let mut shared_slots = Vec::new();
+    add_shared_proto_slots(&ty, &mut shared_slots, implemented_fragments);
+    slots.extend(shared_slots.into_iter().map(|slot_def| MethodAndSlotDef {
+        associated_method: TokenStream::new(),
+        slot_def,
+    }));
+    Ok(method_defs)
+}
+
+/// Generates the methods of a `dataclass`: a constructor taking the fields as arguments,
+/// `__match_args__`, `__dataclass_fields__` and `__replace__`.
+fn impl_dataclass(
+    field_options: &[(&syn::Field, FieldPyO3Options)],
+) -> Result<Vec<syn::ImplItemFn>> {
+    let mut params = Vec::new();
+    let mut signature = Vec::new();
+    let mut initializers = Vec::new();
+    let mut python_names = Vec::new();
+    let mut descriptions = Vec::new();
+    let mut replacements = Vec::new();
+    let mut replaced_fields = Vec::new();
+    let mut follows_default = false;
+    for (field, options) in field_options {
+        let ident = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+        // The parameter is named after the Python name, but keeps the `r#` of a raw field ident
+        // such as `r#type`, which the signature unraws again.
+        let param = options
+            .name
+            .as_ref()
+            .map_or_else(|| ident.clone(), |name| name.value.0.clone());
+        let name = param.unraw().to_string();
+        let type_name = type_name(ty);
+
+        let default = options
+            .default
+            .as_ref()
+            .map(|default| match &default.value {
+                Some(value) => value.to_token_stream(),
+                None => quote_spanned!(default.kw.span=> ::std::default::Default::default()),
+            });
+        ensure_spanned!(
+            default.is_some() || !follows_default,
+            ident.span() =>
+            format!("field `{}` needs a default value, as it follows a field with a `default`", ident)
+        );
+        follows_default |= default.is_some();
+
+        params.push(quote!(#param: #ty));
+        signature.push(match &default {
+            Some(default) => quote!(#param = #default),
+            None => quote!(#param),
+        });
+        initializers.push(if *ident == param {
+            quote!(#ident)
+        } else {
+            quote!(#ident: #param)
+        });
+        let description_default = match &default {
+            // Spanned to the field type, so that a missing `IntoPy<PyObject>` impl points at it.
+            Some(default) => quote_spanned! { ty.span()=>
+                ::std::option::Option::Some(_pyo3::IntoPy::into_py({ let default: #ty = #default; default }, py))
+            },
+            None => quote!(::std::option::Option::None),
+        };
+        descriptions.push(quote! {
+            _pyo3::impl_::dataclass::DataclassField {
+                name: #name,
+                type_name: #type_name,
+                default: #description_default,
+            }
+        });
+        let replacement = format_ident!("__replace_{}", ident.unraw());
+        replacements.push(quote! {
+            #name => #replacement = ::std::option::Option::Some(value.extract()?),
+        });
+        replaced_fields.push((ident, ty, replacement));
+        python_names.push(name);
+    }
+
+    let replacement_vars = replaced_fields
+        .iter()
+        .map(|(_, ty, replacement)| quote!(let mut #replacement: ::std::option::Option<#ty> = ::std::option::Option::None;));
+    let replaced = replaced_fields.iter().map(|(ident, ty, replacement)| {
+        let clone = quote_spanned!(ty.span()=> ::std::clone::Clone::clone(&self.#ident));
+        quote! {
+            #ident: match #replacement {
+                ::std::option::Option::Some(value) => value,
+                ::std::option::Option::None => #clone,
+            }
+        }
+    });
+
+    Ok(vec![
+        syn::parse_quote! {
+            #[new]
+            #[pyo3(signature = (#(#signature),*))]
+            fn __pyo3__dataclass_new__(#(#params),*) -> Self {
+                Self { #(#initializers),* }
+            }
+        },
+        syn::parse_quote! {
+            #[classattr]
+            #[pyo3(name = "__match_args__")]
+            fn __pyo3__match_args__(py: _pyo3::Python<'_>) -> _pyo3::Py<_pyo3::types::PyTuple> {
+                _pyo3::types::PyTuple::new(py, [#(#python_names),*].iter()).into()
+            }
+        },
+        syn::parse_quote! {
+            #[classattr]
+            #[pyo3(name = "__dataclass_fields__")]
+            fn __pyo3__dataclass_fields__(py: _pyo3::Python<'_>) -> _pyo3::PyResult<_pyo3::PyObject> {
+                _pyo3::impl_::dataclass::dataclass_fields(py, ::std::vec![#(#descriptions),*])
+            }
+        },
+        syn::parse_quote! {
+            #[pyo3(name = "__replace__", signature = (**changes))]
+            fn __pyo3__replace__(
+                &self,
+                changes: ::std::option::Option<&_pyo3::types::PyDict>,
+            ) -> _pyo3::PyResult<Self> {
+                #(#replacement_vars)*
+                if let ::std::option::Option::Some(changes) = changes {
+                    for (name, value) in changes {
+                        match name.extract::<&str>()? {
+                            #(#replacements)*
+                            name => return ::std::result::Result::Err(
+                                _pyo3::impl_::dataclass::unexpected_replace_argument(name)
+                            ),
+                        }
+                    }
+                }
+                ::std::result::Result::Ok(Self { #(#replaced),* })
+            }
+        },
+    ])
+}
+
+/// The name of a Rust type as written in the source, for `__dataclass_fields__`.
+fn type_name(ty: &syn::Type) -> String {
+    let mut name = ty.to_token_stream().to_string();
+    for (from, to) in [
+        (" <", "<"),
+        ("< ", "<"),
+        (" >", ">"),
+        (" ::", "::"),
+        (":: ", "::"),
+        (" ,", ","),
+        ("& ", "&"),
+        ("( ", "("),
+        (" )", ")"),
+        ("[ ", "["),
+        (" ]", "]"),
+        (" ;", ";"),
+    ] {
+        name = name.replace(from, to);
+    }
+    name
+}
+
+/// Generates `__copy__` and `__deepcopy__` from the class's `Clone` impl for the `clone` option.
+///
+/// `__deepcopy__` copies the fields with `PyDeepCopy`, passing along the memo dict so that an
+/// object shared by several fields is copied once, except those marked with `deepcopy_skip`, which
+/// are cloned.
+fn impl_copy(
+    args: &PyClassArgs,
+    fields: &[(&syn::Field, FieldPyO3Options)],
+) -> Vec<syn::ImplItemFn> {
+    let clone = match &args.options.clone {
+        Some(clone) => clone,
+        None => return Vec::new(),
+    };
+    let members = fields
+        .iter()
+        .enumerate()
+        .map(|(index, (field, _))| match &field.ident {
+            Some(ident) => syn::Member::Named(ident.clone()),
+            None => syn::Member::Unnamed(index.into()),
+        })
+        .collect::<Vec<_>>();
+    let copied_fields = fields
+        .iter()
+        .zip(&members)
+        .filter(|((_, options), _)| options.deepcopy_skip.is_none())
+        .map(|((field, _), member)| (field.ty.span(), member))
+        .collect::<Vec<_>>();
+    let deep_copy = if copied_fields.is_empty() {
+        quote! {
+            let value = ::std::clone::Clone::clone(&*slf.try_borrow()?);
+            _pyo3::impl_::copy::deep_copy_value(slf, memo, value)
+        }
+    } else if args.options.frozen.is_some() {
+        // The fields of a frozen object can't be replaced, so they are deep copied before it is
+        // created.
+        let values = fields.iter().zip(&members).map(|((field, options), member)| {
+            if options.deepcopy_skip.is_some() {
+                quote!(#member: ::std::clone::Clone::clone(&original.#member))
+            } else {
+                quote_spanned!(field.ty.span()=> #member: _pyo3::pyclass::PyDeepCopy::deep_copy(&original.#member, memo)?)
+            }
+        });
+        quote! {
+            let value = {
+                let original = slf.try_borrow()?;
+                Self { #(#values),* }
+            };
+            _pyo3::impl_::copy::deep_copy_value(slf, memo, value)
+        }
+    } else {
+        let values = (0..copied_fields.len())
+            .map(|index| format_ident!("value_{}", index))
+            .collect::<Vec<_>>();
+        let copies = copied_fields.iter().map(|(span, member)| {
+            quote_spanned!(*span=> _pyo3::pyclass::PyDeepCopy::deep_copy(&original.#member, memo)?)
+        });
+        let copied_members = copied_fields.iter().map(|(_, member)| member);
+        quote! {
+            let copy = _pyo3::impl_::copy::new_deep_copy(slf, memo)?;
+            {
+                let original = slf.try_borrow()?;
+                #(let #values = #copies;)*
+                let mut copy_ref = copy.try_borrow_mut()?;
+                #(copy_ref.#copied_members = #values;)*
+            }
+            ::std::result::Result::Ok(copy.into())
+        }
+    };
+    vec![
+        syn::parse_quote_spanned! { clone.span =>
+            #[pyo3(name = "__copy__")]
+            fn __pyo3__copy__(slf: &_pyo3::PyCell<Self>) -> _pyo3::PyResult<_pyo3::PyObject> {
+                _pyo3::impl_::copy::copy(slf)
+            }
+        },
+        syn::parse_quote_spanned! { clone.span =>
+            #[pyo3(name = "__deepcopy__")]
+            fn __pyo3__deepcopy__(
+                slf: &_pyo3::PyCell<Self>,
+                memo: &_pyo3::types::PyDict,
+            ) -> _pyo3::PyResult<_pyo3::PyObject> {
+                #deep_copy
+            }
+        },
+    ]
+}
+
+/// Generates `__traverse__` and `__clear__` for the `traverse` option, visiting and clearing the
+/// fields with `PyTraverse`, unless they are marked with `traverse_skip`.
+///
+/// `__clear__` needs to mutate the object, so frozen classes only get `__traverse__`. Otherwise a
+/// bare `Py<T>` field is an error, as `__clear__` can't drop it to break a reference cycle.
+fn impl_traverse(
+    args: &PyClassArgs,
+    fields: &[(&syn::Field, FieldPyO3Options)],
+) -> Result<Vec<syn::ImplItemFn>> {
+    let traverse = match &args.options.traverse {
+        Some(traverse) => traverse,
+        None => return Ok(Vec::new()),
+    };
+    let mut traverse_fields = Vec::new();
+    let mut clear_fields = Vec::new();
+    for (index, (field, options)) in fields.iter().enumerate() {
+        if options.traverse_skip.is_some() {
+            continue;
+        }
+        if args.options.frozen.is_none() && is_bare_py(&field.ty) {
+            bail_spanned!(
+                field.ty.span() =>
+                "`__clear__` can't drop a bare `Py<T>` to break reference cycles; \
+                 hold it in an `Option<Py<T>>` instead, or use `#[pyo3(traverse_skip)]`"
+            );
+        }
+        let member = match &field.ident {
+            Some(ident) => syn::Member::Named(ident.clone()),
+            None => syn::Member::Unnamed(index.into()),
+        };
+        traverse_fields.push(quote_spanned! { field.ty.span()=>
+            _pyo3::pyclass::PyTraverse::traverse(&self.#member, &visit)?;
+        });
+        clear_fields.push(quote_spanned! { field.ty.span()=>
+            _pyo3::pyclass::PyTraverse::clear(&mut self.#member);
+        });
+    }
+
+    let unused_visit = if traverse_fields.is_empty() {
+        quote!(let _ = visit;)
+    } else {
+        quote!()
+    };
+    let mut methods: Vec<syn::ImplItemFn> = vec![syn::parse_quote_spanned! { traverse.span =>
+        #[pyo3(name = "__traverse__")]
+        fn __pyo3__traverse__(
+            &self,
+            visit: _pyo3::pyclass::PyVisit<'_>,
+        ) -> ::std::result::Result<(), _pyo3::pyclass::PyTraverseError> {
+            #unused_visit
+            #(#traverse_fields)*
+            ::std::result::Result::Ok(())
+        }
+    }];
+    if args.options.frozen.is_none() {
+        methods.push(syn::parse_quote_spanned! { traverse.span =>
+            #[pyo3(name = "__clear__")]
+            fn __pyo3__clear__(&mut self) {
+                #(#clear_fields)*
+            }
+        });
+    }
+    Ok(methods)
+}
+
+/// Whether `ty` is spelled as a `Py<T>` or a `PyObject`, which `PyTraverse::clear` can't drop.
+fn is_bare_py(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(syn::TypePath { qself: None, path }) => matches!(
+            path.segments.last(),
+            Some(segment) if segment.ident == "Py" || segment.ident == "PyObject"
+        ),
+        syn::Type::Group(group) => is_bare_py(&group.elem),
+        syn::Type::Paren(paren) => is_bare_py(&paren.elem),
+        _ => false,
+    }
+}
+
+/// How an operator of the `ops` option is exposed to Python.
+enum OpKind {
+    /// `-self`, calling the trait method on a clone of `self`.
+    Unary,
+    /// `self + other`, together with the reflected method for `other + self`.
+    Binary { reflected: &'static str },
+    /// `self += other`, calling the trait method on `self` itself.
+    InPlace,
+}
+
+/// An operator of the `ops` option, named after the method of the `std::ops` trait implementing it.
+struct OpDef {
+    name: &'static str,
+    trait_name: &'static str,
+    python_name: &'static str,
+    kind: OpKind,
+}
+
+impl OpDef {
+    const fn unary(
+        name: &'static str,
+        trait_name: &'static str,
+        python_name: &'static str,
+    ) -> Self {
+        OpDef {
+            name,
+            trait_name,
+            python_name,
+            kind: OpKind::Unary,
+        }
+    }
+
+    const fn binary(
+        name: &'static str,
+        trait_name: &'static str,
+        python_name: &'static str,
+        reflected: &'static str,
+    ) -> Self {
+        OpDef {
+            name,
+            trait_name,
+            python_name,
+            kind: OpKind::Binary { reflected },
+        }
+    }
+
+    const fn in_place(
+        name: &'static str,
+        trait_name: &'static str,
+        python_name: &'static str,
+    ) -> Self {
+        OpDef {
+            name,
+            trait_name,
+            python_name,
+            kind: OpKind::InPlace,
+        }
+    }
+}
+
+const OPS: &[OpDef] = &[
+    OpDef::binary("add", "Add", "__add__", "__radd__"),
+    OpDef::binary("sub", "Sub", "__sub__", "__rsub__"),
+    OpDef::binary("mul", "Mul", "__mul__", "__rmul__"),
+    OpDef::binary("div", "Div", "__truediv__", "__rtruediv__"),
+    OpDef::binary("rem", "Rem", "__mod__", "__rmod__"),
+    OpDef::binary("bitand", "BitAnd", "__and__", "__rand__"),
+    OpDef::binary("bitor", "BitOr", "__or__", "__ror__"),
+    OpDef::binary("bitxor", "BitXor", "__xor__", "__rxor__"),
+    OpDef::binary("shl", "Shl", "__lshift__", "__rlshift__"),
+    OpDef::binary("shr", "Shr", "__rshift__", "__rrshift__"),
+    OpDef::in_place("add_assign", "AddAssign", "__iadd__"),
+    OpDef::in_place("sub_assign", "SubAssign", "__isub__"),
+    OpDef::in_place("mul_assign", "MulAssign", "__imul__"),
+    OpDef::in_place("div_assign", "DivAssign", "__itruediv__"),
+    OpDef::in_place("rem_assign", "RemAssign", "__imod__"),
+    OpDef::in_place("bitand_assign", "BitAndAssign", "__iand__"),
+    OpDef::in_place("bitor_assign", "BitOrAssign", "__ior__"),
+    OpDef::in_place("bitxor_assign", "BitXorAssign", "__ixor__"),
+    OpDef::in_place("shl_assign", "ShlAssign", "__ilshift__"),
+    OpDef::in_place("shr_assign", "ShrAssign", "__irshift__"),
+    OpDef::unary("neg", "Neg", "__neg__"),
+    OpDef::unary("not", "Not", "__invert__"),
+];
+
+/// Generates the number protocol methods of the `ops` option from the class's `std::ops` impls.
+///
+/// Binary operators try to extract the other operand as each of the listed types in turn, `Self`
+/// by default, and return `NotImplemented` if none matches. Their reflected methods, such as
+/// `__radd__`, are generated for the types other than `Self`, and return `NotImplemented` when the
+/// other type doesn't implement the operator for the class.
+fn impl_ops(args: &PyClassArgs) -> Result<Vec<syn::ImplItemFn>> {
+    let ops = match &args.options.ops {
+        Some(ops) => ops,
+        None => return Ok(Vec::new()),
+    };
+    let mut seen = std::collections::HashSet::new();
+    let mut methods = Vec::new();
+    for op in &ops.ops {
+        let span = op.name.span();
+        let def = match OPS.iter().find(|def| op.name == def.name) {
+            Some(def) => def,
+            None => {
+                let names: Vec<_> = OPS.iter().map(|def| format!("`{}`", def.name)).collect();
+                bail_spanned!(span => format!(
+                    "unknown operator `{}`, expected one of {}",
+                    op.name,
+                    names.join(", ")
+                ));
             }
         };
+        ensure_spanned!(
+            seen.insert(def.name),
+            span => format!("`{}` is listed more than once", def.name)
+        );
+        let trait_name = syn::Ident::new(def.trait_name, span);
+        let method = &op.name;
+        let python_name = def.python_name;
+        let fn_name = format_ident!("__pyo3{}", python_name, span = span);
+        let operands: Vec<syn::Type> = match &op.operands {
+            Some(operands) => operands.iter().cloned().collect(),
+            None => vec![syn::parse_quote_spanned!(span=> Self)],
+        };
+
+        match def.kind {
+            OpKind::Unary => {
+                ensure_spanned!(
+                    op.operands.is_none(),
+                    span => format!("`{}` doesn't take operand types", def.name)
+                );
+                methods.push(syn::parse_quote_spanned! { span =>
+                    #[pyo3(name = #python_name)]
+                    fn #fn_name(&self, py: _pyo3::Python<'_>) -> _pyo3::PyObject {
+                        _pyo3::IntoPy::into_py(
+                            ::std::ops::#trait_name::#method(::std::clone::Clone::clone(self)),
+                            py,
+                        )
+                    }
+                });
+            }
+            OpKind::Binary { reflected } => {
+                methods.push(syn::parse_quote_spanned! { span =>
+                    #[pyo3(name = #python_name)]
+                    fn #fn_name(
+                        &self,
+                        py: _pyo3::Python<'_>,
+                        other: &_pyo3::PyAny,
+                    ) -> _pyo3::PyResult<_pyo3::PyObject> {
+                        #(
+                            if let ::std::result::Result::Ok(other) = other.extract::<#operands>() {
+                                return ::std::result::Result::Ok(_pyo3::IntoPy::into_py(
+                                    ::std::ops::#trait_name::#method(::std::clone::Clone::clone(self), other),
+                                    py,
+                                ));
+                            }
+                        )*
+                        ::std::result::Result::Ok(py.NotImplemented())
+                    }
+                });
+
+                let reflected_operands: Vec<_> = operands
+                    .iter()
+                    .filter(|operand| !is_self_type(operand))
+                    .collect();
+                if !reflected_operands.is_empty() {
+                    let reflected_fn_name = format_ident!("__pyo3{}", reflected, span = span);
+                    let reflected_trait = format_ident!("Reflected{}", trait_name);
+                    let fallback_trait = format_ident!("NoReflected{}", trait_name);
+                    methods.push(syn::parse_quote_spanned! { span =>
+                        #[pyo3(name = #reflected)]
+                        fn #reflected_fn_name(
+                            &self,
+                            py: _pyo3::Python<'_>,
+                            other: &_pyo3::PyAny,
+                        ) -> _pyo3::PyResult<_pyo3::PyObject> {
+                            #[allow(unused_imports)]
+                            use _pyo3::impl_::ops::{#fallback_trait, #reflected_trait};
+                            #(
+                                if let ::std::result::Result::Ok(other) = other.extract::<#reflected_operands>() {
+                                    return ::std::result::Result::Ok(
+                                        _pyo3::impl_::ops::Operands(other, ::std::clone::Clone::clone(self)).#method(py)
+                                    );
+                                }
+                            )*
+                            ::std::result::Result::Ok(py.NotImplemented())
+                        }
+                    });
+                }
+            }
+            OpKind::InPlace => {
+                ensure_spanned!(
+                    args.options.frozen.is_none(),
+                    span => format!("`{}` can't be used on `frozen` classes, as it mutates the object", def.name)
+                );
+                ensure_spanned!(
+                    operands.len() == 1,
+                    span => format!(
+                        "`{}` takes a single operand type; Python falls back to the binary operator for the others",
+                        def.name
+                    )
+                );
+                let operand = &operands[0];
+                methods.push(syn::parse_quote_spanned! { span =>
+                    #[pyo3(name = #python_name)]
+                    fn #fn_name(&mut self, other: #operand) {
+                        ::std::ops::#trait_name::#method(self, other);
+                    }
+                });
+            }
+        }
+    }
+    Ok(methods)
+}
+
+/// Whether `ty` is `Self`, the operand type of the `ops` option's operators by default.
+fn is_self_type(ty: &syn::Type) -> bool {
+    matches!(ty, syn::Type::Path(ty) if ty.qself.is_none() && ty.path.is_ident("Self"))
+}
+
+/// Generates `__enter__` and `__exit__` from the class's `PyContextManager` impl for the
+/// `context_manager` option, or `__aenter__` and `__aexit__` from its `PyAsyncContextManager` impl
+/// for `context_manager(async)`.
+fn impl_context_manager(args: &PyClassArgs) -> Result<Vec<syn::ImplItemFn>> {
+    let context_manager = match &args.options.context_manager {
+        Some(context_manager) => context_manager,
+        None => return Ok(Vec::new()),
+    };
+    let span = context_manager.kw.span;
+    ensure_spanned!(
+        args.options.frozen.is_none(),
+        span => "`context_manager` can't be used on `frozen` classes, as entering and exiting mutate the object"
+    );
+    let methods = if context_manager.asyncness.is_some() {
+        vec![
+            syn::parse_quote_spanned! { span =>
+                #[pyo3(name = "__aenter__")]
+                fn __pyo3__aenter__(
+                    &mut self,
+                    py: _pyo3::Python<'_>,
+                ) -> _pyo3::PyResult<_pyo3::PyObject> {
+                    _pyo3::pyclass::PyAsyncContextManager::aenter(self, py)
+                }
+            },
+            syn::parse_quote_spanned! { span =>
+                #[pyo3(name = "__aexit__")]
+                fn __pyo3__aexit__(
+                    &mut self,
+                    py: _pyo3::Python<'_>,
+                    exc_type: ::std::option::Option<&_pyo3::PyAny>,
+                    exc_value: ::std::option::Option<&_pyo3::PyAny>,
+                    traceback: ::std::option::Option<&_pyo3::PyAny>,
+                ) -> _pyo3::PyResult<_pyo3::PyObject> {
+                    let _ = (exc_type, traceback);
+                    let error = exc_value.map(_pyo3::PyErr::from_value);
+                    _pyo3::pyclass::PyAsyncContextManager::aexit(self, py, error)
+                }
+            },
+        ]
+    } else {
+        vec![
+            syn::parse_quote_spanned! { span =>
+                #[pyo3(name = "__enter__")]
+                fn __pyo3__enter__(
+                    slf: &_pyo3::PyCell<Self>,
+                ) -> _pyo3::PyResult<_pyo3::PyObject> {
+                    let target = _pyo3::pyclass::PyContextManager::enter(&mut *slf.try_borrow_mut()?)?;
+                    ::std::result::Result::Ok(_pyo3::pyclass::IntoEnterTarget::into_enter_target(target, slf))
+                }
+            },
+            syn::parse_quote_spanned! { span =>
+                #[pyo3(name = "__exit__")]
+                fn __pyo3__exit__(
+                    &mut self,
+                    exc_type: ::std::option::Option<&_pyo3::PyAny>,
+                    exc_value: ::std::option::Option<&_pyo3::PyAny>,
+                    traceback: ::std::option::Option<&_pyo3::PyAny>,
+                ) -> _pyo3::PyResult<bool> {
+                    let _ = (exc_type, traceback);
+                    let error = exc_value.map(_pyo3::PyErr::from_value);
+                    _pyo3::pyclass::PyContextManager::exit(self, error)
+                }
+            },
+        ]
+    };
+    Ok(methods)
+}
+
+/// Generates `__class_getitem__` for the `generic` option, so that the class can be subscripted
+/// in annotations.
+fn impl_generic(args: &PyClassArgs) -> Option<syn::ImplItemFn> {
+    let span = args.options.generic?.span;
+    Some(syn::parse_quote_spanned! { span =>
+        #[classmethod]
+        #[pyo3(name = "__class_getitem__")]
+        fn __pyo3__class_getitem__(
+            cls: &_pyo3::types::PyType,
+            item: &_pyo3::PyAny,
+        ) -> _pyo3::PyResult<_pyo3::PyObject> {
+            _pyo3::impl_::generic::class_getitem(cls, item)
+        }
     })
 }
 
+/// The contents of a class saved by the `pickle` option, when not using `serde`.
+enum PickleState<'a> {
+    /// The fields of a struct, saved in a dict by their Python names.
+    Fields(&'a [(&'a syn::Field, FieldPyO3Options)]),
+    /// The variants of an enum, saved by their Python names.
+    Variants(&'a [PyClassEnumVariant<'a>]),
+}
+
+/// Generates `__getstate__`, `__setstate__` and `__reduce__` for the `pickle` option.
+///
+/// `__reduce__` recreates the object with the `_pyo3_unpickle` classmethod, which builds it from
+/// its state without calling `#[new]`, so frozen classes and classes without a constructor can be
+/// unpickled too. The returned helpers, which convert a state back to a Rust value, are not Python
+/// methods.
+fn impl_pickle(
+    cls: &syn::Ident,
+    args: &PyClassArgs,
+    state: PickleState<'_>,
+) -> Result<Option<(Vec<syn::ImplItemFn>, Vec<syn::ImplItemFn>)>> {
+    let pickle = match &args.options.pickle {
+        Some(pickle) => pickle,
+        None => return Ok(None),
+    };
+    let class_name = get_class_python_name(cls, args).to_string();
+
+    let mut helpers: Vec<syn::ImplItemFn> = Vec::new();
+    let (get_state, from_state) = if let Some(serde) = &pickle.serde {
+        // Both conversions come from a single invocation, so that a missing feature is reported
+        // once.
+        let serde_state = quote_spanned!(serde.span=> _pyo3::pickle_serde_state!());
+        helpers.push(syn::parse_quote! {
+            fn __pyo3__serde_state__() -> _pyo3::impl_::pickle::SerdeState<Self> {
+                #serde_state
+            }
+        });
+        (
+            quote!((Self::__pyo3__serde_state__().serialize)(py, self)),
+            quote!((Self::__pyo3__serde_state__().deserialize)(state)),
+        )
+    } else {
+        match state {
+            PickleState::Fields(fields) => {
+                let mut items = Vec::new();
+                let mut members = Vec::new();
+                for (index, (field, options)) in fields.iter().enumerate() {
+                    let member = match &field.ident {
+                        Some(ident) => syn::Member::Named(ident.clone()),
+                        None => syn::Member::Unnamed(index.into()),
+                    };
+                    let name = match (&options.name, &field.ident) {
+                        (Some(name), _) => name.value.0.to_string(),
+                        (None, Some(ident)) => ident.unraw().to_string(),
+                        (None, None) => index.to_string(),
+                    };
+                    ensure_spanned!(
+                        options.get.is_some(),
+                        field.span() => format!(
+                            "field `{}` needs `#[pyo3(get)]` to be pickled, or use `pickle(serde)` instead",
+                            name
+                        )
+                    );
+                    items.push(quote! {
+                        state.set_item(
+                            #name,
+                            _pyo3::IntoPy::<_pyo3::PyObject>::into_py(::std::clone::Clone::clone(&self.#member), py),
+                        )?;
+                    });
+                    members.push(quote! {
+                        #member: _pyo3::impl_::pickle::extract_state_item(state, #class_name, #name)?
+                    });
+                }
+                (
+                    quote! {
+                        let state = _pyo3::types::PyDict::new(py);
+                        #(#items)*
+                        ::std::result::Result::Ok(state.into())
+                    },
+                    if members.is_empty() {
+                        quote! {
+                            let _: &_pyo3::types::PyDict = state.downcast()?;
+                            ::std::result::Result::Ok(Self {})
+                        }
+                    } else {
+                        quote! {
+                            let state: &_pyo3::types::PyDict = state.downcast()?;
+                            ::std::result::Result::Ok(Self { #(#members),* })
+                        }
+                    },
+                )
+            }
+            PickleState::Variants(variants) => {
+                let to_name = variants.iter().map(|variant| {
+                    let ident = variant.ident;
+                    let name = variant.python_name().to_string();
+                    quote!(#cls::#ident => #name,)
+                });
+                let from_name = variants.iter().map(|variant| {
+                    let ident = variant.ident;
+                    let name = variant.python_name().to_string();
+                    quote!(#name => ::std::result::Result::Ok(#cls::#ident),)
+                });
+                (
+                    quote! {
+                        let name = match self {
+                            #(#to_name)*
+                        };
+                        ::std::result::Result::Ok(_pyo3::IntoPy::into_py(name, py))
+                    },
+                    quote! {
+                        match state.extract::<&str>()? {
+                            #(#from_name)*
+                            name => ::std::result::Result::Err(
+                                _pyo3::impl_::pickle::unknown_variant(#class_name, name)
+                            ),
+                        }
+                    },
+                )
+            }
+        }
+    };
+
+    let mut methods: Vec<syn::ImplItemFn> = vec![
+        syn::parse_quote! {
+            #[pyo3(name = "__getstate__")]
+            fn __pyo3__getstate__(&self, py: _pyo3::Python<'_>) -> _pyo3::PyResult<_pyo3::PyObject> {
+                #get_state
+            }
+        },
+        syn::parse_quote! {
+            #[pyo3(name = "__reduce__")]
+            fn __pyo3__reduce__(slf: &_pyo3::PyCell<Self>) -> _pyo3::PyResult<_pyo3::PyObject> {
+                _pyo3::impl_::pickle::reduce(slf)
+            }
+        },
+        syn::parse_quote! {
+            #[classmethod]
+            #[pyo3(name = "_pyo3_unpickle")]
+            fn __pyo3__unpickle__(
+                cls: &_pyo3::types::PyType,
+                state: &_pyo3::PyAny,
+            ) -> _pyo3::PyResult<_pyo3::PyObject> {
+                _pyo3::impl_::pickle::unpickle::<Self>(cls, Self::__pyo3__from_state__(state)?)
+            }
+        },
+    ];
+    if args.options.frozen.is_none() {
+        methods.push(syn::parse_quote! {
+            #[pyo3(name = "__setstate__")]
+            fn __pyo3__setstate__(&mut self, state: &_pyo3::PyAny) -> _pyo3::PyResult<()> {
+                *self = Self::__pyo3__from_state__(state)?;
+                ::std::result::Result::Ok(())
+            }
+        });
+    }
+    helpers.push(syn::parse_quote! {
+        fn __pyo3__from_state__(state: &_pyo3::PyAny) -> _pyo3::PyResult<Self> {
+            #from_state
+        }
+    });
+    Ok(Some((methods, helpers)))
+}
+
+/// Generates the slots for the options implementing Python methods with Rust traits.
+fn impl_derived_slots(
+    cls: &syn::Ident,
+    args: &PyClassArgs,
+) -> Result<(Vec<syn::ImplItemFn>, Vec<MethodAndSlotDef>)> {
+    let (mut methods, mut slots) = impl_comparison_slots(cls, &args.options)?;
+    let (string_methods, string_slots) = impl_string_slots(cls, args)?;
+    methods.extend(string_methods);
+    slots.extend(string_slots);
+    Ok((methods, slots))
+}
+
+/// Generates `__richcmp__` and `__hash__` from the Rust trait impls for the `eq`, `ord` and
+/// `hash` options.
+fn impl_comparison_slots(
+    cls: &syn::Ident,
+    options: &PyClassPyO3Options,
+) -> Result<(Vec<syn::ImplItemFn>, Vec<MethodAndSlotDef>)> {
+    if let Some(ord) = &options.ord {
+        ensure_spanned!(options.eq.is_some(), ord.span() => "`ord` requires the `eq` option");
+    }
+    if let Some(hash) = &options.hash {
+        ensure_spanned!(options.eq.is_some(), hash.span() => "`hash` requires the `eq` option");
+        ensure_spanned!(
+            options.frozen.is_some(),
+            hash.span() => "`hash` requires the `frozen` option, as the hash of a mutable object could change"
+        );
+    }
+
+    let ty: syn::Type = syn::parse_quote!(#cls);
+    let mut methods = Vec::new();
+    let mut slots = Vec::new();
+
+    if let Some(eq) = &options.eq {
+        let eq_arms = quote_spanned! { eq.span() =>
+            _pyo3::basic::CompareOp::Eq => self == other,
+            _pyo3::basic::CompareOp::Ne => self != other,
+        };
+        let ord_arms = match &options.ord {
+            Some(ord) => quote_spanned! { ord.span() =>
+                _pyo3::basic::CompareOp::Lt
+                | _pyo3::basic::CompareOp::Le
+                | _pyo3::basic::CompareOp::Gt
+                | _pyo3::basic::CompareOp::Ge => ::std::cmp::PartialOrd::partial_cmp(self, other)
+                    .map_or(false, |ordering| op.matches(ordering)),
+            },
+            None => quote! {
+                _ => return ::std::result::Result::Ok(py.NotImplemented()),
+            },
+        };
+        let mut richcmp_impl: syn::ImplItemFn = syn::parse_quote! {
+            fn __pyo3__richcmp__(
+                &self,
+                py: _pyo3::Python,
+                other: &_pyo3::PyAny,
+                op: _pyo3::basic::CompareOp
+            ) -> _pyo3::PyResult<_pyo3::PyObject> {
+                let other = match other.downcast::<_pyo3::PyCell<Self>>() {
+                    ::std::result::Result::Ok(other) => other.try_borrow()?,
+                    ::std::result::Result::Err(_) => return ::std::result::Result::Ok(py.NotImplemented()),
+                };
+                let other: &Self = &other;
+                let result = match op {
+                    #eq_arms
+                    #ord_arms
+                };
+                ::std::result::Result::Ok(_pyo3::IntoPy::into_py(result, py))
+            }
+        };
+        slots.push(generate_default_protocol_slot(
+            &ty,
+            &mut richcmp_impl,
+            &__RICHCMP__,
+        )?);
+        methods.push(richcmp_impl);
+    }
+
+    if let Some(hash) = &options.hash {
+        let mut hash_impl: syn::ImplItemFn = syn::parse_quote_spanned! { hash.span() =>
+            fn __pyo3__hash__(&self) -> u64 {
+                let mut hasher = ::std::collections::hash_map::DefaultHasher::new();
+                ::std::hash::Hash::hash(self, &mut hasher);
+                ::std::hash::Hasher::finish(&hasher)
+            }
+        };
+        slots.push(generate_default_protocol_slot(
+            &ty,
+            &mut hash_impl,
+            &__HASH__,
+        )?);
+        methods.push(hash_impl);
+    }
+
+    Ok((methods, slots))
+}
+
+/// Generates `__str__` from `Display` and `__repr__` from `Debug` or a template for the `str` and
+/// `repr` options.
+fn impl_string_slots(
+    cls: &syn::Ident,
+    args: &PyClassArgs,
+) -> Result<(Vec<syn::ImplItemFn>, Vec<MethodAndSlotDef>)> {
+    let ty: syn::Type = syn::parse_quote!(#cls);
+    let mut methods = Vec::new();
+    let mut slots = Vec::new();
+
+    if let Some(str) = &args.options.str {
+        let mut str_impl: syn::ImplItemFn = syn::parse_quote_spanned! { str.span() =>
+            fn __pyo3__str__(&self) -> ::std::string::String {
+                ::std::string::ToString::to_string(self)
+            }
+        };
+        slots.push(generate_default_protocol_slot(
+            &ty,
+            &mut str_impl,
+            &__STR__,
+        )?);
+        methods.push(str_impl);
+    }
+
+    if let Some(repr) = &args.options.repr {
+        let format = match &repr.template {
+            Some(template) => {
+                ensure_spanned!(
+                    args.class_kind == PyClassKind::Struct,
+                    template.span() => "`repr` templates cannot be used on enums"
+                );
+                let (template, fields) = parse_repr_template(template)?;
+                let args = fields
+                    .iter()
+                    .map(|(name, member)| quote_spanned!(template.span()=> #name = self.#member));
+                quote_spanned!(template.span()=> ::std::format!(#template, #(#args),*))
+            }
+            None => quote_spanned!(repr.span()=> ::std::format!("{:?}", self)),
+        };
+        let mut repr_impl: syn::ImplItemFn = syn::parse_quote! {
+            fn __pyo3__repr__(&self) -> ::std::string::String {
+                #format
+            }
+        };
+        slots.push(generate_default_protocol_slot(
+            &ty,
+            &mut repr_impl,
+            &__REPR__,
+        )?);
+        methods.push(repr_impl);
+    }
+
+    Ok((methods, slots))
+}
+
+/// Finds the fields used by a `repr` template, returning the template with the placeholders
+/// of tuple struct fields renamed to `_0`, `_1`, etc. so that all of them are named arguments.
+fn parse_repr_template(
+    template: &syn::LitStr,
+) -> Result<(syn::LitStr, Vec<(syn::Ident, syn::Member)>)> {
+    let value = template.value();
+    let mut rewritten = String::with_capacity(value.len());
+    let mut fields: Vec<(syn::Ident, syn::Member)> = Vec::new();
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        rewritten.push(c);
+        if c == '}' && chars.peek() == Some(&'}') {
+            rewritten.push(chars.next().unwrap());
+        } else if c == '{' {
+            if chars.peek() == Some(&'{') {
+                rewritten.push(chars.next().unwrap());
+                continue;
+            }
+            let mut field = String::new();
+            while let Some(&c) = chars.peek() {
+                if c == ':' || c == '}' {
+                    break;
+                }
+                field.push(c);
+                chars.next();
+            }
+            let field = field.trim();
+            let (name, member) = if !field.is_empty() && field.bytes().all(|b| b.is_ascii_digit()) {
+                let index = syn::Index {
+                    index: field.parse().unwrap(),
+                    span: template.span(),
+                };
+                (
+                    syn::Ident::new(&format!("_{}", field), template.span()),
+                    syn::Member::Unnamed(index),
+                )
+            } else {
+                match syn::parse_str::<syn::Ident>(field) {
+                    Ok(ident) => {
+                        let ident = syn::Ident::new(&ident.to_string(), template.span());
+                        (ident.clone(), syn::Member::Named(ident))
+                    }
+                    Err(_) => bail_spanned!(
+                        template.span() =>
+                        format!("`{{{}}}` in the `repr` template must be the name of a field, such as `{{x}}` or `{{0}}`", field)
+                    ),
+                }
+            };
+            rewritten.push_str(&name.to_string());
+            if !fields.iter().any(|(existing, _)| *existing == name) {
+                fields.push((name, member));
+            }
+        }
+    }
+    Ok((syn::LitStr::new(&rewritten, template.span()), fields))
+}
+
 fn generate_default_protocol_slot(
     cls: &syn::Type,
     method: &mut syn::ImplItemFn,
@@ -685,6 +1937,7 @@ fn descriptors_to_items(
             name: Some(name),
             get: None,
             set: None,
+            ..
         } = options
         {
             return Err(syn::Error::new_spanned(name, USELESS_NAME));
@@ -861,11 +2114,17 @@ impl<'a> PyClassImplsBuilder<'a> {
         let attr = self.attr;
         // If #cls is not extended type, we allow Self->PyObject conversion
         if attr.options.extends.is_none() {
+            // The `generic` option also describes the class as generic under `experimental-inspect`
+            let type_output = attr.options.generic.map(
+                |generic| quote_spanned!(generic.span=> _pyo3::pyclass_generic_type_output!(#cls);),
+            );
             quote! {
                 impl _pyo3::IntoPy<_pyo3::PyObject> for #cls {
                     fn into_py(self, py: _pyo3::Python) -> _pyo3::PyObject {
                         _pyo3::IntoPy::into_py(_pyo3::Py::new(py, self).unwrap(), py)
                     }
+
+                    #type_output
                 }
             }
         } else {
@@ -925,6 +2184,19 @@ impl<'a> PyClassImplsBuilder<'a> {
             TokenStream::new()
         };
 
+        let metaclass = if let Some(metaclass) = &self.attr.options.metaclass {
+            let metaclass = &metaclass.value;
+            let metaclass_type_object =
+                quote_spanned!(metaclass.span()=> _pyo3::pyclass_metaclass!(py, #metaclass));
+            quote! {
+                fn metaclass(py: _pyo3::Python<'_>) -> ::std::option::Option<*mut _pyo3::ffi::PyTypeObject> {
+                    #metaclass_type_object
+                }
+            }
+        } else {
+            TokenStream::new()
+        };
+
         let thread_checker = if self.attr.options.unsendable.is_some() {
             quote! { _pyo3::impl_::pyclass::ThreadCheckerImpl<#cls> }
         } else if self.attr.options.extends.is_some() {
@@ -958,6 +2230,16 @@ impl<'a> PyClassImplsBuilder<'a> {
             }
         };
 
+        let pymethods_groups = self.attr.options.pymethods_groups.as_ref().map(|attr| {
+            let group_fns = attr.groups.iter().map(pymethods_group_fn);
+            quote! {
+                .with_pymethods_groups({
+                    static GROUPS: &[fn() -> &'static PyClassItems] = &[#(#cls::#group_fns),*];
+                    GROUPS
+                })
+            }
+        });
+
         let default_methods = self
             .default_methods
             .iter()
@@ -1029,7 +2311,7 @@ impl<'a> PyClassImplsBuilder<'a> {
                         methods: &[#(#default_method_defs),*],
                         slots: &[#(#default_slot_defs),* #(#freelist_slots),*],
                     };
-                    PyClassItemsIter::new(&INTRINSIC_ITEMS, #pymethods_items)
+                    PyClassItemsIter::new(&INTRINSIC_ITEMS, #pymethods_items)#pymethods_groups
                 }
 
                 fn doc(py: _pyo3::Python<'_>) -> _pyo3::PyResult<&'static ::std::ffi::CStr>  {
@@ -1045,6 +2327,8 @@ impl<'a> PyClassImplsBuilder<'a> {
 
                 #weaklist_offset
 
+                #metaclass
+
                 fn lazy_type_object() -> &'static _pyo3::impl_::pyclass::LazyTypeObject<Self> {
                     use _pyo3::impl_::pyclass::LazyTypeObject;
                     static TYPE_OBJECT: LazyTypeObject<#cls> = LazyTypeObject::new();
@@ -0,0 +1,286 @@
+use crate::{
+    attributes::{
+        self, get_pyo3_options, CrateAttribute, KeywordAttribute, ModuleAttribute, NameAttribute,
+    },
+    utils::{get_doc, get_pyo3_crate},
+};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    parse::{Parse, ParseStream},
+    spanned::Spanned,
+    Attribute, DeriveInput, Fields, Result, Token,
+};
+
+type BaseAttribute = KeywordAttribute<attributes::kw::base, syn::Path>;
+
+/// Options for `#[derive(PyException)]`, given on the enum.
+#[derive(Default)]
+struct ExceptionOptions {
+    base: Option<BaseAttribute>,
+    krate: Option<CrateAttribute>,
+    module: Option<ModuleAttribute>,
+    name: Option<NameAttribute>,
+}
+
+enum ExceptionOption {
+    Base(BaseAttribute),
+    Crate(CrateAttribute),
+    Module(ModuleAttribute),
+    Name(NameAttribute),
+}
+
+impl Parse for ExceptionOption {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let lookahead = input.lookahead1();
+        if lookahead.peek(attributes::kw::base) {
+            input.parse().map(ExceptionOption::Base)
+        } else if lookahead.peek(Token![crate]) {
+            input.parse().map(ExceptionOption::Crate)
+        } else if lookahead.peek(attributes::kw::module) {
+            input.parse().map(ExceptionOption::Module)
+        } else if lookahead.peek(attributes::kw::name) {
+            input.parse().map(ExceptionOption::Name)
+        } else {
+            Err(lookahead.error())
+        }
+    }
+}
+
+impl ExceptionOptions {
+    fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
+        let mut options = ExceptionOptions::default();
+
+        macro_rules! set_option {
+            ($key:ident) => {
+                {
+                    ensure_spanned!(
+                        options.$key.is_none(),
+                        $key.span() => concat!("`", stringify!($key), "` may only be specified once")
+                    );
+                    options.$key = Some($key);
+                }
+            };
+        }
+
+        for attr in attrs {
+            if let Some(pyo3_attrs) = get_pyo3_options(attr)? {
+                for pyo3_attr in pyo3_attrs {
+                    match pyo3_attr {
+                        ExceptionOption::Base(base) => set_option!(base),
+                        ExceptionOption::Crate(krate) => set_option!(krate),
+                        ExceptionOption::Module(module) => set_option!(module),
+                        ExceptionOption::Name(name) => set_option!(name),
+                    }
+                }
+            }
+        }
+        Ok(options)
+    }
+}
+
+/// Returns the `name` option of a variant, which is the only option variants accept.
+fn variant_name(attrs: &[Attribute]) -> Result<Option<NameAttribute>> {
+    let mut name = None;
+    for attr in attrs {
+        if let Some(pyo3_attrs) = get_pyo3_options::<NameAttribute>(attr)? {
+            for attr in pyo3_attrs {
+                ensure_spanned!(
+                    name.is_none(),
+                    attr.span() => "`name` may only be specified once"
+                );
+                name = Some(attr);
+            }
+        }
+    }
+    Ok(name)
+}
+
+/// Derive `PyExceptionEnum`, `From<Enum> for PyErr` and `FromPyObject` for an error enum.
+///
+///   * No generic parameters.
+///   * At least one variant.
+///   * Fields must implement `IntoPy<PyObject>`, `FromPyObject` and be `Send + Sync`; the enum
+///     itself must implement `Display`, which is used for the exception message.
+pub fn build_derive_py_exception(tokens: &DeriveInput) -> Result<TokenStream> {
+    let data = match &tokens.data {
+        syn::Data::Enum(data) => data,
+        _ => bail_spanned!(tokens.span() => "#[derive(PyException)] is only supported for enums"),
+    };
+    ensure_spanned!(
+        tokens.generics.params.is_empty(),
+        tokens.generics.span() => "#[derive(PyException)] does not support generic enums"
+    );
+    ensure_spanned!(
+        !data.variants.is_empty(),
+        tokens.ident.span() => "cannot derive PyException for empty enum"
+    );
+
+    let options = ExceptionOptions::from_attrs(&tokens.attrs)?;
+    let krate = get_pyo3_crate(&options.krate);
+    let ident = &tokens.ident;
+    let name = options
+        .name
+        .map(|name| name.value.0.to_string())
+        .unwrap_or_else(|| ident.to_string());
+    let module = options
+        .module
+        .map(|module| module.value.value())
+        .unwrap_or_else(|| "builtins".to_string());
+    let base = options
+        .base
+        .map(|base| base.value)
+        .unwrap_or_else(|| syn::parse_quote!(_pyo3::exceptions::PyException));
+    let doc = get_doc(&tokens.attrs, None);
+
+    let mut variant_descriptions = Vec::new();
+    let mut to_exceptions = Vec::new();
+    let mut extracts = Vec::new();
+    for (index, variant) in data.variants.iter().enumerate() {
+        // index 0 is the base exception type
+        let index = index + 1;
+        let var_ident = &variant.ident;
+        let var_name = variant_name(&variant.attrs)?
+            .map(|name| name.value.0.to_string())
+            .unwrap_or_else(|| var_ident.to_string());
+        let var_doc = get_doc(&variant.attrs, None);
+        variant_descriptions.push(quote!((#var_name, #var_doc)));
+
+        let struct_name = format!("{}::{}", ident, var_ident);
+        // The binding of each field in the match pattern, and its exception attribute name
+        let fields: Vec<(syn::Ident, String)> = match &variant.fields {
+            Fields::Named(fields) => fields
+                .named
+                .iter()
+                .map(|field| {
+                    let field = field.ident.clone().unwrap();
+                    let name = field.to_string();
+                    (field, name)
+                })
+                .collect(),
+            Fields::Unnamed(fields) => (0..fields.unnamed.len())
+                .map(|i| {
+                    let field = format_ident!("_{}", i);
+                    let name = field.to_string();
+                    (field, name)
+                })
+                .collect(),
+            Fields::Unit => Vec::new(),
+        };
+        let bindings = fields.iter().map(|(field, _)| field);
+        let (pattern, construct) = match &variant.fields {
+            Fields::Named(_) => {
+                let extract_fields = fields.iter().map(|(field, name)| {
+                    quote!(
+                        #field: _pyo3::impl_::frompyobject::extract_struct_field(
+                            obj.getattr(_pyo3::intern!(py, #name))?,
+                            #struct_name,
+                            #name
+                        )?
+                    )
+                });
+                (
+                    quote!(#ident::#var_ident { #(#bindings),* }),
+                    quote!(#ident::#var_ident { #(#extract_fields),* }),
+                )
+            }
+            Fields::Unnamed(_) => {
+                let extract_fields = fields.iter().enumerate().map(|(i, (_, name))| {
+                    quote!(
+                        _pyo3::impl_::frompyobject::extract_tuple_struct_field(
+                            obj.getattr(_pyo3::intern!(py, #name))?,
+                            #struct_name,
+                            #i
+                        )?
+                    )
+                });
+                (
+                    quote!(#ident::#var_ident(#(#bindings),*)),
+                    quote!(#ident::#var_ident(#(#extract_fields),*)),
+                )
+            }
+            Fields::Unit => (quote!(#ident::#var_ident), quote!(#ident::#var_ident)),
+        };
+
+        let set_attributes = fields.iter().map(|(field, name)| {
+            quote!(
+                exception.setattr(
+                    _pyo3::intern!(py, #name),
+                    _pyo3::IntoPy::<_pyo3::PyObject>::into_py(#field, py),
+                )?;
+            )
+        });
+        to_exceptions.push(quote!(
+            #pattern => {
+                let exception = types[#index].as_ref(py).call1((message,))?;
+                #(#set_attributes)*
+                ::std::result::Result::Ok(exception)
+            }
+        ));
+        extracts.push(quote!(
+            if obj.is_instance(types[#index].as_ref(py))? {
+                return ::std::result::Result::Ok(#construct);
+            }
+        ));
+    }
+
+    Ok(quote!(
+        const _: () = {
+            use #krate as _pyo3;
+
+            fn exception_types(
+                py: _pyo3::Python<'_>,
+            ) -> &[_pyo3::Py<_pyo3::types::PyType>] {
+                static TYPES: _pyo3::impl_::pyexception::ExceptionTypes =
+                    _pyo3::impl_::pyexception::ExceptionTypes::new();
+                TYPES.get_or_init(
+                    py,
+                    py.get_type::<#base>(),
+                    #module,
+                    #name,
+                    #doc,
+                    &[#(#variant_descriptions),*],
+                )
+            }
+
+            #[automatically_derived]
+            impl _pyo3::exceptions::PyExceptionEnum for #ident {
+                fn base_type_object(py: _pyo3::Python<'_>) -> &_pyo3::types::PyType {
+                    exception_types(py)[0].as_ref(py)
+                }
+
+                fn variant_type_objects(
+                    py: _pyo3::Python<'_>,
+                ) -> ::std::vec::Vec<&_pyo3::types::PyType> {
+                    exception_types(py)[1..]
+                        .iter()
+                        .map(|ty| ty.as_ref(py))
+                        .collect()
+                }
+            }
+
+            #[automatically_derived]
+            impl ::std::convert::From<#ident> for _pyo3::PyErr {
+                fn from(err: #ident) -> _pyo3::PyErr {
+                    let message = ::std::string::ToString::to_string(&err);
+                    _pyo3::impl_::pyexception::new_err(move |py| {
+                        let types = exception_types(py);
+                        match err {
+                            #(#to_exceptions)*
+                        }
+                    })
+                }
+            }
+
+            #[automatically_derived]
+            impl<'source> _pyo3::FromPyObject<'source> for #ident {
+                fn extract(obj: &'source _pyo3::PyAny) -> _pyo3::PyResult<Self> {
+                    let py = obj.py();
+                    let types = exception_types(py);
+                    #(#extracts)*
+                    ::std::result::Result::Err(_pyo3::PyDowncastError::new(obj, #name).into())
+                }
+            }
+        };
+    ))
+}
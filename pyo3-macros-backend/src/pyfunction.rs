@@ -72,6 +72,7 @@ pub struct PyFunctionOptions {
     pub signature: Option<SignatureAttribute>,
     pub text_signature: Option<TextSignatureAttribute>,
     pub krate: Option<CrateAttribute>,
+    pub overridable: Option<attributes::kw::overridable>,
 }
 
 impl Parse for PyFunctionOptions {
@@ -107,6 +108,7 @@ pub enum PyFunctionOption {
     Signature(SignatureAttribute),
     TextSignature(TextSignatureAttribute),
     Crate(CrateAttribute),
+    Overridable(attributes::kw::overridable),
 }
 
 impl Parse for PyFunctionOption {
@@ -122,6 +124,8 @@ impl Parse for PyFunctionOption {
             input.parse().map(PyFunctionOption::TextSignature)
         } else if lookahead.peek(syn::Token![crate]) {
             input.parse().map(PyFunctionOption::Crate)
+        } else if lookahead.peek(attributes::kw::overridable) {
+            input.parse().map(PyFunctionOption::Overridable)
         } else {
             Err(lookahead.error())
         }
@@ -157,6 +161,7 @@ impl PyFunctionOptions {
                 PyFunctionOption::Signature(signature) => set_option!(signature),
                 PyFunctionOption::TextSignature(text_signature) => set_option!(text_signature),
                 PyFunctionOption::Crate(krate) => set_option!(krate),
+                PyFunctionOption::Overridable(overridable) => set_option!(overridable),
             }
         }
         Ok(())
@@ -186,8 +191,13 @@ pub fn impl_wrap_pyfunction(
         signature,
         text_signature,
         krate,
+        overridable,
     } = options;
 
+    if let Some(overridable) = overridable {
+        bail_spanned!(overridable.span() => "`overridable` can only be used on methods in `#[pymethods]`");
+    }
+
     let python_name = name.map_or_else(|| func.sig.ident.unraw(), |name| name.value.0);
 
     let mut arguments = func
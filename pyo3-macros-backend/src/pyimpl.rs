@@ -1,17 +1,19 @@
 use std::collections::HashSet;
 
 use crate::{
-    attributes::{take_pyo3_options, CrateAttribute},
+    attributes::{self, take_pyo3_options, CrateAttribute, GroupAttribute},
     konst::{ConstAttributes, ConstSpec},
     pyfunction::PyFunctionOptions,
     pymethod::{self, is_proto_method, MethodAndMethodDef, MethodAndSlotDef},
     utils::get_pyo3_crate,
 };
-use proc_macro2::TokenStream;
+use proc_macro2::{Group, Spacing, TokenStream, TokenTree};
 use pymethod::GeneratedPyMethod;
-use quote::{format_ident, quote};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
 use syn::{
+    ext::IdentExt,
     parse::{Parse, ParseStream},
+    punctuated::Punctuated,
     spanned::Spanned,
     Result,
 };
@@ -25,6 +27,8 @@ pub enum PyClassMethodsType {
 
 enum PyImplPyO3Option {
     Crate(CrateAttribute),
+    For(ForAttribute),
+    Group(GroupAttribute),
 }
 
 impl Parse for PyImplPyO3Option {
@@ -32,15 +36,37 @@ impl Parse for PyImplPyO3Option {
         let lookahead = input.lookahead1();
         if lookahead.peek(syn::Token![crate]) {
             input.parse().map(PyImplPyO3Option::Crate)
+        } else if lookahead.peek(syn::Token![for]) {
+            input.parse().map(PyImplPyO3Option::For)
+        } else if lookahead.peek(attributes::kw::group) {
+            input.parse().map(PyImplPyO3Option::Group)
         } else {
             Err(lookahead.error())
         }
     }
 }
 
+/// `for(A, B, ...)`: the `#[pyclass]` types to instantiate a generic trait impl for.
+pub struct ForAttribute {
+    kw: syn::Token![for],
+    types: Punctuated<syn::Type, syn::Token![,]>,
+}
+
+impl Parse for ForAttribute {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let kw = input.parse()?;
+        let content;
+        syn::parenthesized!(content in input);
+        let types = content.parse_terminated(syn::Type::parse, syn::Token![,])?;
+        Ok(ForAttribute { kw, types })
+    }
+}
+
 #[derive(Default)]
 pub struct PyImplOptions {
     krate: Option<CrateAttribute>,
+    for_types: Option<ForAttribute>,
+    group: Option<GroupAttribute>,
 }
 
 impl PyImplOptions {
@@ -50,6 +76,8 @@ impl PyImplOptions {
         for option in take_pyo3_options(attrs)? {
             match option {
                 PyImplPyO3Option::Crate(path) => options.set_crate(path)?,
+                PyImplPyO3Option::For(for_types) => options.set_for_types(for_types)?,
+                PyImplPyO3Option::Group(group) => options.set_group(group)?,
             }
         }
 
@@ -65,23 +93,293 @@ impl PyImplOptions {
         self.krate = Some(path);
         Ok(())
     }
+
+    fn set_for_types(&mut self, for_types: ForAttribute) -> Result<()> {
+        ensure_spanned!(
+            self.for_types.is_none(),
+            for_types.kw.span() => "`for` may only be specified once"
+        );
+        ensure_spanned!(
+            !for_types.types.is_empty(),
+            for_types.kw.span() => "`for` must list at least one type"
+        );
+
+        self.for_types = Some(for_types);
+        Ok(())
+    }
+
+    fn set_group(&mut self, group: GroupAttribute) -> Result<()> {
+        ensure_spanned!(
+            self.group.is_none(),
+            group.kw.span() => "`group` may only be specified once"
+        );
+
+        self.group = Some(group);
+        Ok(())
+    }
 }
 
 pub fn build_py_methods(
     ast: &mut syn::ItemImpl,
     methods_type: PyClassMethodsType,
 ) -> syn::Result<TokenStream> {
-    if let Some((_, path, _)) = &ast.trait_ {
-        bail_spanned!(path.span() => "#[pymethods] cannot be used on trait impl blocks");
-    } else if ast.generics != Default::default() {
-        bail_spanned!(
-            ast.generics.span() =>
-            "#[pymethods] cannot be used with lifetime parameters or generics"
-        );
-    } else {
-        let options = PyImplOptions::from_attrs(&mut ast.attrs)?;
-        impl_methods(&ast.self_ty, &mut ast.items, methods_type, options)
+    let options = PyImplOptions::from_attrs(&mut ast.attrs)?;
+    if ast.trait_.is_some() {
+        return build_trait_py_methods(ast, methods_type, options);
     }
+    if let Some(for_types) = &options.for_types {
+        bail_spanned!(for_types.kw.span() => "`for` can only be used on generic trait impl blocks");
+    }
+    ensure_spanned!(
+        ast.generics == Default::default(),
+        ast.generics.span() => "#[pymethods] cannot be used with lifetime parameters or generics"
+    );
+    impl_methods(&ast.self_ty, &mut ast.items, methods_type, options)
+}
+
+/// Exposes the methods of a trait impl block.
+///
+/// For each `#[pyclass]` implementing the trait, the trait methods are forwarded from hidden
+/// inherent methods, which are then processed like an inherent `#[pymethods]` block.
+fn build_trait_py_methods(
+    ast: &mut syn::ItemImpl,
+    methods_type: PyClassMethodsType,
+    mut options: PyImplOptions,
+) -> syn::Result<TokenStream> {
+    let trait_path = &ast.trait_.as_ref().unwrap().1;
+
+    let (types, type_param) = match options.for_types.take() {
+        Some(for_types) => {
+            let type_param = match (ast.generics.params.first(), &*ast.self_ty) {
+                (Some(syn::GenericParam::Type(param)), syn::Type::Path(self_ty))
+                    if ast.generics.params.len() == 1 && self_ty.path.is_ident(&param.ident) =>
+                {
+                    param.ident.clone()
+                }
+                _ => bail_spanned!(
+                    ast.generics.span() =>
+                    "`for` can only be used on trait impl blocks of the form `impl<T> Trait for T`"
+                ),
+            };
+            (for_types.types.into_iter().collect(), Some(type_param))
+        }
+        None => {
+            ensure_spanned!(
+                ast.generics == Default::default(),
+                ast.generics.span() =>
+                "#[pymethods] on a generic trait impl block needs `#[pymethods(for(...))]` to list the \
+                 #[pyclass] types to implement the methods for"
+            );
+            (vec![(*ast.self_ty).clone()], None)
+        }
+    };
+
+    let trait_path = replace_self_tokens(
+        trait_path.to_token_stream(),
+        &trait_path.to_token_stream(),
+        type_param.as_ref(),
+    );
+    let shim_prefix = format!("__pymethods_{}_", mangle(&trait_path.to_string()));
+
+    // The attributes are removed from the trait impl itself, so that they are not left behind if
+    // there is an error, and restored without the ones used by `#[pymethods]` below.
+    let items = ast.items.clone();
+    for item in &mut ast.items {
+        clear_attributes(item);
+    }
+
+    let mut expanded = Vec::new();
+    let mut processed_items = None;
+    for ty in &types {
+        let mut shims = items
+            .iter()
+            .map(|item| trait_method_shim(item, &trait_path, &shim_prefix, type_param.as_ref()))
+            .collect::<Result<Vec<_>>>()?;
+        let options = PyImplOptions {
+            krate: options.krate.clone(),
+            for_types: None,
+            group: options.group.clone(),
+        };
+        let methods = impl_methods(ty, &mut shims, methods_type, options)?;
+        let shim_fns = shims
+            .iter()
+            .filter(|item| matches!(item, syn::ImplItem::Fn(_)));
+        expanded.push(quote! {
+            #[doc(hidden)]
+            #[allow(non_snake_case)]
+            impl #ty {
+                #(#shim_fns)*
+            }
+
+            #methods
+        });
+        processed_items.get_or_insert(shims);
+    }
+
+    for (item, processed) in ast.items.iter_mut().zip(processed_items.unwrap()) {
+        match (item, processed) {
+            (syn::ImplItem::Fn(meth), syn::ImplItem::Fn(processed)) => {
+                meth.attrs = processed.attrs;
+                for (input, processed) in meth.sig.inputs.iter_mut().zip(processed.sig.inputs) {
+                    if let (syn::FnArg::Typed(input), syn::FnArg::Typed(processed)) =
+                        (input, processed)
+                    {
+                        input.attrs = processed.attrs;
+                    }
+                }
+            }
+            (item, processed) => *item = processed,
+        }
+    }
+
+    Ok(quote!(#(#expanded)*))
+}
+
+fn clear_attributes(item: &mut syn::ImplItem) {
+    match item {
+        syn::ImplItem::Fn(meth) => {
+            meth.attrs.clear();
+            for input in &mut meth.sig.inputs {
+                if let syn::FnArg::Typed(input) = input {
+                    input.attrs.clear();
+                }
+            }
+        }
+        syn::ImplItem::Const(konst) => konst.attrs.clear(),
+        _ => {}
+    }
+}
+
+/// Creates an inherent method which forwards to the trait method `item`, with the same Python
+/// name and attributes.
+fn trait_method_shim(
+    item: &syn::ImplItem,
+    trait_path: &TokenStream,
+    shim_prefix: &str,
+    type_param: Option<&syn::Ident>,
+) -> Result<syn::ImplItem> {
+    let meth = match item {
+        syn::ImplItem::Fn(meth) => meth,
+        syn::ImplItem::Const(konst) => {
+            if let Some(attr) = konst
+                .attrs
+                .iter()
+                .find(|attr| attr.path().is_ident("classattr"))
+            {
+                bail_spanned!(attr.span() => "#[classattr] cannot be used in trait impl blocks");
+            }
+            return Ok(item.clone());
+        }
+        other => return Ok(other.clone()),
+    };
+
+    let mut attrs = meth.attrs.clone();
+    let options = PyFunctionOptions::from_attrs(&mut attrs.clone())?;
+    if let Some(overridable) = options.overridable {
+        bail_spanned!(overridable.span() => "`overridable` cannot be used in trait impl blocks");
+    }
+    if options.name.is_none() {
+        if let Some(python_name) = default_python_name(meth) {
+            attrs.push(syn::parse_quote!(#[pyo3(name = #python_name)]));
+        }
+    }
+
+    let sig_tokens = replace_self_tokens(meth.sig.to_token_stream(), trait_path, type_param);
+    let mut sig: syn::Signature = syn::parse2(sig_tokens)?;
+    let rust_name = &meth.sig.ident;
+    sig.ident = format_ident!("{}{}", shim_prefix, rust_name.unraw());
+
+    let args = sig.inputs.iter().map(|input| match input {
+        syn::FnArg::Receiver(receiver) => receiver.self_token.to_token_stream(),
+        syn::FnArg::Typed(pat_type) => match &*pat_type.pat {
+            syn::Pat::Ident(pat) => pat.ident.to_token_stream(),
+            // rejected by `FnArg::parse`
+            other => other.to_token_stream(),
+        },
+    });
+
+    Ok(syn::ImplItem::Fn(syn::ImplItemFn {
+        attrs,
+        vis: syn::Visibility::Inherited,
+        defaultness: None,
+        block: syn::parse_quote!({ <Self as #trait_path>::#rust_name(#(#args),*) }),
+        sig,
+    }))
+}
+
+/// The Python name which `#[pymethods]` would give to `meth` in an inherent impl block, or `None`
+/// if it does not depend on the Rust name.
+fn default_python_name(meth: &syn::ImplItemFn) -> Option<String> {
+    let name = meth.sig.ident.unraw().to_string();
+    for attr in &meth.attrs {
+        match &attr.meta {
+            syn::Meta::Path(path) if path.is_ident("new") || path.is_ident("__new__") => {
+                return None
+            }
+            syn::Meta::Path(path) if path.is_ident("getter") => {
+                return Some(name.strip_prefix("get_").unwrap_or(&name).to_owned())
+            }
+            syn::Meta::Path(path) if path.is_ident("setter") => {
+                return Some(name.strip_prefix("set_").unwrap_or(&name).to_owned())
+            }
+            syn::Meta::List(list)
+                if list.path.is_ident("getter") || list.path.is_ident("setter") =>
+            {
+                return None
+            }
+            _ => {}
+        }
+    }
+    Some(name)
+}
+
+/// Replaces `Self::Item` with `<Self as Trait>::Item`, as associated types of `Self` are ambiguous
+/// in an inherent impl block, and replaces the type parameter of a generic impl with `Self`.
+fn replace_self_tokens(
+    tokens: TokenStream,
+    trait_path: &TokenStream,
+    type_param: Option<&syn::Ident>,
+) -> TokenStream {
+    let mut output = TokenStream::new();
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Group(group) => {
+                let stream = replace_self_tokens(group.stream(), trait_path, type_param);
+                let mut replaced = Group::new(group.delimiter(), stream);
+                replaced.set_span(group.span());
+                output.extend(Some(TokenTree::Group(replaced)));
+            }
+            TokenTree::Ident(ident)
+                if ident == "Self"
+                    && matches!(
+                        tokens.peek(),
+                        Some(TokenTree::Punct(punct))
+                            if punct.as_char() == ':' && punct.spacing() == Spacing::Joint
+                    ) =>
+            {
+                output.extend(quote_spanned!(ident.span() => <Self as #trait_path>));
+            }
+            TokenTree::Ident(ident) if Some(&ident) == type_param => {
+                output.extend(quote_spanned!(ident.span() => Self));
+            }
+            other => output.extend(Some(other)),
+        }
+    }
+    output
+}
+
+/// Turns a path into a string usable in an identifier.
+fn mangle(path: &str) -> String {
+    let mut mangled = String::new();
+    for c in path.chars() {
+        if c.is_ascii_alphanumeric() {
+            mangled.push(c);
+        } else if !c.is_whitespace() && !mangled.ends_with('_') {
+            mangled.push('_');
+        }
+    }
+    mangled.trim_end_matches('_').to_owned()
 }
 
 pub fn impl_methods(
@@ -94,6 +392,7 @@ pub fn impl_methods(
     let mut proto_impls = Vec::new();
     let mut methods = Vec::new();
     let mut associated_methods = Vec::new();
+    let mut dispatchers = Vec::new();
 
     let mut implemented_proto_fragments = HashSet::new();
 
@@ -102,6 +401,12 @@ pub fn impl_methods(
             syn::ImplItem::Fn(meth) => {
                 let mut fun_options = PyFunctionOptions::from_attrs(&mut meth.attrs)?;
                 fun_options.krate = fun_options.krate.or_else(|| options.krate.clone());
+                let overridable_name = fun_options.overridable.map(|_| {
+                    fun_options
+                        .name
+                        .as_ref()
+                        .map_or_else(|| meth.sig.ident.unraw(), |name| name.value.0.clone())
+                });
                 match pymethod::gen_py_method(ty, &mut meth.sig, &mut meth.attrs, fun_options)? {
                     GeneratedPyMethod::Method(MethodAndMethodDef {
                         associated_method,
@@ -111,6 +416,18 @@ pub fn impl_methods(
                         associated_methods.push(quote!(#(#attrs)* #associated_method));
                         methods.push(quote!(#(#attrs)* #method_def));
                     }
+                    GeneratedPyMethod::MethodAndTraitImpl(
+                        MethodAndMethodDef {
+                            associated_method,
+                            method_def,
+                        },
+                        trait_impl,
+                    ) => {
+                        let attrs = get_cfg_attributes(&meth.attrs);
+                        associated_methods.push(quote!(#(#attrs)* #associated_method));
+                        methods.push(quote!(#(#attrs)* #method_def));
+                        trait_impls.push(quote!(#(#attrs)* #trait_impl));
+                    }
                     GeneratedPyMethod::SlotTraitImpl(method_name, token_stream) => {
                         implemented_proto_fragments.insert(method_name);
                         let attrs = get_cfg_attributes(&meth.attrs);
@@ -125,6 +442,12 @@ pub fn impl_methods(
                         associated_methods.push(quote!(#(#attrs)* #associated_method));
                     }
                 }
+                if let Some(python_name) = overridable_name {
+                    let attrs = get_cfg_attributes(&meth.attrs);
+                    let dispatcher =
+                        pymethod::impl_overridable_dispatcher(&meth.vis, &meth.sig, &python_name);
+                    dispatchers.push(quote!(#(#attrs)* #dispatcher));
+                }
             }
             syn::ImplItem::Const(konst) => {
                 let attributes = ConstAttributes::from_attrs(&mut konst.attrs)?;
@@ -157,9 +480,10 @@ pub fn impl_methods(
 
     let krate = get_pyo3_crate(&options.krate);
 
-    let items = match methods_type {
-        PyClassMethodsType::Specialization => impl_py_methods(ty, methods, proto_impls),
-        PyClassMethodsType::Inventory => submit_methods_inventory(ty, methods, proto_impls),
+    let items = match (&options.group, methods_type) {
+        (Some(group), _) => impl_py_methods_group(ty, &group.value, methods, proto_impls),
+        (None, PyClassMethodsType::Specialization) => impl_py_methods(ty, methods, proto_impls),
+        (None, PyClassMethodsType::Inventory) => submit_methods_inventory(ty, methods, proto_impls),
     };
 
     Ok(quote! {
@@ -175,6 +499,10 @@ pub fn impl_methods(
             impl #ty {
                 #(#associated_methods)*
             }
+
+            impl #ty {
+                #(#dispatchers)*
+            }
         };
     })
 }
@@ -227,7 +555,34 @@ fn impl_py_methods(
     }
 }
 
-fn add_shared_proto_slots(
+/// The name of the function returning the items of a `#[pymethods(group = ...)]` block.
+pub fn pymethods_group_fn(group: &syn::Ident) -> syn::Ident {
+    format_ident!("__pymethods_group_{}", group.unraw(), span = group.span())
+}
+
+fn impl_py_methods_group(
+    ty: &syn::Type,
+    group: &syn::Ident,
+    methods: Vec<TokenStream>,
+    proto_impls: Vec<TokenStream>,
+) -> TokenStream {
+    let group_fn = pymethods_group_fn(group);
+    quote! {
+        impl #ty {
+            #[doc(hidden)]
+            #[allow(non_snake_case)]
+            pub(crate) fn #group_fn() -> &'static _pyo3::impl_::pyclass::PyClassItems {
+                static ITEMS: _pyo3::impl_::pyclass::PyClassItems = _pyo3::impl_::pyclass::PyClassItems {
+                    methods: &[#(#methods),*],
+                    slots: &[#(#proto_impls),*]
+                };
+                &ITEMS
+            }
+        }
+    }
+}
+
+pub fn add_shared_proto_slots(
     ty: &syn::Type,
     proto_impls: &mut Vec<TokenStream>,
     mut implemented_proto_fragments: HashSet<String>,
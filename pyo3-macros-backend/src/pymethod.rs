@@ -9,7 +9,7 @@ use crate::{
 };
 use crate::{quotes, utils};
 use proc_macro2::{Span, TokenStream};
-use quote::{format_ident, quote, ToTokens};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
 use syn::{ext::IdentExt, spanned::Spanned, Result};
 
 /// Generated code for a single pymethod item.
@@ -30,6 +30,8 @@ pub struct MethodAndSlotDef {
 
 pub enum GeneratedPyMethod {
     Method(MethodAndMethodDef),
+    /// A method which also implements a marker trait for the class, at item level.
+    MethodAndTraitImpl(MethodAndMethodDef, TokenStream),
     Proto(MethodAndSlotDef),
     SlotTraitImpl(String, TokenStream),
 }
@@ -190,9 +192,22 @@ pub fn gen_py_method(
     check_generic(sig)?;
     ensure_not_async_fn(sig)?;
     ensure_function_options_valid(&options)?;
+    let overridable = options.overridable;
     let method = PyMethod::parse(sig, meth_attrs, options)?;
     let spec = &method.spec;
 
+    if let Some(overridable) = overridable {
+        ensure_spanned!(
+            matches!(
+                (&method.kind, &spec.tp),
+                (PyMethodKind::Fn, FnType::Fn(SelfType::Receiver { .. }))
+            ),
+            overridable.span() => "`overridable` can only be used on methods taking `&self` or `&mut self`"
+        );
+    }
+
+    ensure_hook_receiver(spec, &method.method_name)?;
+
     Ok(match (method.kind, &spec.tp) {
         // Class attributes go before protos so that class attributes can be used to set proto
         // method to None.
@@ -218,6 +233,11 @@ pub fn gen_py_method(
                 }
             }
         }
+        (_, FnType::FnClass) if method.method_name == "__class_getitem__" => {
+            let (method_def, trait_impl) =
+                impl_class_getitem(cls, spec, &spec.get_doc(meth_attrs))?;
+            GeneratedPyMethod::MethodAndTraitImpl(method_def, trait_impl)
+        }
         // ordinary functions (with some specialties)
         (_, FnType::Fn(_)) => GeneratedPyMethod::Method(impl_py_method_def(
             cls,
@@ -264,6 +284,112 @@ pub fn gen_py_method(
     })
 }
 
+/// Generates `dispatch_<name>` for a `#[pyo3(overridable)]` method, which calls the Python
+/// override of the method if the type of the instance has one, and the Rust method otherwise.
+///
+/// The signature must already have been validated by `gen_py_method`.
+pub fn impl_overridable_dispatcher(
+    vis: &syn::Visibility,
+    sig: &syn::Signature,
+    python_name: &syn::Ident,
+) -> TokenStream {
+    let rust_name = &sig.ident;
+    let dispatcher_ident = format_ident!("dispatch_{}", rust_name.unraw());
+    let python_name = python_name.to_string();
+    let slf = syn::Ident::new("slf", Span::mixed_site());
+    let py = syn::Ident::new("py", Span::mixed_site());
+    let borrow = syn::Ident::new("borrow", Span::mixed_site());
+
+    let mut params = Vec::new();
+    let mut python_args = Vec::new();
+    let mut rust_args = Vec::new();
+    for input in sig.inputs.iter().skip(1) {
+        let (pat, ty) = match input {
+            syn::FnArg::Typed(syn::PatType { pat, ty, .. }) => (pat, ty),
+            syn::FnArg::Receiver(_) => unreachable!("only the first argument can be a receiver"),
+        };
+        let ident = match &**pat {
+            syn::Pat::Ident(pat) => &pat.ident,
+            _ => unreachable!("argument patterns are checked by FnArg::parse"),
+        };
+        if utils::is_python(ty) {
+            rust_args.push(quote!(#py));
+            continue;
+        }
+        params.push(quote!(#ident: #ty));
+        python_args.push(match &**ty {
+            syn::Type::Reference(_) => quote!(_pyo3::ToPyObject::to_object(&*#ident, #py)),
+            _ => quote!(_pyo3::IntoPy::<_pyo3::PyObject>::into_py(#ident, #py)),
+        });
+        rust_args.push(quote!(#ident));
+    }
+
+    let (borrow_slf, receiver) = match sig.receiver() {
+        Some(receiver) if receiver.mutability.is_some() => (
+            quote!(let mut #borrow = #slf.try_borrow_mut()?;),
+            quote!(&mut *#borrow),
+        ),
+        _ => (quote!(let #borrow = #slf.try_borrow()?;), quote!(&*#borrow)),
+    };
+    let call_python = quote! {
+        #slf.call_method1(_pyo3::intern!(#py, #python_name), (#(#python_args,)*))
+    };
+    let call_rust = quote!(Self::#rust_name(#receiver, #(#rust_args),*));
+    let unit = syn::parse_quote!(());
+    let (output, python_result, rust_result) = match &sig.output {
+        syn::ReturnType::Type(_, ty) if !utils::is_unit(ty) => match utils::result_ok_type(ty) {
+            Some(ok) if utils::is_unit(ok) => (
+                ok,
+                quote!(#call_python.map(::std::mem::drop)),
+                quote!(#call_rust.map_err(::std::convert::Into::into)),
+            ),
+            Some(ok) => (
+                ok,
+                quote!(#call_python.and_then(_pyo3::PyAny::extract)),
+                quote!(#call_rust.map_err(::std::convert::Into::into)),
+            ),
+            None => (
+                &**ty,
+                quote!(#call_python.and_then(_pyo3::PyAny::extract)),
+                quote!(::std::result::Result::Ok(#call_rust)),
+            ),
+        },
+        _ => (
+            &unit,
+            quote!(#call_python.map(::std::mem::drop)),
+            quote!({
+                #call_rust;
+                ::std::result::Result::Ok(())
+            }),
+        ),
+    };
+
+    let doc = format!(
+        "Calls [`Self::{}`], or the override of `{}` if `slf` is an instance of a Python subclass \
+         which overrides it.",
+        rust_name, python_name
+    );
+    let unsafety = &sig.unsafety;
+    let generics = &sig.generics;
+    let where_clause = &sig.generics.where_clause;
+
+    quote! {
+        #[doc = #doc]
+        #vis #unsafety fn #dispatcher_ident #generics(
+            #slf: &_pyo3::PyCell<Self>,
+            #(#params),*
+        ) -> _pyo3::PyResult<#output> #where_clause {
+            let #py = #slf.py();
+            if _pyo3::impl_::pymethods::is_overridden(#slf, _pyo3::intern!(#py, #python_name))? {
+                #python_result
+            } else {
+                #borrow_slf
+                #rust_result
+            }
+        }
+    }
+}
+
 pub fn check_generic(sig: &syn::Signature) -> syn::Result<()> {
     let err_msg = |typ| format!("Python functions cannot have generic {} parameters", typ);
     for param in &sig.generics.params {
@@ -283,6 +409,27 @@ fn ensure_function_options_valid(options: &PyFunctionOptions) -> syn::Result<()>
     Ok(())
 }
 
+/// Checks the receivers of the methods Python calls while creating a class, which aren't
+/// protocols but are only called with a particular receiver.
+fn ensure_hook_receiver(spec: &FnSpec<'_>, method_name: &str) -> syn::Result<()> {
+    match method_name {
+        "__init_subclass__" => ensure_spanned!(
+            matches!(spec.tp, FnType::FnClass),
+            spec.name.span() => "`__init_subclass__` must be a `#[classmethod]`, as Python calls it with the new subclass"
+        ),
+        "__class_getitem__" => ensure_spanned!(
+            matches!(spec.tp, FnType::FnClass),
+            spec.name.span() => "`__class_getitem__` must be a `#[classmethod]`, as Python calls it with the subscripted class"
+        ),
+        "__set_name__" => ensure_spanned!(
+            matches!(spec.tp, FnType::Fn(_)),
+            spec.name.span() => "`__set_name__` must take `self`, as Python calls it on the attributes of the new class"
+        ),
+        _ => {}
+    }
+    Ok(())
+}
+
 fn ensure_no_forbidden_protocol_attributes(
     proto_kind: &PyMethodProtoKind,
     spec: &FnSpec<'_>,
@@ -325,6 +472,36 @@ pub fn impl_py_method_def(
     })
 }
 
+/// `__class_getitem__` can be defined by the `generic` option of `#[pyclass]` or in
+/// `#[pymethods]`, but not both: each definition implements `PyClassGetitem` for the class, so a
+/// second one is a conflicting implementation. The wrapper is named after the Rust function, so
+/// that the conflict is reported rather than duplicate wrappers.
+fn impl_class_getitem(
+    cls: &syn::Type,
+    spec: &FnSpec<'_>,
+    doc: &PythonDoc,
+) -> Result<(MethodAndMethodDef, TokenStream)> {
+    let wrapper_ident = format_ident!("__pymethod_{}__", spec.name.unraw());
+    let associated_method = spec.get_wrapper_function(&wrapper_ident, Some(cls))?;
+    let methoddef = spec.get_methoddef(quote! { #cls::#wrapper_ident }, doc);
+    let method_def = quote! {
+        _pyo3::class::PyMethodDefType::Class(#methoddef.flags(_pyo3::ffi::METH_CLASS))
+    };
+    let trait_impl = quote_spanned! { spec.name.span() =>
+        impl _pyo3::impl_::pyclass::PyClassGetitem<#cls>
+            for _pyo3::impl_::pyclass::PyClassImplCollector<#cls>
+        {
+        }
+    };
+    Ok((
+        MethodAndMethodDef {
+            associated_method,
+            method_def,
+        },
+        trait_impl,
+    ))
+}
+
 fn impl_py_method_def_new(cls: &syn::Type, spec: &FnSpec<'_>) -> Result<MethodAndSlotDef> {
     let wrapper_ident = syn::Ident::new("__pymethod___new____", Span::call_site());
     let associated_method = spec.get_wrapper_function(&wrapper_ident, Some(cls))?;
@@ -761,9 +938,9 @@ impl PropertyType<'_> {
     }
 }
 
-const __STR__: SlotDef = SlotDef::new("Py_tp_str", "reprfunc");
+pub const __STR__: SlotDef = SlotDef::new("Py_tp_str", "reprfunc");
 pub const __REPR__: SlotDef = SlotDef::new("Py_tp_repr", "reprfunc");
-const __HASH__: SlotDef = SlotDef::new("Py_tp_hash", "hashfunc")
+pub const __HASH__: SlotDef = SlotDef::new("Py_tp_hash", "hashfunc")
     .ret_ty(Ty::PyHashT)
     .return_conversion(TokenGenerator(
         || quote! { _pyo3::callback::HashCallbackOutput },
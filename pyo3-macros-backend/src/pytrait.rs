@@ -0,0 +1,216 @@
+use crate::{
+    attributes::{self, take_pyo3_options, CrateAttribute, NameAttribute},
+    utils::{ensure_not_async_fn, get_pyo3_crate, is_unit, result_ok_type},
+};
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{
+    ext::IdentExt,
+    parse::{Parse, ParseStream},
+    spanned::Spanned,
+    token::Comma,
+    Result, Token,
+};
+
+/// The arguments of `#[pytrait(...)]`.
+#[derive(Default)]
+pub struct PyTraitArgs {
+    krate: Option<CrateAttribute>,
+}
+
+impl Parse for PyTraitArgs {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let mut args = PyTraitArgs::default();
+
+        while !input.is_empty() {
+            let lookahead = input.lookahead1();
+            if lookahead.peek(Token![crate]) {
+                let krate: CrateAttribute = input.parse()?;
+                ensure_spanned!(
+                    args.krate.is_none(),
+                    krate.kw.span() => "`crate` may only be specified once"
+                );
+                args.krate = Some(krate);
+            } else {
+                return Err(lookahead.error());
+            }
+            if !input.is_empty() {
+                let _: Comma = input.parse()?;
+            }
+        }
+
+        Ok(args)
+    }
+}
+
+enum PyTraitMethodOption {
+    Name(NameAttribute),
+}
+
+impl Parse for PyTraitMethodOption {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let lookahead = input.lookahead1();
+        if lookahead.peek(attributes::kw::name) {
+            input.parse().map(PyTraitMethodOption::Name)
+        } else {
+            Err(lookahead.error())
+        }
+    }
+}
+
+/// Implements the trait for `PyImpl<dyn Trait>` by calling Python methods.
+pub fn build_py_trait(ast: &mut syn::ItemTrait, args: PyTraitArgs) -> Result<TokenStream> {
+    ensure_spanned!(
+        ast.generics.params.is_empty(),
+        ast.generics.span() => "#[pytrait] cannot be used on generic traits"
+    );
+
+    let krate = get_pyo3_crate(&args.krate);
+    let trait_ident = &ast.ident;
+    let trait_name = trait_ident.unraw().to_string();
+
+    let mut methods = Vec::new();
+    let mut required_methods = Vec::new();
+    for item in &mut ast.items {
+        match item {
+            syn::TraitItem::Fn(method) => {
+                let mut python_name = None;
+                for option in take_pyo3_options(&mut method.attrs)? {
+                    match option {
+                        PyTraitMethodOption::Name(name) => {
+                            ensure_spanned!(
+                                python_name.is_none(),
+                                name.kw.span() => "`name` may only be specified once"
+                            );
+                            python_name = Some(name.value.0.to_string());
+                        }
+                    }
+                }
+                let python_name =
+                    python_name.unwrap_or_else(|| method.sig.ident.unraw().to_string());
+                if method.default.is_none() {
+                    required_methods.push(python_name.clone());
+                }
+                methods.push(impl_trait_method(method, &python_name, &trait_name)?);
+            }
+            other => bail_spanned!(
+                other.span() => "#[pytrait] traits may only contain methods"
+            ),
+        }
+    }
+
+    Ok(quote! {
+        const _: () = {
+            use #krate as _pyo3;
+
+            impl _pyo3::impl_::pytrait::PyTrait for dyn #trait_ident {
+                const NAME: &'static str = #trait_name;
+                const REQUIRED_METHODS: &'static [&'static str] = &[#(#required_methods),*];
+            }
+
+            impl #trait_ident for _pyo3::PyImpl<dyn #trait_ident> {
+                #(#methods)*
+            }
+        };
+    })
+}
+
+/// Generates a trait method which calls the Python method `python_name`.
+fn impl_trait_method(
+    method: &syn::TraitItemFn,
+    python_name: &str,
+    trait_name: &str,
+) -> Result<TokenStream> {
+    let mut sig = method.sig.clone();
+    ensure_not_async_fn(&sig)?;
+    match sig.receiver() {
+        Some(receiver) if receiver.reference.is_some() && receiver.colon_token.is_none() => {}
+        Some(receiver) => {
+            bail_spanned!(receiver.span() => "#[pytrait] methods must take `&self` or `&mut self`")
+        }
+        None => {
+            bail_spanned!(sig.ident.span() => "#[pytrait] methods must take `&self` or `&mut self`")
+        }
+    }
+    for param in &sig.generics.params {
+        ensure_spanned!(
+            matches!(param, syn::GenericParam::Lifetime(_)),
+            param.span() => "#[pytrait] methods cannot have type or const parameters"
+        );
+    }
+    if let Some(variadic) = &sig.variadic {
+        bail_spanned!(variadic.span() => "#[pytrait] methods cannot be variadic");
+    }
+
+    let method_name = sig.ident.unraw().to_string();
+    let mut bindings = Vec::new();
+    let mut arguments = Vec::new();
+    for (i, input) in sig.inputs.iter_mut().skip(1).enumerate() {
+        let pat_type = match input {
+            syn::FnArg::Typed(pat_type) => pat_type,
+            syn::FnArg::Receiver(_) => unreachable!("only the first argument can be a receiver"),
+        };
+        let ident = syn::Ident::new(&format!("arg{}", i), Span::mixed_site());
+        let pat = &pat_type.pat;
+        bindings.push(quote!(let #pat = #ident;));
+        arguments.push(match &*pat_type.ty {
+            syn::Type::Reference(_) => quote!(_pyo3::ToPyObject::to_object(&*#ident, py)),
+            _ => quote!(_pyo3::IntoPy::<_pyo3::PyObject>::into_py(#ident, py)),
+        });
+        *pat_type.pat = syn::Pat::Ident(syn::PatIdent {
+            attrs: Vec::new(),
+            by_ref: None,
+            mutability: None,
+            ident,
+            subpat: None,
+        });
+    }
+
+    let call = quote! {
+        self.as_ref(py).call_method1(_pyo3::intern!(py, #python_name), (#(#arguments,)*))
+    };
+    let unwrap = |result: TokenStream| quote!(_pyo3::impl_::pytrait::unwrap_result(#result, #trait_name, #method_name));
+    let convert = match &sig.output {
+        syn::ReturnType::Default => unwrap(quote!(#call.map(::std::mem::drop))),
+        syn::ReturnType::Type(_, ty) => {
+            if is_unit(ty) {
+                unwrap(quote!(#call.map(::std::mem::drop)))
+            } else if let Some(ok) = result_ok_type(ty) {
+                if is_unit(ok) {
+                    quote!(#call.map(::std::mem::drop).map_err(::std::convert::Into::into))
+                } else {
+                    quote! {
+                        #call
+                            .and_then(|result| result.extract::<#ok>())
+                            .map_err(::std::convert::Into::into)
+                    }
+                }
+            } else {
+                unwrap(quote!(#call.and_then(|result| result.extract::<#ty>())))
+            }
+        }
+    };
+    let call_python = quote!(_pyo3::Python::with_gil(|py| #convert));
+
+    let body = match &method.default {
+        Some(default) => {
+            quote! {
+                if _pyo3::Python::with_gil(|py| {
+                    _pyo3::impl_::pytrait::has_method(self.as_ref(py), _pyo3::intern!(py, #python_name))
+                }) {
+                    #call_python
+                } else {
+                    #(#bindings)*
+                    #default
+                }
+            }
+        }
+        None => call_python,
+    };
+
+    Ok(quote! {
+        #sig {
+            #body
+        }
+    })
+}
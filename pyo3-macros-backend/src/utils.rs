@@ -54,6 +54,27 @@ pub fn option_type_argument(ty: &syn::Type) -> Option<&syn::Type> {
     None
 }
 
+/// Check if the given type `ty` is `()`.
+pub fn is_unit(ty: &syn::Type) -> bool {
+    matches!(unwrap_ty_group(ty), syn::Type::Tuple(tuple) if tuple.elems.is_empty())
+}
+
+/// If `ty` is `Result<T, E>` or `PyResult<T>`, return `Some(T)`, else `None`.
+pub fn result_ok_type(ty: &syn::Type) -> Option<&syn::Type> {
+    if let syn::Type::Path(syn::TypePath { qself: None, path }) = unwrap_ty_group(ty) {
+        let seg = path
+            .segments
+            .last()
+            .filter(|s| s.ident == "Result" || s.ident == "PyResult")?;
+        if let syn::PathArguments::AngleBracketed(params) = &seg.arguments {
+            if let syn::GenericArgument::Type(ty) = params.args.first()? {
+                return Some(ty);
+            }
+        }
+    }
+    None
+}
+
 /// A syntax tree which evaluates to a nul-terminated docstring for Python.
 ///
 /// Typically the tokens will just be that string, but if the original docs included macro
@@ -7,9 +7,10 @@ extern crate proc_macro;
 use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
 use pyo3_macros_backend::{
-    build_derive_from_pyobject, build_py_class, build_py_enum, build_py_function, build_py_methods,
-    get_doc, process_functions_in_module, pymodule_impl, PyClassArgs, PyClassMethodsType,
-    PyFunctionOptions, PyModuleOptions,
+    build_derive_from_pyobject, build_derive_py_exception, build_py_class, build_py_enum,
+    build_py_function, build_py_methods, build_py_trait, get_doc, process_functions_in_module,
+    pymodule_impl, PyClassArgs, PyClassMethodsType, PyFunctionOptions, PyModuleOptions,
+    PyTraitArgs,
 };
 use quote::quote;
 use syn::{parse::Nothing, parse_macro_input};
@@ -95,6 +96,13 @@ pub fn pyclass(attr: TokenStream, input: TokenStream) -> TokenStream {
 /// If the [`multiple-pymethods`][2] feature is enabled, it is possible to implement
 /// multiple `#[pymethods]` blocks for a single `#[pyclass]`.
 /// This will add a transitive dependency on the [`inventory`][3] crate.
+/// Alternatively, further blocks can be named with `#[pymethods(group = name)]` and added to the
+/// class with `#[pyclass(pymethods_groups(name, ...))]`, which works on all platforms. See the
+/// [class section of the guide][13] for details.
+///
+/// `#[pymethods]` can also be used on trait `impl` blocks to expose the trait methods. A blanket
+/// `impl<T> Trait for T` block can be exposed for the given `#[pyclass]` types with
+/// `#[pymethods(for(Type1, Type2))]`. See the [class section of the guide][12] for details.
 ///
 /// [1]: https://pyo3.rs/latest/class.html#instance-methods
 /// [2]: https://pyo3.rs/latest/features.html#multiple-pymethods
@@ -107,6 +115,8 @@ pub fn pyclass(attr: TokenStream, input: TokenStream) -> TokenStream {
 /// [9]: https://pyo3.rs/latest/class.html#class-attributes
 /// [10]: https://pyo3.rs/latest/class.html#method-arguments
 /// [11]: https://pyo3.rs/latest/class.html#object-properties-using-pyo3get-set
+/// [12]: https://pyo3.rs/latest/class.html#trait-implementations
+/// [13]: https://pyo3.rs/latest/class.html#multiple-pymethods-blocks
 #[proc_macro_attribute]
 pub fn pymethods(attr: TokenStream, input: TokenStream) -> TokenStream {
     let methods_type = if cfg!(feature = "multiple-pymethods") {
@@ -150,6 +160,37 @@ pub fn pyfunction(attr: TokenStream, input: TokenStream) -> TokenStream {
     .into()
 }
 
+/// A proc macro used to implement a Rust trait with Python objects.
+///
+/// `#[pytrait]` implements the trait for `PyImpl<dyn Trait>`, which wraps any Python object with
+/// methods of the same names as the trait methods. Each trait method calls the Python method,
+/// converting its arguments to Python objects and its return value back to Rust.
+///
+/// Trait methods must take `&self` or `&mut self`, and can be annotated with the following:
+///
+/// |  Annotation  |  Description |
+/// | :-  | :- |
+/// | `#[pyo3(name = "...")]` | Defines the name of the Python method to call. |
+///
+/// The path to the `pyo3` crate can be given as `#[pytrait(crate = "...")]`.
+///
+/// For more on implementing Rust traits in Python see the [trait bounds section of the guide][1].
+///
+/// [1]: https://pyo3.rs/latest/trait_bounds.html#generating-the-implementation-with-pytrait
+#[proc_macro_attribute]
+pub fn pytrait(attr: TokenStream, input: TokenStream) -> TokenStream {
+    let mut ast = parse_macro_input!(input as syn::ItemTrait);
+    let args = parse_macro_input!(attr as PyTraitArgs);
+
+    let expanded = build_py_trait(&mut ast, args).unwrap_or_compile_error();
+
+    quote!(
+        #ast
+        #expanded
+    )
+    .into()
+}
+
 #[proc_macro_derive(FromPyObject, attributes(pyo3))]
 pub fn derive_from_py_object(item: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(item as syn::DeriveInput);
@@ -160,6 +201,37 @@ pub fn derive_from_py_object(item: TokenStream) -> TokenStream {
     .into()
 }
 
+/// A derive macro which maps a Rust error enum to a hierarchy of Python exception types.
+///
+/// A base exception type is created for the enum, and one subclass of it for each variant. The
+/// enum's [`Display`](std::fmt::Display) implementation provides the exception message, and the
+/// fields of the variant are exposed as attributes of the exception: named fields under their
+/// own names, tuple fields as `_0`, `_1`, etc.
+///
+/// The enum can be annotated with the following `#[pyo3]` options:
+///
+/// |  Annotation  |  Description |
+/// | :-  | :- |
+/// | `#[pyo3(module = "...")]` | Defines the module the exception types belong to. Defaults to `builtins`. |
+/// | `#[pyo3(name = "...")]` | Defines the name of the base exception type. Defaults to the name of the enum. |
+/// | `#[pyo3(base = ...)]` | Defines the exception type which the base exception type derives from. Defaults to `PyException`. |
+/// | `#[pyo3(crate = "...")]` | Defines the path to the `pyo3` crate. |
+///
+/// Variants can be annotated with `#[pyo3(name = "...")]` to rename their exception type.
+///
+/// For more on mapping errors to exceptions see the [exception section of the guide][1].
+///
+/// [1]: https://pyo3.rs/latest/exception.html#mapping-rust-error-enums-to-exceptions
+#[proc_macro_derive(PyException, attributes(pyo3))]
+pub fn derive_py_exception(item: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(item as syn::DeriveInput);
+    let expanded = build_derive_py_exception(&ast).unwrap_or_compile_error();
+    quote!(
+        #expanded
+    )
+    .into()
+}
+
 fn pyclass_impl(
     attrs: TokenStream,
     mut ast: syn::ItemStruct,
@@ -0,0 +1,272 @@
+#[cfg(feature = "experimental-inspect")]
+use crate::inspect::types::TypeInfo;
+use crate::types::PyTuple;
+use crate::{
+    FromPyObject, IntoPy, Py, PyAny, PyDowncastError, PyObject, PyResult, Python, ToPyObject,
+};
+use std::fmt;
+use std::marker::PhantomData;
+
+/// A Python callable with a known signature.
+///
+/// `PyCallable<(A, B), R>` holds any Python object which is callable, and calls it with
+/// arguments of type `A` and `B`, extracting the result as `R`. It corresponds to the Python type
+/// `Callable[[A, B], R]`, which is also how it is reported by the `experimental-inspect` feature.
+///
+/// Extracting a `PyCallable` fails with a `TypeError` if the object is not callable. The
+/// arguments and return value are only checked when it is called.
+///
+/// Callbacks whose return value is not needed can use `PyObject` as the return type.
+///
+/// # Examples
+///
+/// ```rust
+/// use pyo3::prelude::*;
+/// use pyo3::PyCallable;
+///
+/// #[pyfunction]
+/// fn apply_twice(py: Python<'_>, f: PyCallable<(i64,), i64>, x: i64) -> PyResult<i64> {
+///     let y = f.call(py, x)?;
+///     f.call(py, y)
+/// }
+///
+/// Python::with_gil(|py| {
+///     let apply_twice = wrap_pyfunction!(apply_twice, py).unwrap();
+///     pyo3::py_run!(py, apply_twice, r#"
+///         assert apply_twice(lambda x: x * 3, 2) == 18
+///
+///         try:
+///             apply_twice(3, 2)
+///         except TypeError:
+///             pass
+///         else:
+///             assert False
+///     "#);
+/// });
+/// ```
+pub struct PyCallable<Args, R> {
+    callable: PyObject,
+    marker: PhantomData<fn(Args) -> R>,
+}
+
+impl<Args, R> PyCallable<Args, R> {
+    /// Wraps `callable` without checking that it is callable.
+    ///
+    /// Calling the result fails with a `TypeError` if `callable` is not callable.
+    pub fn new_unchecked(callable: PyObject) -> Self {
+        PyCallable {
+            callable,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the underlying Python object.
+    pub fn as_ref<'py>(&'py self, py: Python<'py>) -> &'py PyAny {
+        self.callable.as_ref(py)
+    }
+
+    /// Returns the underlying Python object.
+    pub fn into_inner(self) -> PyObject {
+        self.callable
+    }
+
+    fn call_tuple<'py>(&self, py: Python<'py>, args: impl IntoPy<Py<PyTuple>>) -> PyResult<R>
+    where
+        R: FromPyObject<'py>,
+    {
+        self.callable.call1(py, args)?.into_ref(py).extract()
+    }
+}
+
+impl<R> PyCallable<(), R> {
+    /// Calls the callable with no arguments.
+    pub fn call<'py>(&self, py: Python<'py>) -> PyResult<R>
+    where
+        R: FromPyObject<'py>,
+    {
+        self.call_tuple(py, ())
+    }
+}
+
+macro_rules! callable_call {
+    ($($arg:ident: $T:ident),+) => {
+        impl<$($T,)+ R> PyCallable<($($T,)+), R>
+        where
+            $($T: IntoPy<PyObject>,)+
+        {
+            /// Calls the callable with the given arguments.
+            #[allow(clippy::too_many_arguments)]
+            pub fn call<'py>(&self, py: Python<'py>, $($arg: $T),+) -> PyResult<R>
+            where
+                R: FromPyObject<'py>,
+            {
+                self.call_tuple(py, ($($arg,)+))
+            }
+        }
+
+        impl<$($T: IntoPy<PyObject>),+> PyCallableArgs for ($($T,)+) {
+            #[cfg(feature = "experimental-inspect")]
+            fn type_outputs() -> Vec<TypeInfo> {
+                vec![$($T::type_output()),+]
+            }
+        }
+    };
+}
+
+callable_call!(a0: A0);
+callable_call!(a0: A0, a1: A1);
+callable_call!(a0: A0, a1: A1, a2: A2);
+callable_call!(a0: A0, a1: A1, a2: A2, a3: A3);
+callable_call!(a0: A0, a1: A1, a2: A2, a3: A3, a4: A4);
+callable_call!(a0: A0, a1: A1, a2: A2, a3: A3, a4: A4, a5: A5);
+callable_call!(a0: A0, a1: A1, a2: A2, a3: A3, a4: A4, a5: A5, a6: A6);
+callable_call!(a0: A0, a1: A1, a2: A2, a3: A3, a4: A4, a5: A5, a6: A6, a7: A7);
+
+/// The argument types of a [`PyCallable`]: `()` or a tuple of up to eight types which can be
+/// converted to Python objects.
+pub trait PyCallableArgs {
+    /// Returns the Python types of the arguments.
+    #[cfg(feature = "experimental-inspect")]
+    fn type_outputs() -> Vec<TypeInfo>;
+}
+
+impl PyCallableArgs for () {
+    #[cfg(feature = "experimental-inspect")]
+    fn type_outputs() -> Vec<TypeInfo> {
+        Vec::new()
+    }
+}
+
+impl<'source, Args, R> FromPyObject<'source> for PyCallable<Args, R>
+where
+    Args: PyCallableArgs,
+    R: FromPyObject<'source>,
+{
+    fn extract(obj: &'source PyAny) -> PyResult<Self> {
+        if obj.is_callable() {
+            Ok(PyCallable::new_unchecked(obj.into()))
+        } else {
+            Err(PyDowncastError::new(obj, "Callable").into())
+        }
+    }
+
+    #[cfg(feature = "experimental-inspect")]
+    fn type_input() -> TypeInfo {
+        TypeInfo::Callable(Some(Args::type_outputs()), Box::new(R::type_input()))
+    }
+}
+
+impl<Args, R> ToPyObject for PyCallable<Args, R> {
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        self.callable.clone_ref(py)
+    }
+}
+
+impl<Args, R> IntoPy<PyObject> for PyCallable<Args, R> {
+    fn into_py(self, _py: Python<'_>) -> PyObject {
+        self.callable
+    }
+}
+
+impl<Args, R> Clone for PyCallable<Args, R> {
+    fn clone(&self) -> Self {
+        PyCallable::new_unchecked(self.callable.clone())
+    }
+}
+
+impl<Args, R> fmt::Debug for PyCallable<Args, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("PyCallable").field(&self.callable).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PyCallable;
+    use crate::types::PyTuple;
+    use crate::{PyObject, Python};
+
+    #[test]
+    fn test_call() {
+        Python::with_gil(|py| {
+            let add: PyCallable<(i32, i32), i32> = py
+                .eval("lambda a, b: a + b", None, None)
+                .unwrap()
+                .extract()
+                .unwrap();
+            assert_eq!(add.call(py, 1, 2).unwrap(), 3);
+
+            let answer: PyCallable<(), String> = py
+                .eval("lambda: '42'", None, None)
+                .unwrap()
+                .extract()
+                .unwrap();
+            assert_eq!(answer.call(py).unwrap(), "42");
+
+            let ignored: PyCallable<(&str,), PyObject> = py
+                .eval("lambda s: None", None, None)
+                .unwrap()
+                .extract()
+                .unwrap();
+            assert!(ignored.call(py, "").unwrap().is_none(py));
+        });
+    }
+
+    #[test]
+    fn test_call_errors() {
+        Python::with_gil(|py| {
+            let err = py
+                .eval("1", None, None)
+                .unwrap()
+                .extract::<PyCallable<(i32,), i32>>()
+                .unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "TypeError: 'int' object cannot be converted to 'Callable'"
+            );
+
+            let to_str: PyCallable<(i32,), i32> =
+                py.eval("str", None, None).unwrap().extract().unwrap();
+            let err = to_str.call(py, 1).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "TypeError: 'str' object cannot be interpreted as an integer"
+            );
+
+            let raises: PyCallable<(i32,), i32> = py
+                .eval("lambda x: 1 // x", None, None)
+                .unwrap()
+                .extract()
+                .unwrap();
+            assert!(raises
+                .call(py, 0)
+                .unwrap_err()
+                .is_instance_of::<crate::exceptions::PyZeroDivisionError>(py));
+        });
+    }
+
+    #[test]
+    fn test_from_tuple_type() {
+        Python::with_gil(|py| {
+            let tuple = py.get_type::<PyTuple>();
+            let new_tuple: PyCallable<(Vec<i32>,), &PyTuple> = tuple.extract().unwrap();
+            assert_eq!(new_tuple.call(py, vec![1, 2]).unwrap().len(), 2);
+            assert!(new_tuple.as_ref(py).is(tuple));
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "experimental-inspect")]
+    fn test_type_input() {
+        use crate::FromPyObject;
+
+        assert_eq!(
+            PyCallable::<(i32, &str), bool>::type_input().to_string(),
+            "Callable[[int, str], bool]"
+        );
+        assert_eq!(
+            PyCallable::<(), Vec<f64>>::type_input().to_string(),
+            "Callable[[], Sequence[float]]"
+        );
+    }
+}
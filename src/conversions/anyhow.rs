@@ -9,8 +9,12 @@
 //! want error handling to be easy. If you are writing a library or you need more control over your
 //! errors you might want to design your own error type instead.
 //!
-//! When the inner error is a [`PyErr`] without source, it will be extracted out.
-//! Otherwise a Python [`RuntimeError`] will be created.
+//! When the error is a [`PyErr`] without added context, it will be extracted out.
+//! Otherwise a Python [`RuntimeError`] will be created for each error in the
+//! [chain](https://docs.rs/anyhow/latest/anyhow/struct.Error.html#method.chain), linked together
+//! with `__cause__` so that Python tracebacks show the full chain. A [`PyErr`] found in the chain
+//! is preserved as it is, including its type. The original [`anyhow::Error`] can be retrieved again
+//! with [`PyErr::as_anyhow`] after the exception has passed through Python code.
 //! You might find that you need to map the error from your Rust code into another Python exception.
 //! See [`PyErr::new`] for more information about that.
 //!
@@ -103,19 +107,51 @@
 //! [`RuntimeError`]: https://docs.python.org/3/library/exceptions.html#RuntimeError "Built-in Exceptions — Python documentation"
 //! [Error handling]: https://doc.rust-lang.org/book/ch09-02-recoverable-errors-with-result.html "Recoverable Errors with Result - The Rust Programming Language"
 
+use crate::err::rust_error;
 use crate::exceptions::PyRuntimeError;
-use crate::PyErr;
+use crate::{PyErr, Python};
 
 impl From<anyhow::Error> for PyErr {
     fn from(mut error: anyhow::Error) -> Self {
-        // Errors containing a PyErr without chain or context are returned as the underlying error
-        if error.source().is_none() {
+        // Errors which are a PyErr without added context are returned as the underlying error
+        let outermost: &(dyn std::error::Error + 'static) = &*error;
+        if outermost.is::<Self>() {
             error = match error.downcast::<Self>() {
                 Ok(py_err) => return py_err,
                 Err(error) => error,
             };
         }
-        PyRuntimeError::new_err(format!("{:?}", error))
+        PyRuntimeError::new_err(rust_error::RustErrorArguments(error))
+    }
+}
+
+impl PyErr {
+    /// Returns the [`anyhow::Error`] which this exception was created from, if any.
+    ///
+    /// This makes it possible to recover the original Rust error after it has been converted to
+    /// a [`PyErr`] and has passed through Python code.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use pyo3::prelude::*;
+    /// use pyo3::types::IntoPyDict;
+    /// use std::io;
+    ///
+    /// let error = anyhow::Error::new(io::Error::new(io::ErrorKind::NotFound, "missing.txt"));
+    /// let py_err = PyErr::from(error.context("failed to read config"));
+    ///
+    /// Python::with_gil(|py| {
+    ///     let locals = [("err", py_err)].into_py_dict(py);
+    ///     let py_err = py.run("raise err", None, Some(locals)).unwrap_err();
+    ///
+    ///     let error = py_err.as_anyhow(py).expect("exception was created from anyhow::Error");
+    ///     let io_error = error.downcast_ref::<io::Error>().unwrap();
+    ///     assert_eq!(io_error.kind(), io::ErrorKind::NotFound);
+    /// });
+    /// ```
+    pub fn as_anyhow<'py>(&'py self, py: Python<'py>) -> Option<&'py anyhow::Error> {
+        rust_error::payload(self.value(py))?.downcast_ref()
     }
 }
 
@@ -143,13 +179,21 @@ mod test_anyhow {
     #[test]
     fn test_pyo3_exception_contents() {
         let err = h().unwrap_err();
-        let expected_contents = format!("{:?}", err);
         let pyerr = PyErr::from(err);
 
         Python::with_gil(|py| {
             let locals = [("err", pyerr)].into_py_dict(py);
             let pyerr = py.run("raise err", None, Some(locals)).unwrap_err();
-            assert_eq!(pyerr.value(py).to_string(), expected_contents);
+            assert!(pyerr.is_instance_of::<PyRuntimeError>(py));
+            assert_eq!(pyerr.value(py).to_string(), "g failed");
+
+            let cause = pyerr.cause(py).unwrap();
+            assert!(cause.is_instance_of::<PyRuntimeError>(py));
+            assert_eq!(cause.value(py).to_string(), "f failed");
+
+            let cause = cause.cause(py).unwrap();
+            assert_eq!(cause.value(py).to_string(), "oh no!");
+            assert!(cause.cause(py).is_none());
         })
     }
 
@@ -160,7 +204,7 @@ mod test_anyhow {
     #[test]
     fn test_pyo3_exception_contents2() {
         let err = k().unwrap_err();
-        let expected_contents = format!("{:?}", err);
+        let expected_contents = err.to_string();
         let pyerr = PyErr::from(err);
 
         Python::with_gil(|py| {
@@ -185,8 +229,57 @@ mod test_anyhow {
         let mut err: anyhow::Error = origin_exc.into();
         err = err.context("Context");
         let converted: PyErr = err.into();
-        assert!(Python::with_gil(
-            |py| converted.is_instance_of::<PyRuntimeError>(py)
-        ))
+        Python::with_gil(|py| {
+            assert!(converted.is_instance_of::<PyRuntimeError>(py));
+            assert_eq!(converted.value(py).to_string(), "Context");
+            let cause = converted.cause(py).unwrap();
+            assert!(cause.is_instance_of::<PyValueError>(py));
+            assert_eq!(cause.value(py).to_string(), "Value Error");
+        })
+    }
+
+    #[test]
+    fn test_pyo3_embedded_err_keeps_cause() {
+        Python::with_gil(|py| {
+            let origin_exc = py
+                .run(
+                    "raise ValueError('outer') from KeyError('inner')",
+                    None,
+                    None,
+                )
+                .unwrap_err();
+            let err = anyhow::Error::from(origin_exc).context("Context");
+            let converted = PyErr::from(err);
+            let cause = converted.cause(py).unwrap();
+            assert!(cause.is_instance_of::<PyValueError>(py));
+            let cause = cause.cause(py).unwrap();
+            assert_eq!(cause.to_string(), "KeyError: 'inner'");
+        })
+    }
+
+    #[test]
+    fn test_pyo3_recover_anyhow_error() {
+        let err = h().unwrap_err();
+        let pyerr = PyErr::from(err);
+
+        Python::with_gil(|py| {
+            let locals = [("err", pyerr)].into_py_dict(py);
+            let pyerr = py.run("raise err", None, Some(locals)).unwrap_err();
+            let err = pyerr.as_anyhow(py).unwrap();
+            assert_eq!(err.to_string(), "g failed");
+            let io_err = err.downcast_ref::<std::io::Error>().unwrap();
+            assert_eq!(io_err.kind(), std::io::ErrorKind::PermissionDenied);
+            assert_eq!(
+                pyerr
+                    .downcast_rust_error::<std::io::Error>(py)
+                    .unwrap()
+                    .kind(),
+                std::io::ErrorKind::PermissionDenied
+            );
+
+            assert!(PyValueError::new_err("not from anyhow")
+                .as_anyhow(py)
+                .is_none());
+        })
     }
 }
@@ -8,8 +8,12 @@
 //! want error handling to be easy. If you are writing a library or you need more control over your
 //! errors you might want to design your own error type instead.
 //!
-//! When the inner error is a [`PyErr`] without source, it will be extracted out.
-//! Otherwise a Python [`RuntimeError`] will be created.
+//! When the error is a [`PyErr`] without added context, it will be extracted out.
+//! Otherwise a Python [`RuntimeError`] will be created for each error in the
+//! [chain](https://docs.rs/eyre/latest/eyre/struct.Report.html#method.chain), linked together
+//! with `__cause__` so that Python tracebacks show the full chain. A [`PyErr`] found in the chain
+//! is preserved as it is, including its type. The original [`Report`] can be retrieved again
+//! with [`PyErr::as_eyre`] after the exception has passed through Python code.
 //! You might find that you need to map the error from your Rust code into another Python exception.
 //! See [`PyErr::new`] for more information about that.
 //!
@@ -103,8 +107,9 @@
 //! [`RuntimeError`]: https://docs.python.org/3/library/exceptions.html#RuntimeError "Built-in Exceptions — Python documentation"
 //! [Error handling]: https://doc.rust-lang.org/book/ch09-02-recoverable-errors-with-result.html "Recoverable Errors with Result - The Rust Programming Language"
 
+use crate::err::rust_error;
 use crate::exceptions::PyRuntimeError;
-use crate::PyErr;
+use crate::{PyErr, Python};
 use eyre::Report;
 
 /// Converts [`eyre::Report`] to a [`PyErr`] containing a [`PyRuntimeError`].
@@ -113,14 +118,46 @@ use eyre::Report;
 /// [`PyErr::new`] for more information about that.
 impl From<eyre::Report> for PyErr {
     fn from(mut error: Report) -> Self {
-        // Errors containing a PyErr without chain or context are returned as the underlying error
-        if error.source().is_none() {
+        // Errors which are a PyErr without added context are returned as the underlying error
+        let outermost: &(dyn std::error::Error + 'static) = &*error;
+        if outermost.is::<Self>() {
             error = match error.downcast::<Self>() {
                 Ok(py_err) => return py_err,
                 Err(error) => error,
             };
         }
-        PyRuntimeError::new_err(format!("{:?}", error))
+        PyRuntimeError::new_err(rust_error::RustErrorArguments(error))
+    }
+}
+
+impl PyErr {
+    /// Returns the [`eyre::Report`] which this exception was created from, if any.
+    ///
+    /// This makes it possible to recover the original Rust error after it has been converted to
+    /// a [`PyErr`] and has passed through Python code.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eyre::WrapErr;
+    /// use pyo3::prelude::*;
+    /// use pyo3::types::IntoPyDict;
+    /// use std::io;
+    ///
+    /// let result: Result<(), _> = Err(io::Error::new(io::ErrorKind::NotFound, "missing.txt"));
+    /// let py_err = PyErr::from(result.wrap_err("failed to read config").unwrap_err());
+    ///
+    /// Python::with_gil(|py| {
+    ///     let locals = [("err", py_err)].into_py_dict(py);
+    ///     let py_err = py.run("raise err", None, Some(locals)).unwrap_err();
+    ///
+    ///     let report = py_err.as_eyre(py).expect("exception was created from eyre::Report");
+    ///     let io_error = report.downcast_ref::<io::Error>().unwrap();
+    ///     assert_eq!(io_error.kind(), io::ErrorKind::NotFound);
+    /// });
+    /// ```
+    pub fn as_eyre<'py>(&'py self, py: Python<'py>) -> Option<&'py Report> {
+        rust_error::payload(self.value(py))?.downcast_ref()
     }
 }
 
@@ -148,13 +185,21 @@ mod tests {
     #[test]
     fn test_pyo3_exception_contents() {
         let err = h().unwrap_err();
-        let expected_contents = format!("{:?}", err);
         let pyerr = PyErr::from(err);
 
         Python::with_gil(|py| {
             let locals = [("err", pyerr)].into_py_dict(py);
             let pyerr = py.run("raise err", None, Some(locals)).unwrap_err();
-            assert_eq!(pyerr.value(py).to_string(), expected_contents);
+            assert!(pyerr.is_instance_of::<PyRuntimeError>(py));
+            assert_eq!(pyerr.value(py).to_string(), "g failed");
+
+            let cause = pyerr.cause(py).unwrap();
+            assert!(cause.is_instance_of::<PyRuntimeError>(py));
+            assert_eq!(cause.value(py).to_string(), "f failed");
+
+            let cause = cause.cause(py).unwrap();
+            assert_eq!(cause.value(py).to_string(), "oh no!");
+            assert!(cause.cause(py).is_none());
         })
     }
 
@@ -165,7 +210,7 @@ mod tests {
     #[test]
     fn test_pyo3_exception_contents2() {
         let err = k().unwrap_err();
-        let expected_contents = format!("{:?}", err);
+        let expected_contents = err.to_string();
         let pyerr = PyErr::from(err);
 
         Python::with_gil(|py| {
@@ -190,8 +235,29 @@ mod tests {
         let mut report: Report = origin_exc.into();
         report = report.wrap_err("Wrapped");
         let converted: PyErr = report.into();
-        assert!(Python::with_gil(
-            |py| converted.is_instance_of::<PyRuntimeError>(py)
-        ))
+        Python::with_gil(|py| {
+            assert!(converted.is_instance_of::<PyRuntimeError>(py));
+            assert_eq!(converted.value(py).to_string(), "Wrapped");
+            let cause = converted.cause(py).unwrap();
+            assert!(cause.is_instance_of::<PyValueError>(py));
+            assert_eq!(cause.value(py).to_string(), "Value Error");
+        })
+    }
+
+    #[test]
+    fn test_pyo3_recover_report() {
+        let err = h().unwrap_err();
+        let pyerr = PyErr::from(err);
+
+        Python::with_gil(|py| {
+            let locals = [("err", pyerr)].into_py_dict(py);
+            let pyerr = py.run("raise err", None, Some(locals)).unwrap_err();
+            let report = pyerr.as_eyre(py).unwrap();
+            assert_eq!(report.to_string(), "g failed");
+            let io_err = report.downcast_ref::<std::io::Error>().unwrap();
+            assert_eq!(io_err.kind(), std::io::ErrorKind::PermissionDenied);
+
+            assert!(PyValueError::new_err("not from eyre").as_eyre(py).is_none());
+        })
     }
 }
@@ -1,20 +1,26 @@
 use crate::panic::PanicException;
+use crate::sync::GILProtected;
 use crate::type_object::PyTypeInfo;
-use crate::types::{PyTraceback, PyType};
+#[cfg(not(Py_3_11))]
+use crate::types::PyList;
+use crate::types::{PyString, PyTraceback, PyType};
 use crate::{
     exceptions::{self, PyBaseException},
     ffi,
 };
 use crate::{IntoPy, Py, PyAny, PyObject, Python, ToPyObject};
 use std::borrow::Cow;
-use std::cell::UnsafeCell;
+use std::cell::{RefCell, UnsafeCell};
 use std::ffi::CString;
 
 mod err_state;
 mod impls;
+pub(crate) mod rust_error;
+mod snapshot;
 
 pub use err_state::PyErrArguments;
 use err_state::{PyErrState, PyErrStateLazyFnOutput, PyErrStateNormalized};
+pub use snapshot::PyErrSnapshot;
 
 /// Represents a Python exception.
 ///
@@ -32,6 +38,12 @@ pub struct PyErr {
     // The state is temporarily removed from the PyErr during normalization, to avoid
     // concurrent modifications.
     state: UnsafeCell<Option<PyErrState>>,
+    // The chains of causes or contexts created by `Error::source`. A new chain is added when the
+    // exception's cause or context changed since the last one, and earlier chains are kept as long
+    // as the error, as references to them may still be in use.
+    sources: GILProtected<RefCell<Vec<Option<Box<PyErr>>>>>,
+    // The links of a chain keep the source they were created with, so that the chain always ends.
+    source_is_fixed: bool,
 }
 
 // The inner value is only accessed through ways that require proving the gil is held
@@ -187,6 +199,36 @@ impl PyErr {
         PyErr::from_state(state)
     }
 
+    /// Creates a `RuntimeError` which carries the Rust `error`.
+    ///
+    /// The error's message and its chain of sources become the exception's message and its
+    /// `__cause__` chain. The error itself is stored in the exception instance, so that it can be
+    /// recovered with [`PyErr::downcast_rust_error`] even after the exception has been raised
+    /// through Python code.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use pyo3::prelude::*;
+    /// use std::num::ParseIntError;
+    ///
+    /// fn parse(s: &str) -> PyResult<i32> {
+    ///     s.parse().map_err(PyErr::from_rust_error)
+    /// }
+    ///
+    /// Python::with_gil(|py| {
+    ///     let err = parse("seven").unwrap_err();
+    ///     assert_eq!(err.to_string(), "RuntimeError: invalid digit found in string");
+    ///     assert!(err.downcast_rust_error::<ParseIntError>(py).is_some());
+    /// });
+    /// ```
+    pub fn from_rust_error<E>(error: E) -> PyErr
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        let error: Box<dyn std::error::Error + Send + Sync> = Box::new(error);
+        exceptions::PyRuntimeError::new_err(rust_error::RustErrorArguments(error))
+    }
+
     /// Returns the type of this exception.
     ///
     /// # Examples
@@ -473,6 +515,72 @@ impl PyErr {
         self.is_instance(py, exc.to_object(py).as_ref(py))
     }
 
+    /// Splits this error into the part which matches `exc` and the part which does not, in the
+    /// same way as an `except*` clause.
+    ///
+    /// If this error is an exception group, this is equivalent to `BaseExceptionGroup.split`,
+    /// and `exc` may be an exception type, a tuple of exception types, or a predicate callable.
+    /// Otherwise the whole error is returned on the side given by [`PyErr::matches`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pyo3::prelude::*;
+    /// use pyo3::exceptions::{PyBaseExceptionGroup, PyTypeError, PyValueError};
+    ///
+    /// Python::with_gil(|py| -> PyResult<()> {
+    ///     let err = PyBaseExceptionGroup::new_group(
+    ///         "batch failed",
+    ///         vec![PyValueError::new_err("bad value"), PyTypeError::new_err("bad type")],
+    ///     );
+    ///     let (values, rest) = err.split_group(py, py.get_type::<PyValueError>())?;
+    ///     assert_eq!(values.unwrap().to_string(), "ExceptionGroup: batch failed (1 sub-exception)");
+    ///     assert!(rest.is_some());
+    ///     Ok(())
+    /// })
+    /// # .unwrap();
+    /// ```
+    #[cfg(Py_3_11)]
+    pub fn split_group<T>(&self, py: Python<'_>, exc: T) -> PyResult<(Option<PyErr>, Option<PyErr>)>
+    where
+        T: ToPyObject,
+    {
+        let exc = exc.to_object(py);
+        if let Ok(group) = self
+            .value(py)
+            .downcast::<exceptions::PyBaseExceptionGroup>()
+        {
+            let (matched, rest) = group.split(exc.as_ref(py))?;
+            Ok((matched.map(PyErr::from), rest.map(PyErr::from)))
+        } else if self.is_instance(py, exc.as_ref(py)) {
+            Ok((Some(self.clone_ref(py)), None))
+        } else {
+            Ok((None, Some(self.clone_ref(py))))
+        }
+    }
+
+    /// Returns the part of this error which matches `exc`, or `None` if nothing matches.
+    ///
+    /// This is the first half of [`PyErr::split_group`]; for exception groups it is equivalent
+    /// to `BaseExceptionGroup.subgroup`.
+    #[cfg(Py_3_11)]
+    pub fn subgroup<T>(&self, py: Python<'_>, exc: T) -> PyResult<Option<PyErr>>
+    where
+        T: ToPyObject,
+    {
+        let exc = exc.to_object(py);
+        if let Ok(group) = self
+            .value(py)
+            .downcast::<exceptions::PyBaseExceptionGroup>()
+        {
+            Ok(group.subgroup(exc.as_ref(py))?.map(PyErr::from))
+        } else if self.is_instance(py, exc.as_ref(py)) {
+            Ok(Some(self.clone_ref(py)))
+        } else {
+            Ok(None)
+        }
+    }
+
     /// Returns true if the current exception is instance of `T`.
     #[inline]
     pub fn is_instance(&self, py: Python<'_>, ty: &PyAny) -> bool {
@@ -647,10 +755,108 @@ impl PyErr {
         }
     }
 
+    /// Adds a note to the exception, which is displayed after the message in tracebacks.
+    ///
+    /// On Python 3.11 and up this calls `BaseException.add_note` as specified in
+    /// [PEP 678](https://peps.python.org/pep-0678/). On older versions the note is appended to
+    /// the exception's `__notes__` list, which is created if it does not exist yet.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pyo3::prelude::*;
+    /// use pyo3::exceptions::PyValueError;
+    ///
+    /// Python::with_gil(|py| -> PyResult<()> {
+    ///     let err = PyValueError::new_err("invalid port");
+    ///     err.add_note(py, "while parsing config.toml")?;
+    ///     let notes: Vec<String> = err.value(py).getattr("__notes__")?.extract()?;
+    ///     assert_eq!(notes, ["while parsing config.toml"]);
+    ///     Ok(())
+    /// })
+    /// # .unwrap();
+    /// ```
+    pub fn add_note(&self, py: Python<'_>, note: &str) -> PyResult<()> {
+        let value = self.value(py);
+        let note = PyString::new(py, note);
+
+        #[cfg(Py_3_11)]
+        {
+            value.call_method1(crate::intern!(py, "add_note"), (note,))?;
+        }
+
+        #[cfg(not(Py_3_11))]
+        {
+            let notes = crate::intern!(py, "__notes__");
+            if value.hasattr(notes)? {
+                value
+                    .getattr(notes)?
+                    .downcast::<PyList>()
+                    .map_err(|_| {
+                        exceptions::PyTypeError::new_err("Cannot add note: __notes__ is not a list")
+                    })?
+                    .append(note)?;
+            } else {
+                value.setattr(notes, PyList::new(py, [note]))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the Rust error of type `E` which this exception carries, if any.
+    ///
+    /// This recovers errors stored by [`PyErr::from_rust_error`], including errors further down
+    /// their chain of sources, as well as errors stored by the `anyhow` and `eyre` conversions
+    /// when those features are enabled. Since the error lives in the exception instance, it
+    /// survives the exception being raised and caught again by Python code.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use pyo3::prelude::*;
+    /// use pyo3::types::IntoPyDict;
+    /// use std::io;
+    ///
+    /// Python::with_gil(|py| {
+    ///     let err = PyErr::from_rust_error(io::Error::new(io::ErrorKind::NotFound, "missing.txt"));
+    ///     let locals = [("err", err)].into_py_dict(py);
+    ///     let err = py.run("raise err", None, Some(locals)).unwrap_err();
+    ///
+    ///     let io_error = err.downcast_rust_error::<io::Error>(py).unwrap();
+    ///     assert_eq!(io_error.kind(), io::ErrorKind::NotFound);
+    /// });
+    /// ```
+    pub fn downcast_rust_error<'py, E>(&'py self, py: Python<'py>) -> Option<&'py E>
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        rust_error::downcast(rust_error::payload(self.value(py))?)
+    }
+
+    /// Captures this exception as plain Rust data which can be used without the GIL.
+    ///
+    /// The snapshot records the exception's type name, message, notes and formatted traceback,
+    /// along with snapshots of its cause or context. See [`PyErrSnapshot`] for details.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use pyo3::prelude::*;
+    /// use pyo3::exceptions::PyValueError;
+    ///
+    /// let snapshot = Python::with_gil(|py| PyValueError::new_err("invalid port").snapshot(py));
+    /// assert_eq!(snapshot.type_name(), "ValueError");
+    /// assert_eq!(snapshot.message(), "invalid port");
+    /// ```
+    pub fn snapshot(&self, py: Python<'_>) -> PyErrSnapshot {
+        PyErrSnapshot::new(self.value(py))
+    }
+
     #[inline]
     fn from_state(state: PyErrState) -> PyErr {
         PyErr {
             state: UnsafeCell::new(Some(state)),
+            sources: GILProtected::new(RefCell::new(Vec::new())),
+            source_is_fixed: false,
         }
     }
 
@@ -703,9 +909,17 @@ impl std::fmt::Debug for PyErr {
     }
 }
 
+/// Writes the exception type and message, e.g. `ValueError: invalid port`.
+///
+/// The alternate form (`{:#}`) writes the full report which Python would print for the exception
+/// if it were uncaught, including its traceback, notes and chain of causes. See
+/// [`PyErrSnapshot`] for details.
 impl std::fmt::Display for PyErr {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         Python::with_gil(|py| {
+            if f.alternate() {
+                return write!(f, "{:#}", self.snapshot(py));
+            }
             let value = self.value(py);
             let type_name = value.get_type().name().map_err(|_| std::fmt::Error)?;
             write!(f, "{}", type_name)?;
@@ -718,7 +932,64 @@ impl std::fmt::Display for PyErr {
     }
 }
 
-impl std::error::Error for PyErr {}
+/// The [`source`](std::error::Error::source) of a `PyErr` is its `__cause__`, or otherwise its
+/// `__context__` unless `__suppress_context__` is set, following the same rules as Python
+/// tracebacks. The chain is created when it is requested, again if the cause or context changed
+/// since, and stops before the first repeated exception, so that it always ends even if the
+/// exceptions form a cycle.
+impl std::error::Error for PyErr {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Python::with_gil(|py| {
+            let mut sources = self.sources.get(py).borrow_mut();
+            if !self.source_is_fixed {
+                let chain = snapshot::exception_chain(self.value(py));
+                let links = &chain[1..];
+                if !sources
+                    .last()
+                    .map_or(false, |source| is_chain(py, source.as_deref(), links))
+                {
+                    // Each link gets its source from this chain rather than computing its own,
+                    // which would start over from that link in a cycle
+                    let mut source: Option<Box<PyErr>> = None;
+                    for (value, _) in links.iter().rev() {
+                        let mut link = PyErr::from_value(value);
+                        link.sources.get(py).borrow_mut().push(source.take());
+                        link.source_is_fixed = true;
+                        source = Some(Box::new(link));
+                    }
+                    sources.push(source);
+                }
+            }
+            let source: *const PyErr = sources.last()?.as_deref()?;
+            // Safety: the boxed errors don't move when `sources` grows, and are only dropped
+            // with `self`
+            Some(unsafe { &*source } as _)
+        })
+    }
+}
+
+/// Whether `source` and its sources are the exceptions in `links`.
+fn is_chain(
+    py: Python<'_>,
+    mut source: Option<&PyErr>,
+    links: &[(&PyBaseException, bool)],
+) -> bool {
+    for (value, _) in links {
+        let err = match source {
+            Some(err) if err.value(py).is(*value) => err,
+            _ => return false,
+        };
+        let next: Option<*const PyErr> = err
+            .sources
+            .get(py)
+            .borrow()
+            .last()
+            .and_then(|source| source.as_deref().map(|source| source as *const PyErr));
+        // Safety: as in `Error::source`, the boxed error lives as long as `err`
+        source = next.map(|next| unsafe { &*next });
+    }
+    source.is_none()
+}
 
 impl IntoPy<PyObject> for PyErr {
     fn into_py(self, py: Python<'_>) -> PyObject {
@@ -738,6 +1009,84 @@ impl<'a> IntoPy<PyObject> for &'a PyErr {
     }
 }
 
+/// Extension trait to enrich errors with notes as they propagate, similar to `anyhow::Context`.
+///
+/// Unlike wrapping the error in a new exception, the type of the original exception is
+/// preserved; the context is attached with [`PyErr::add_note`] and shown by Python in the
+/// traceback.
+///
+/// # Examples
+///
+/// ```rust
+/// use pyo3::prelude::*;
+/// use pyo3::PyResultExt;
+///
+/// fn parse_port(s: &str) -> PyResult<u16> {
+///     s.parse::<u16>().context("port must be a number between 0 and 65535")
+/// }
+///
+/// fn load_config(port: &str) -> PyResult<u16> {
+///     parse_port(port).with_context(|| format!("while loading config (port = {:?})", port))
+/// }
+///
+/// Python::with_gil(|py| -> PyResult<()> {
+///     let err = load_config("http").unwrap_err();
+///     assert!(err.is_instance_of::<pyo3::exceptions::PyValueError>(py));
+///     let notes: Vec<String> = err.value(py).getattr("__notes__")?.extract()?;
+///     assert_eq!(
+///         notes,
+///         [
+///             "port must be a number between 0 and 65535",
+///             "while loading config (port = \"http\")"
+///         ]
+///     );
+///     Ok(())
+/// })
+/// # .unwrap();
+/// ```
+pub trait PyResultExt<T> {
+    /// Converts the error into a [`PyErr`] and adds `context` to it as a note.
+    ///
+    /// If the note cannot be added, the error is returned unchanged.
+    fn context<C>(self, context: C) -> PyResult<T>
+    where
+        C: std::fmt::Display;
+
+    /// Like [`context`](PyResultExt::context), but the note is only computed if there is an
+    /// error.
+    fn with_context<C, F>(self, f: F) -> PyResult<T>
+    where
+        C: std::fmt::Display,
+        F: FnOnce() -> C;
+}
+
+impl<T, E> PyResultExt<T> for Result<T, E>
+where
+    E: Into<PyErr>,
+{
+    fn context<C>(self, context: C) -> PyResult<T>
+    where
+        C: std::fmt::Display,
+    {
+        self.with_context(|| context)
+    }
+
+    fn with_context<C, F>(self, f: F) -> PyResult<T>
+    where
+        C: std::fmt::Display,
+        F: FnOnce() -> C,
+    {
+        self.map_err(|err| {
+            let err = err.into();
+            Python::with_gil(|py| {
+                // The original error is more useful than a failure to attach the note
+                let _ = err.add_note(py, &f().to_string());
+            });
+            err
+        })
+    }
+}
+
 struct PyDowncastErrorArguments {
     from: Py<PyType>,
     to: Cow<'static, str>,
@@ -821,8 +1170,8 @@ impl_signed_integer!(isize);
 #[cfg(test)]
 mod tests {
     use super::PyErrState;
-    use crate::exceptions::{self, PyTypeError, PyValueError};
-    use crate::{PyErr, PyTypeInfo, Python};
+    use crate::exceptions::{self, PyKeyError, PyTypeError, PyValueError};
+    use crate::{PyErr, PyResult, PyTypeInfo, Python};
 
     #[test]
     fn no_error() {
@@ -1007,6 +1356,249 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_pyerr_add_note() {
+        use crate::PyResultExt;
+
+        Python::with_gil(|py| {
+            let err = PyValueError::new_err("banana");
+            err.add_note(py, "first").unwrap();
+            err.add_note(py, "second").unwrap();
+            let notes: Vec<String> = err
+                .value(py)
+                .getattr("__notes__")
+                .unwrap()
+                .extract()
+                .unwrap();
+            assert_eq!(notes, ["first", "second"]);
+
+            // notes which are not a list cannot be extended
+            let err = PyValueError::new_err("banana");
+            err.value(py).setattr("__notes__", "not a list").unwrap();
+            assert!(err
+                .add_note(py, "note")
+                .unwrap_err()
+                .is_instance_of::<PyTypeError>(py));
+
+            let result: Result<(), std::num::ParseIntError> = "x".parse::<i32>().map(|_| ());
+            let err = result
+                .context("outer")
+                .with_context(|| format!("{} most", "outer"))
+                .unwrap_err();
+            assert!(err.is_instance_of::<PyValueError>(py));
+            let notes: Vec<String> = err
+                .value(py)
+                .getattr("__notes__")
+                .unwrap()
+                .extract()
+                .unwrap();
+            assert_eq!(notes, ["outer", "outer most"]);
+
+            // context is not evaluated without an error
+            let ok: PyResult<i32> = Ok(1);
+            assert_eq!(
+                ok.with_context(|| -> String { panic!("should not be called") })
+                    .unwrap(),
+                1
+            );
+        });
+    }
+
+    #[test]
+    fn test_pyerr_source() {
+        use std::error::Error;
+
+        Python::with_gil(|py| {
+            let err = py
+                .run(
+                    r#"
+try:
+    raise KeyError("port") from OSError("config.toml")
+except KeyError:
+    raise ValueError("invalid config")
+"#,
+                    None,
+                    None,
+                )
+                .unwrap_err();
+
+            let context = err.source().unwrap();
+            assert_eq!(context.to_string(), "KeyError: 'port'");
+            let cause = context.source().unwrap();
+            assert_eq!(cause.to_string(), "OSError: config.toml");
+            assert!(cause.source().is_none());
+
+            let report = format!("{:#}", err);
+            assert!(report.starts_with("OSError: config.toml\n"));
+            assert!(report.contains("Traceback (most recent call last):"));
+            assert!(report.ends_with("ValueError: invalid config\n"));
+
+            // suppressed contexts are not followed
+            let err = py
+                .run(
+                    "try:\n    1 / 0\nexcept ZeroDivisionError:\n    raise ValueError() from None",
+                    None,
+                    None,
+                )
+                .unwrap_err();
+            assert!(err.source().is_none());
+        });
+    }
+
+    #[test]
+    fn test_pyerr_source_cycle() {
+        use std::error::Error;
+
+        Python::with_gil(|py| {
+            let err = PyValueError::new_err("a");
+            let other = PyTypeError::new_err("b");
+            other.set_cause(py, Some(err.clone_ref(py)));
+            err.set_cause(py, Some(other));
+
+            let source = err.source().unwrap();
+            assert_eq!(source.to_string(), "TypeError: b");
+            assert!(source.source().is_none());
+        });
+    }
+
+    #[test]
+    fn test_pyerr_source_after_set_cause() {
+        use std::error::Error;
+
+        Python::with_gil(|py| {
+            let err = PyValueError::new_err("a");
+            err.set_cause(py, Some(PyTypeError::new_err("b")));
+            let first = err.source().unwrap();
+            assert_eq!(first.to_string(), "TypeError: b");
+
+            err.set_cause(py, Some(PyKeyError::new_err("c")));
+            assert_eq!(err.source().unwrap().to_string(), "KeyError: 'c'");
+            // the earlier source is still usable
+            assert_eq!(first.to_string(), "TypeError: b");
+
+            err.set_cause(py, None);
+            assert!(err.source().is_none());
+        });
+    }
+
+    #[test]
+    fn test_pyerr_source_context_cycle() {
+        use std::error::Error;
+
+        Python::with_gil(|py| {
+            let a = PyValueError::new_err("a");
+            let b = PyTypeError::new_err("b");
+            let c = PyKeyError::new_err("c");
+            for (exc, context) in [(&a, &b), (&b, &c), (&c, &a)] {
+                exc.value(py)
+                    .setattr("__context__", context.value(py))
+                    .unwrap();
+            }
+
+            let mut messages = Vec::new();
+            let mut link: Option<&(dyn Error + 'static)> = Some(&a);
+            while let Some(error) = link {
+                messages.push(error.to_string());
+                link = error.source();
+            }
+            assert_eq!(messages, ["ValueError: a", "TypeError: b", "KeyError: 'c'"]);
+        });
+    }
+
+    #[test]
+    fn test_pyerr_rust_error_roundtrip() {
+        use crate::types::IntoPyDict;
+        use std::fmt;
+
+        #[derive(Debug)]
+        struct ConfigError(std::num::ParseIntError);
+
+        impl fmt::Display for ConfigError {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("invalid config")
+            }
+        }
+
+        impl std::error::Error for ConfigError {
+            fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+                Some(&self.0)
+            }
+        }
+
+        Python::with_gil(|py| {
+            let parse_error = "x".parse::<i32>().unwrap_err();
+            let err = PyErr::from_rust_error(ConfigError(parse_error.clone()));
+            assert_eq!(err.to_string(), "RuntimeError: invalid config");
+            assert_eq!(
+                err.cause(py).unwrap().to_string(),
+                format!("RuntimeError: {}", parse_error)
+            );
+
+            let locals = [("err", err)].into_py_dict(py);
+            let err = py.run("raise err", None, Some(locals)).unwrap_err();
+            assert_eq!(
+                err.downcast_rust_error::<ConfigError>(py).unwrap().0,
+                parse_error
+            );
+            // sources of the stored error can be recovered too
+            assert_eq!(
+                err.downcast_rust_error::<std::num::ParseIntError>(py),
+                Some(&parse_error)
+            );
+            assert!(err.downcast_rust_error::<std::io::Error>(py).is_none());
+
+            // exceptions raised by Python code carry no Rust error
+            let err = PyValueError::new_err("banana");
+            assert!(err.downcast_rust_error::<ConfigError>(py).is_none());
+        });
+    }
+
+    #[test]
+    #[cfg(Py_3_11)]
+    fn test_pyerr_split_group() {
+        use crate::exceptions::PyBaseExceptionGroup;
+
+        Python::with_gil(|py| {
+            let err = PyBaseExceptionGroup::new_group(
+                "group",
+                vec![
+                    PyValueError::new_err("a"),
+                    PyTypeError::new_err("b"),
+                    PyValueError::new_err("c"),
+                ],
+            );
+
+            let (matched, rest) = err.split_group(py, PyValueError::type_object(py)).unwrap();
+            let matched = matched.unwrap();
+            let matched = matched
+                .value(py)
+                .downcast::<PyBaseExceptionGroup>()
+                .unwrap();
+            assert_eq!(matched.message().unwrap(), "group");
+            assert_eq!(matched.exceptions().unwrap().len(), 2);
+            let rest = rest.unwrap();
+            let rest = rest.value(py).downcast::<PyBaseExceptionGroup>().unwrap();
+            assert_eq!(rest.exceptions().unwrap().len(), 1);
+
+            let subgroup = err.subgroup(py, PyTypeError::type_object(py)).unwrap();
+            assert!(subgroup.unwrap().is_instance_of::<PyBaseExceptionGroup>(py));
+            assert!(err
+                .subgroup(py, exceptions::PyKeyError::type_object(py))
+                .unwrap()
+                .is_none());
+
+            // errors which are not groups are matched as a whole
+            let err = PyValueError::new_err("plain");
+            let (matched, rest) = err.split_group(py, PyValueError::type_object(py)).unwrap();
+            assert!(matched.unwrap().value(py).is(err.value(py)));
+            assert!(rest.is_none());
+            assert!(err
+                .subgroup(py, PyTypeError::type_object(py))
+                .unwrap()
+                .is_none());
+        });
+    }
+
     #[test]
     fn warnings() {
         // Note: although the warning filter is interpreter global, keeping the
@@ -0,0 +1,111 @@
+//! Support for carrying Rust errors through Python exceptions.
+
+use crate::exceptions::{PyBaseException, PyRuntimeError};
+use crate::types::PyCapsule;
+use crate::{PyErr, PyErrArguments, PyObject, PyResult, Python, ToPyObject};
+use std::any::Any;
+use std::error::Error;
+use std::ffi::{CStr, CString};
+
+/// The attribute of the exception instance which holds the capsule.
+const ATTRIBUTE_NAME: &str = "__pyo3_rust_error__";
+const CAPSULE_NAME: &[u8] = b"pyo3.rust_error\0";
+
+struct Payload(Box<dyn Any + Send + Sync>);
+
+/// Stores `payload` on the exception instance, replacing any previously stored value.
+pub(crate) fn attach(exc: &PyBaseException, payload: Box<dyn Any + Send + Sync>) -> PyResult<()> {
+    let name = CStr::from_bytes_with_nul(CAPSULE_NAME).unwrap();
+    let capsule = PyCapsule::new(exc.py(), Payload(payload), Some(CString::from(name)))?;
+    exc.setattr(crate::intern!(exc.py(), ATTRIBUTE_NAME), capsule)
+}
+
+/// Returns the value stored on the exception instance with [`attach`], if any.
+pub(crate) fn payload(exc: &PyBaseException) -> Option<&(dyn Any + Send + Sync)> {
+    let capsule: &PyCapsule = exc
+        .getattr(crate::intern!(exc.py(), ATTRIBUTE_NAME))
+        .ok()?
+        .downcast()
+        .ok()?;
+    // The capsule name guarantees the contents were created by `attach`.
+    if capsule.name().ok()?.map(CStr::to_bytes_with_nul) != Some(CAPSULE_NAME) {
+        return None;
+    }
+    let payload = unsafe { capsule.reference::<Payload>() };
+    Some(&*payload.0)
+}
+
+/// Converts `error` and its chain of sources into Python exceptions linked with `__cause__`.
+///
+/// Each Rust error becomes a `RuntimeError` with the error's message. A `PyErr` found in the
+/// chain is used as-is and ends the chain, as it carries its own Python cause.
+pub(crate) fn exception_chain(py: Python<'_>, error: &(dyn Error + 'static)) -> PyErr {
+    let mut exceptions = Vec::new();
+    let mut link = Some(error);
+    while let Some(error) = link {
+        if let Some(py_err) = error.downcast_ref::<PyErr>() {
+            exceptions.push(py_err.clone_ref(py));
+            break;
+        }
+        exceptions.push(PyRuntimeError::new_err(error.to_string()));
+        link = error.source();
+    }
+
+    let mut exceptions = exceptions.into_iter().rev();
+    let mut chain = exceptions
+        .next()
+        .expect("error chain always contains at least one error");
+    for exception in exceptions {
+        exception.set_cause(py, Some(chain));
+        chain = exception;
+    }
+    chain
+}
+
+/// Creates the chain of exceptions for a Rust error once the GIL is held, and attaches the error
+/// to the outermost exception.
+///
+/// `E` is a boxed error, or an `anyhow::Error` or `eyre::Report`, which are attached as they are so
+/// that `PyErr::as_anyhow` and `PyErr::as_eyre` can return them.
+pub(crate) struct RustErrorArguments<E>(pub(crate) E);
+
+impl<E> PyErrArguments for RustErrorArguments<E>
+where
+    E: AsRef<dyn Error + Send + Sync + 'static> + Send + Sync + 'static,
+{
+    fn arguments(self, py: Python<'_>) -> PyObject {
+        let exception = exception_chain(py, self.0.as_ref()).into_value(py);
+        // Failing to attach the original error only makes it unrecoverable from the exception
+        let _ = attach(exception.as_ref(py), Box::new(self.0));
+        exception.to_object(py)
+    }
+}
+
+/// Looks for an error of type `E` in a value stored with [`attach`].
+///
+/// Boxed errors are searched along their chain of sources; `anyhow` and `eyre` errors use their
+/// own `downcast_ref`, which also finds context values.
+pub(crate) fn downcast<E>(payload: &(dyn Any + Send + Sync)) -> Option<&E>
+where
+    E: Error + Send + Sync + 'static,
+{
+    if let Some(error) = payload.downcast_ref::<Box<dyn Error + Send + Sync>>() {
+        let mut link: Option<&(dyn Error + 'static)> = Some(&**error);
+        while let Some(error) = link {
+            if let Some(error) = error.downcast_ref() {
+                return Some(error);
+            }
+            link = error.source();
+        }
+        return None;
+    }
+    #[cfg(feature = "anyhow")]
+    if let Some(error) = payload.downcast_ref::<anyhow::Error>() {
+        return error.downcast_ref();
+    }
+    #[cfg(feature = "eyre")]
+    if let Some(error) = payload.downcast_ref::<eyre::Report>() {
+        return error.downcast_ref();
+    }
+    None
+}
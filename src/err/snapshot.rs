@@ -0,0 +1,409 @@
+use crate::exceptions::PyBaseException;
+use crate::types::PyTraceback;
+use crate::{AsPyPointer, PyAny, PyErr, Python};
+use std::fmt;
+
+/// A snapshot of a Python exception as plain Rust data.
+///
+/// Unlike [`PyErr`], a snapshot holds no Python objects, so it can be formatted, sent to other
+/// threads or processes, and dropped without holding the GIL. It is created with
+/// [`PyErr::snapshot`].
+///
+/// Like that of `PyErr`, the [`Display`](std::fmt::Display) implementation writes the type name
+/// and message. The alternate form (`{:#}`) writes the full report in the same layout as
+/// Python's default exception handler: the chain of causes, each with its traceback and notes.
+///
+/// With the `serde` feature enabled, snapshots can be serialized and deserialized.
+///
+/// # Examples
+///
+/// ```rust
+/// use pyo3::prelude::*;
+///
+/// let snapshot = Python::with_gil(|py| {
+///     let err = py
+///         .run("raise ValueError('invalid port') from KeyError('port')", None, None)
+///         .unwrap_err();
+///     err.snapshot(py)
+/// });
+///
+/// // No GIL is needed from here on.
+/// std::thread::spawn(move || {
+///     assert_eq!(snapshot.to_string(), "ValueError: invalid port");
+///     assert_eq!(snapshot.cause().unwrap().type_name(), "KeyError");
+///     assert!(format!("{:#}", snapshot).contains("direct cause"));
+/// })
+/// .join()
+/// .unwrap();
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PyErrSnapshot {
+    type_name: String,
+    message: String,
+    notes: Vec<String>,
+    traceback: Option<String>,
+    cause: Option<Box<PyErrSnapshot>>,
+    context: Option<Box<PyErrSnapshot>>,
+}
+
+impl PyErrSnapshot {
+    pub(crate) fn new(value: &PyBaseException) -> Self {
+        let chain = exception_chain(value);
+        let mut snapshot: Option<PyErrSnapshot> = None;
+        let mut snapshot_is_cause = false;
+        for (value, is_cause) in chain.into_iter().rev() {
+            let inner = snapshot.take().map(Box::new);
+            let (cause, context) = if snapshot_is_cause {
+                (inner, None)
+            } else {
+                (None, inner)
+            };
+            snapshot = Some(PyErrSnapshot {
+                type_name: type_name(value),
+                message: value
+                    .str()
+                    .map(|s| s.to_string_lossy().into_owned())
+                    .unwrap_or_else(|_| "<exception str() failed>".to_string()),
+                notes: notes(value),
+                traceback: value
+                    .getattr(crate::intern!(value.py(), "__traceback__"))
+                    .ok()
+                    .and_then(|traceback| traceback.downcast::<PyTraceback>().ok())
+                    .and_then(|traceback| traceback.format().ok()),
+                cause,
+                context,
+            });
+            snapshot_is_cause = is_cause;
+        }
+        snapshot.expect("exception chain always contains at least one exception")
+    }
+
+    /// Returns the name of the exception type, qualified with its module unless it is a
+    /// built-in type, e.g. `ValueError` or `mymodule.MyError`.
+    pub fn type_name(&self) -> &str {
+        &self.type_name
+    }
+
+    /// Returns the exception message, i.e. `str(exception)`.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the notes attached to the exception with `add_note` (`__notes__`).
+    pub fn notes(&self) -> &[String] {
+        &self.notes
+    }
+
+    /// Returns the formatted traceback of the exception, if it has one.
+    pub fn traceback(&self) -> Option<&str> {
+        self.traceback.as_deref()
+    }
+
+    /// Returns the snapshot of the exception's explicit cause (`__cause__`), if any.
+    pub fn cause(&self) -> Option<&PyErrSnapshot> {
+        self.cause.as_deref()
+    }
+
+    /// Returns the snapshot of the exception which was being handled when this exception was
+    /// raised (`__context__`), if any.
+    ///
+    /// As in Python tracebacks, the context is omitted when the exception has an explicit cause
+    /// or sets `__suppress_context__`.
+    pub fn context(&self) -> Option<&PyErrSnapshot> {
+        self.context.as_deref()
+    }
+
+    /// Writes the report for this exception and its causes, innermost first.
+    fn fmt_report(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(cause) = self.cause() {
+            cause.fmt_report(f)?;
+            f.write_str(
+                "\nThe above exception was the direct cause of the following exception:\n\n",
+            )?;
+        } else if let Some(context) = self.context() {
+            context.fmt_report(f)?;
+            f.write_str(
+                "\nDuring handling of the above exception, another exception occurred:\n\n",
+            )?;
+        }
+        if let Some(traceback) = self.traceback() {
+            f.write_str(traceback)?;
+        }
+        if self.message.is_empty() {
+            writeln!(f, "{}", self.type_name)?;
+        } else {
+            writeln!(f, "{}: {}", self.type_name, self.message)?;
+        }
+        for note in &self.notes {
+            writeln!(f, "{}", note)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for PyErrSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            self.fmt_report(f)
+        } else {
+            write!(f, "{}: {}", self.type_name, self.message)
+        }
+    }
+}
+
+impl std::error::Error for PyErrSnapshot {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.cause()
+            .or_else(|| self.context())
+            .map(|snapshot| snapshot as _)
+    }
+}
+
+impl From<PyErr> for PyErrSnapshot {
+    fn from(err: PyErr) -> Self {
+        Python::with_gil(|py| err.snapshot(py))
+    }
+}
+
+#[cfg(feature = "serde")]
+const FIELDS: &[&str] = &[
+    "type_name",
+    "message",
+    "notes",
+    "traceback",
+    "cause",
+    "context",
+];
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for PyErrSnapshot {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+        let mut state = serializer.serialize_struct("PyErrSnapshot", FIELDS.len())?;
+        state.serialize_field("type_name", &self.type_name)?;
+        state.serialize_field("message", &self.message)?;
+        state.serialize_field("notes", &self.notes)?;
+        state.serialize_field("traceback", &self.traceback)?;
+        state.serialize_field("cause", &self.cause)?;
+        state.serialize_field("context", &self.context)?;
+        state.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for PyErrSnapshot {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::{self, MapAccess, SeqAccess};
+
+        struct SnapshotVisitor;
+
+        impl<'de> de::Visitor<'de> for SnapshotVisitor {
+            type Value = PyErrSnapshot;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("struct PyErrSnapshot")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                fn next<'de, T, A>(seq: &mut A, index: usize) -> Result<T, A::Error>
+                where
+                    T: serde::Deserialize<'de>,
+                    A: SeqAccess<'de>,
+                {
+                    seq.next_element()?.ok_or_else(|| {
+                        de::Error::invalid_length(index, &"struct PyErrSnapshot with 6 elements")
+                    })
+                }
+                Ok(PyErrSnapshot {
+                    type_name: next(&mut seq, 0)?,
+                    message: next(&mut seq, 1)?,
+                    notes: next(&mut seq, 2)?,
+                    traceback: next(&mut seq, 3)?,
+                    cause: next(&mut seq, 4)?,
+                    context: next(&mut seq, 5)?,
+                })
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut type_name = None;
+                let mut message = None;
+                let mut notes = None;
+                let mut traceback = None;
+                let mut cause = None;
+                let mut context = None;
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        "type_name" => type_name = Some(map.next_value()?),
+                        "message" => message = Some(map.next_value()?),
+                        "notes" => notes = Some(map.next_value()?),
+                        "traceback" => traceback = Some(map.next_value()?),
+                        "cause" => cause = Some(map.next_value()?),
+                        "context" => context = Some(map.next_value()?),
+                        _ => return Err(de::Error::unknown_field(&key, FIELDS)),
+                    }
+                }
+                Ok(PyErrSnapshot {
+                    type_name: type_name.ok_or_else(|| de::Error::missing_field("type_name"))?,
+                    message: message.ok_or_else(|| de::Error::missing_field("message"))?,
+                    notes: notes.ok_or_else(|| de::Error::missing_field("notes"))?,
+                    traceback: traceback.unwrap_or_default(),
+                    cause: cause.unwrap_or_default(),
+                    context: context.unwrap_or_default(),
+                })
+            }
+        }
+
+        deserializer.deserialize_struct("PyErrSnapshot", FIELDS, SnapshotVisitor)
+    }
+}
+
+/// Returns `value` followed by its causes or contexts, each with whether it is an explicit cause.
+///
+/// As in Python's traceback module, the chain stops before the first repeated exception.
+pub(super) fn exception_chain(value: &PyBaseException) -> Vec<(&PyBaseException, bool)> {
+    let mut chain: Vec<(&PyBaseException, bool)> = Vec::new();
+    let mut next = Some((value, false));
+    while let Some((value, is_cause)) = next {
+        if chain
+            .iter()
+            .any(|(seen, _)| seen.as_ptr() == value.as_ptr())
+        {
+            break;
+        }
+        chain.push((value, is_cause));
+        next = next_in_chain(value);
+    }
+    chain
+}
+
+/// Returns the next exception in the chain, and whether it is an explicit cause.
+fn next_in_chain(value: &PyBaseException) -> Option<(&PyBaseException, bool)> {
+    fn downcast(obj: &PyAny) -> Option<&PyBaseException> {
+        obj.downcast().ok()
+    }
+
+    let py = value.py();
+    if let Some(cause) = value
+        .getattr(crate::intern!(py, "__cause__"))
+        .ok()
+        .and_then(downcast)
+    {
+        return Some((cause, true));
+    }
+    let suppress_context = value
+        .getattr(crate::intern!(py, "__suppress_context__"))
+        .and_then(|suppress| suppress.is_true())
+        .unwrap_or(false);
+    if suppress_context {
+        return None;
+    }
+    value
+        .getattr(crate::intern!(py, "__context__"))
+        .ok()
+        .and_then(downcast)
+        .map(|context| (context, false))
+}
+
+fn type_name(value: &PyBaseException) -> String {
+    let ty = value.get_type();
+    let qualname = ty.name().unwrap_or("<unknown>");
+    match ty
+        .getattr(crate::intern!(value.py(), "__module__"))
+        .and_then(|module| module.extract::<&str>())
+    {
+        Ok(module) if module != "builtins" && module != "__main__" => {
+            format!("{}.{}", module, qualname)
+        }
+        _ => qualname.to_string(),
+    }
+}
+
+fn notes(value: &PyBaseException) -> Vec<String> {
+    value
+        .getattr(crate::intern!(value.py(), "__notes__"))
+        .and_then(|notes| notes.iter())
+        .map(|notes| {
+            notes
+                .flatten()
+                .map(|note| match note.str() {
+                    Ok(note) => note.to_string_lossy().into_owned(),
+                    Err(_) => "<note str() failed>".to_string(),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PyErrSnapshot;
+    use crate::exceptions::{PyKeyError, PyValueError};
+    use crate::{PyErr, Python};
+
+    #[test]
+    fn test_snapshot_chain() {
+        Python::with_gil(|py| {
+            let err = py
+                .run(
+                    r#"
+try:
+    try:
+        {}["port"]
+    except KeyError as e:
+        raise ValueError("invalid config") from e
+except ValueError:
+    raise RuntimeError("startup failed")
+"#,
+                    None,
+                    None,
+                )
+                .unwrap_err();
+            err.add_note(py, "while starting the server").unwrap();
+
+            let snapshot = err.snapshot(py);
+            assert_eq!(snapshot.type_name(), "RuntimeError");
+            assert_eq!(snapshot.message(), "startup failed");
+            assert_eq!(snapshot.notes(), ["while starting the server"]);
+            assert!(snapshot
+                .traceback()
+                .unwrap()
+                .starts_with("Traceback (most recent call last):"));
+            assert!(snapshot.cause().is_none());
+
+            let context = snapshot.context().unwrap();
+            assert_eq!(context.to_string(), "ValueError: invalid config");
+            let cause = context.cause().unwrap();
+            assert_eq!(cause.to_string(), "KeyError: 'port'");
+            assert!(cause.cause().is_none() && cause.context().is_none());
+
+            let report = format!("{:#}", snapshot);
+            let key_error = report.find("KeyError: 'port'\n").unwrap();
+            let value_error = report.find("direct cause").unwrap();
+            let runtime_error = report.find("During handling").unwrap();
+            assert!(key_error < value_error && value_error < runtime_error);
+            assert!(report.ends_with("RuntimeError: startup failed\nwhile starting the server\n"));
+        });
+    }
+
+    #[test]
+    fn test_snapshot_cycle() {
+        Python::with_gil(|py| {
+            let err = PyValueError::new_err("a");
+            let other = PyKeyError::new_err("b");
+            other.set_cause(py, Some(err.clone_ref(py)));
+            err.set_cause(py, Some(other));
+
+            let snapshot = PyErrSnapshot::from(err);
+            assert_eq!(snapshot.cause().unwrap().type_name(), "KeyError");
+            assert!(snapshot.cause().unwrap().cause().is_none());
+        });
+    }
+
+    #[test]
+    fn test_snapshot_is_send() {
+        fn is_send_sync<T: Send + Sync>() {}
+        is_send_sync::<PyErrSnapshot>();
+
+        let _: fn(PyErr) -> PyErrSnapshot = PyErrSnapshot::from;
+    }
+}
@@ -10,6 +10,8 @@
 //! `BaseException`.
 
 use crate::{ffi, PyResult, Python};
+#[cfg(Py_3_11)]
+use crate::{types::PyTuple, PyAny};
 use std::ffi::CStr;
 use std::ops;
 use std::os::raw::c_char;
@@ -256,6 +258,37 @@ macro_rules! create_exception_type_object {
     };
 }
 
+/// A Rust error enum which maps to a hierarchy of Python exception types.
+///
+/// This trait is implemented by `#[derive(PyException)]`, which creates a base exception type
+/// for the enum and one subclass of it for each variant. See the [exceptions chapter of the
+/// guide](https://pyo3.rs/latest/exception.html#mapping-rust-error-enums-to-exceptions) for
+/// details.
+pub trait PyExceptionEnum: Sized + for<'source> crate::FromPyObject<'source> {
+    /// Returns the base exception type which all variant exception types derive from.
+    fn base_type_object(py: Python<'_>) -> &crate::types::PyType;
+
+    /// Returns the exception type of each variant, in declaration order.
+    fn variant_type_objects(py: Python<'_>) -> Vec<&crate::types::PyType>;
+
+    /// Adds the base exception type and all variant exception types to `module`.
+    fn add_to_module(module: &crate::types::PyModule) -> PyResult<()> {
+        let py = module.py();
+        let base = Self::base_type_object(py);
+        module.add(base.name()?, base)?;
+        for ty in Self::variant_type_objects(py) {
+            module.add(ty.name()?, ty)?;
+        }
+        Ok(())
+    }
+
+    /// Converts `err` back into `Self`, if it is an instance of one of the variant exception
+    /// types.
+    fn from_err(py: Python<'_>, err: &crate::PyErr) -> Option<Self> {
+        err.value(py).extract().ok()
+    }
+}
+
 macro_rules! impl_native_exception (
     ($name:ident, $exc_name:ident, $doc:expr, $layout:path $(, #checkfunction=$checkfunction:path)?) => (
         #[doc = $doc]
@@ -666,6 +699,70 @@ impl PyUnicodeDecodeError {
     }
 }
 
+#[cfg(Py_3_11)]
+impl PyBaseExceptionGroup {
+    /// Creates a new [`PyErr`] containing an exception group of `exceptions`.
+    ///
+    /// As in Python, the resulting exception is an `ExceptionGroup` if all of `exceptions` are
+    /// instances of `Exception`, and a `BaseExceptionGroup` otherwise. Python raises a
+    /// `ValueError` when `exceptions` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pyo3::prelude::*;
+    /// use pyo3::exceptions::{PyBaseExceptionGroup, PyTypeError, PyValueError};
+    ///
+    /// Python::with_gil(|py| {
+    ///     let err = PyBaseExceptionGroup::new_group(
+    ///         "batch failed",
+    ///         vec![PyValueError::new_err("bad value"), PyTypeError::new_err("bad type")],
+    ///     );
+    ///     assert_eq!(err.to_string(), "ExceptionGroup: batch failed (2 sub-exceptions)");
+    /// });
+    /// ```
+    pub fn new_group(message: impl Into<String>, exceptions: Vec<crate::PyErr>) -> crate::PyErr {
+        Self::new_err((message.into(), exceptions))
+    }
+
+    /// Returns the message given when the exception group was created.
+    pub fn message(&self) -> PyResult<&str> {
+        self.getattr(crate::intern!(self.py(), "message"))?
+            .extract()
+    }
+
+    /// Returns the tuple of exceptions contained in this group.
+    pub fn exceptions(&self) -> PyResult<&PyTuple> {
+        self.getattr(crate::intern!(self.py(), "exceptions"))?
+            .extract()
+    }
+
+    /// Returns an exception group containing only the exceptions which match `condition`,
+    /// or `None` if no exceptions match.
+    ///
+    /// `condition` can be an exception type, a tuple of exception types, or a callable which
+    /// accepts an exception and returns `True` for those which should be kept. This is the
+    /// equivalent of `BaseExceptionGroup.subgroup` in Python.
+    pub fn subgroup(&self, condition: &PyAny) -> PyResult<Option<&PyBaseExceptionGroup>> {
+        self.call_method1(crate::intern!(self.py(), "subgroup"), (condition,))?
+            .extract()
+    }
+
+    /// Splits this exception group into the exceptions which match `condition` and those which
+    /// do not. Either half is `None` if it would be empty.
+    ///
+    /// `condition` has the same meaning as in [`subgroup`](PyBaseExceptionGroup::subgroup). This
+    /// is the equivalent of `BaseExceptionGroup.split` in Python.
+    #[allow(clippy::type_complexity)]
+    pub fn split(
+        &self,
+        condition: &PyAny,
+    ) -> PyResult<(Option<&PyBaseExceptionGroup>, Option<&PyBaseExceptionGroup>)> {
+        self.call_method1(crate::intern!(self.py(), "split"), (condition,))?
+            .extract()
+    }
+}
+
 impl_native_exception!(PyWarning, PyExc_Warning, native_doc!("Warning"));
 impl_native_exception!(PyUserWarning, PyExc_UserWarning, native_doc!("UserWarning"));
 impl_native_exception!(
@@ -1031,6 +1128,50 @@ mod tests {
             );
         });
     }
+
+    #[test]
+    #[cfg(Py_3_11)]
+    fn exception_group() {
+        Python::with_gil(|py| {
+            let err = PyBaseExceptionGroup::new_group(
+                "errors",
+                vec![
+                    PyValueError::new_err("a"),
+                    PyKeyboardInterrupt::new_err("b"),
+                ],
+            );
+            // contains a BaseException, so is not an ExceptionGroup
+            assert_eq!(err.get_type(py).name().unwrap(), "BaseExceptionGroup");
+
+            let group: &PyBaseExceptionGroup = err.value(py).downcast().unwrap();
+            assert_eq!(group.message().unwrap(), "errors");
+            let messages: Vec<String> = group
+                .exceptions()
+                .unwrap()
+                .iter()
+                .map(|exc| PyErr::from_value(exc).to_string())
+                .collect();
+            assert_eq!(messages, ["ValueError: a", "KeyboardInterrupt: b"]);
+
+            let is_value_error = py
+                .eval("lambda e: isinstance(e, ValueError)", None, None)
+                .unwrap();
+            let (matched, rest) = group.split(is_value_error).unwrap();
+            assert_eq!(
+                matched.unwrap().get_type().name().unwrap(),
+                "ExceptionGroup"
+            );
+            assert_eq!(rest.unwrap().exceptions().unwrap().len(), 1);
+            assert!(group
+                .subgroup(py.get_type::<PyTypeError>())
+                .unwrap()
+                .is_none());
+
+            let empty = PyBaseExceptionGroup::new_group("empty", Vec::new());
+            assert!(empty.is_instance_of::<PyValueError>(py));
+        });
+    }
+
     #[cfg(Py_3_11)]
     test_exception!(PyBaseExceptionGroup, |_| PyBaseExceptionGroup::new_err((
         "msg",
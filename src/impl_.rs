@@ -6,16 +6,23 @@
 //! APIs may may change at any time without documentation in the CHANGELOG and without
 //! breaking semver guarantees.
 
+pub mod copy;
+pub mod dataclass;
 pub mod deprecations;
 pub mod extract_argument;
 pub mod freelist;
 pub mod frompyobject;
+pub mod generic;
 pub(crate) mod not_send;
+pub mod ops;
 pub mod panic;
+pub mod pickle;
 pub mod pycell;
 pub mod pyclass;
+pub mod pyexception;
 pub mod pyfunction;
 pub mod pymethods;
 pub mod pymodule;
+pub mod pytrait;
 #[doc(hidden)]
 pub mod trampoline;
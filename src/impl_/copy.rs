@@ -0,0 +1,94 @@
+use crate::exceptions::PyAttributeError;
+use crate::pyclass_init::PyClassInitializer;
+use crate::types::PyDict;
+use crate::{intern, AsPyPointer, PyCell, PyClass, PyObject, PyResult};
+
+/// Implements `__copy__` for `#[pyclass(clone)]`, with a `Clone` of `slf` in a new object of the
+/// same type, which may be a Python subclass, and a shallow copy of its `__dict__`.
+pub fn copy<T>(slf: &PyCell<T>) -> PyResult<PyObject>
+where
+    T: PyClass + Clone + Into<PyClassInitializer<T>>,
+{
+    let copy = new_object(slf, slf.try_borrow()?.clone())?;
+    if let Some(dict) = instance_dict(slf)? {
+        copy_dict(copy, dict)?;
+    }
+    Ok(copy.into())
+}
+
+/// Creates the object returned by `__deepcopy__` for a class which isn't frozen, from a `Clone`
+/// of `slf`, and stores it in `memo` under `id(slf)` before any field is deep copied, so that
+/// objects which refer back to `slf` are copied to refer to the new object. The fields are then
+/// replaced with their deep copies by the generated code.
+pub fn new_deep_copy<'py, T>(slf: &'py PyCell<T>, memo: &PyDict) -> PyResult<&'py PyCell<T>>
+where
+    T: PyClass + Clone + Into<PyClassInitializer<T>>,
+{
+    let copy = new_object(slf, slf.try_borrow()?.clone())?;
+    memo.set_item(slf.as_ptr() as usize, copy)?;
+    deep_copy_dict(slf, copy, memo)?;
+    Ok(copy)
+}
+
+/// Implements `__deepcopy__` for a frozen class, or one without fields to deep copy, from `value`
+/// holding the deep copies of the fields of `slf`.
+///
+/// The fields of a frozen object can't be replaced once it is created, so like `copy.deepcopy`
+/// does for a tuple, the fields are copied first, and the new object is only stored in `memo`
+/// once it is complete. If copying the fields already copied `slf`, through an object which
+/// refers back to it, that copy is returned instead.
+pub fn deep_copy_value<T>(slf: &PyCell<T>, memo: &PyDict, value: T) -> PyResult<PyObject>
+where
+    T: PyClass + Into<PyClassInitializer<T>>,
+{
+    let id = slf.as_ptr() as usize;
+    if let Some(copy) = memo.get_item(id) {
+        return Ok(copy.into());
+    }
+    let copy = new_object(slf, value)?;
+    memo.set_item(id, copy)?;
+    deep_copy_dict(slf, copy, memo)?;
+    Ok(copy.into())
+}
+
+/// Creates an object of the same type as `slf`, holding `value`.
+fn new_object<T>(slf: &PyCell<T>, value: T) -> PyResult<&PyCell<T>>
+where
+    T: PyClass + Into<PyClassInitializer<T>>,
+{
+    let py = slf.py();
+    unsafe {
+        let cell = value
+            .into()
+            .create_cell_from_subtype(py, slf.get_type().as_type_ptr())?;
+        py.from_owned_ptr_or_err(cell as *mut crate::ffi::PyObject)
+    }
+}
+
+/// Copies the deep copy of the `__dict__` of `slf`, if it has one, to `copy`.
+fn deep_copy_dict<T: PyClass>(slf: &PyCell<T>, copy: &PyCell<T>, memo: &PyDict) -> PyResult<()> {
+    if let Some(dict) = instance_dict(slf)? {
+        let py = slf.py();
+        let dict = py
+            .import(intern!(py, "copy"))?
+            .getattr(intern!(py, "deepcopy"))?
+            .call1((dict, memo))?
+            .downcast()?;
+        copy_dict(copy, dict)?;
+    }
+    Ok(())
+}
+
+/// The `__dict__` of the instance, which a Python subclass or the `dict` option gives it.
+fn instance_dict<T: PyClass>(slf: &PyCell<T>) -> PyResult<Option<&PyDict>> {
+    match slf.getattr(intern!(slf.py(), "__dict__")) {
+        Ok(dict) => Ok(Some(dict.downcast()?)),
+        Err(err) if err.is_instance_of::<PyAttributeError>(slf.py()) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+fn copy_dict<T: PyClass>(copy: &PyCell<T>, dict: &PyDict) -> PyResult<()> {
+    let copy_dict: &PyDict = copy.getattr(intern!(copy.py(), "__dict__"))?.downcast()?;
+    copy_dict.update(dict.as_mapping())
+}
@@ -0,0 +1,141 @@
+use crate::exceptions::{PyRuntimeWarning, PyTypeError};
+use crate::types::{IntoPyDict, PyDict, PyModule};
+use crate::{PyErr, PyObject, PyResult, Python};
+
+/// A field of a `#[pyclass(dataclass)]`, as described by `__dataclass_fields__`.
+pub struct DataclassField {
+    pub name: &'static str,
+    pub type_name: &'static str,
+    pub default: Option<PyObject>,
+}
+
+/// Creates the `__dataclass_fields__` class attribute, a dict of `dataclasses.Field` objects, so
+/// that `dataclasses.fields()`, `dataclasses.asdict()` and `dataclasses.is_dataclass()` support
+/// the class.
+pub fn dataclass_fields(py: Python<'_>, fields: Vec<DataclassField>) -> PyResult<PyObject> {
+    let dataclasses = PyModule::import(py, "dataclasses")?;
+    // `dataclasses.fields()` only returns the fields whose private `_field_type` is `_FIELD`,
+    // which `@dataclass` sets itself. There is no public API to do the same, so if a Python
+    // version drops `_FIELD` the fields are still described, but only with a warning that
+    // `dataclasses.fields()` may not list them, rather than failing to create the class.
+    let field_type = match dataclasses.getattr("_FIELD") {
+        Ok(field_type) => Some(field_type),
+        Err(_) => {
+            PyErr::warn(
+                py,
+                py.get_type::<PyRuntimeWarning>(),
+                "`dataclasses._FIELD` is missing, so `dataclasses.fields()` may not list the \
+                 fields of a `#[pyclass(dataclass)]`",
+                0,
+            )?;
+            None
+        }
+    };
+    let result = PyDict::new(py);
+    for field in fields {
+        let kwargs = field
+            .default
+            .map(|default| [("default", default)].into_py_dict(py));
+        let object = dataclasses.getattr("field")?.call((), kwargs)?;
+        object.setattr("name", field.name)?;
+        object.setattr("type", field.type_name)?;
+        if let Some(field_type) = field_type {
+            object.setattr("_field_type", field_type)?;
+        }
+        result.set_item(field.name, object)?;
+    }
+    Ok(result.into())
+}
+
+/// The error raised by `__replace__` for a keyword argument which is not a field.
+pub fn unexpected_replace_argument(name: &str) -> PyErr {
+    PyTypeError::new_err(format!(
+        "__replace__() got an unexpected keyword argument '{}'",
+        name
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IntoPy;
+
+    #[test]
+    fn test_private_dataclasses_api() {
+        Python::with_gil(|py| {
+            let fields = dataclass_fields(
+                py,
+                vec![DataclassField {
+                    name: "x",
+                    type_name: "i32",
+                    default: Some(1_i32.into_py(py)),
+                }],
+            )
+            .unwrap();
+            let globals = PyDict::new(py);
+            globals.set_item("pyo3_fields", fields).unwrap();
+            py.run(
+                r#"
+import dataclasses
+
+@dataclasses.dataclass
+class Expected:
+    x: "i32" = 1
+
+expected = dataclasses.fields(Expected)[0]
+actual = pyo3_fields["x"]
+assert actual._field_type is expected._field_type is dataclasses._FIELD
+for attr in ("name", "type", "default", "default_factory", "init", "repr", "compare"):
+    assert getattr(actual, attr) == getattr(expected, attr), attr
+
+class Actual:
+    __dataclass_fields__ = pyo3_fields
+
+assert dataclasses.fields(Actual) == (actual,)
+"#,
+                Some(globals),
+                None,
+            )
+            .unwrap();
+        })
+    }
+
+    #[test]
+    fn test_dataclass_fields_without_private_api() {
+        Python::with_gil(|py| {
+            let dataclasses = PyModule::import(py, "dataclasses").unwrap();
+            let field_type = dataclasses.getattr("_FIELD").unwrap();
+            dataclasses.delattr("_FIELD").unwrap();
+            let warnings = PyModule::import(py, "warnings").unwrap();
+            let catcher = warnings
+                .call_method(
+                    "catch_warnings",
+                    (),
+                    Some([("record", true)].into_py_dict(py)),
+                )
+                .unwrap();
+            let caught = catcher.call_method0("__enter__").unwrap();
+            let fields = dataclass_fields(
+                py,
+                vec![DataclassField {
+                    name: "x",
+                    type_name: "i32",
+                    default: None,
+                }],
+            );
+            catcher
+                .call_method1("__exit__", (py.None(), py.None(), py.None()))
+                .unwrap();
+            dataclasses.setattr("_FIELD", field_type).unwrap();
+
+            let fields: &PyDict = fields.unwrap().into_ref(py).downcast().unwrap();
+            assert_eq!(fields.len(), 1);
+            assert_eq!(caught.len().unwrap(), 1);
+            let warning = caught.get_item(0).unwrap();
+            assert!(warning
+                .getattr("category")
+                .unwrap()
+                .is(py.get_type::<PyRuntimeWarning>()));
+        })
+    }
+}
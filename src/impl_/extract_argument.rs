@@ -3,7 +3,7 @@ use crate::{
     ffi,
     pyclass::boolean_struct::False,
     types::{PyDict, PyString, PyTuple},
-    FromPyObject, PyAny, PyClass, PyErr, PyRef, PyRefMut, PyResult, Python,
+    AsPyPointer, FromPyObject, PyAny, PyClass, PyErr, PyRef, PyRefMut, PyResult, Python,
 };
 
 /// A trait which is used to help PyO3 macros extract function arguments.
@@ -180,22 +180,36 @@ pub unsafe fn unwrap_required_argument(argument: Option<&PyAny>) -> &PyAny {
     }
 }
 
-pub struct KeywordOnlyParameterDescription {
-    pub name: &'static str,
+/// Description of a keyword-only parameter of a [`FunctionDescription`].
+#[doc(hidden)]
+pub struct KeywordOnlyParameterDescription<'a> {
+    /// The name of the parameter.
+    pub name: &'a str,
+    /// Whether the parameter must be provided, i.e. it has no default value.
     pub required: bool,
 }
 
 /// Function argument specification for a `#[pyfunction]` or `#[pymethod]`.
-pub struct FunctionDescription {
-    pub cls_name: Option<&'static str>,
-    pub func_name: &'static str,
-    pub positional_parameter_names: &'static [&'static str],
+///
+/// This is the signature used to sort the arguments of a Python call into parameters. Signatures
+/// built at runtime with [`Signature`](crate::signature::Signature) are converted to it.
+#[doc(hidden)]
+pub struct FunctionDescription<'a> {
+    /// The name of the class the function is defined on, used in error messages.
+    pub cls_name: Option<&'a str>,
+    /// The name of the function, used in error messages.
+    pub func_name: &'a str,
+    /// The names of all positional parameters, including positional-only parameters.
+    pub positional_parameter_names: &'a [&'a str],
+    /// The number of leading positional parameters which cannot be passed by keyword.
     pub positional_only_parameters: usize,
+    /// The number of leading positional parameters which must be provided.
     pub required_positional_parameters: usize,
-    pub keyword_only_parameters: &'static [KeywordOnlyParameterDescription],
+    /// The keyword-only parameters, which follow `*args` (or `*`) in a Python signature.
+    pub keyword_only_parameters: &'a [KeywordOnlyParameterDescription<'a>],
 }
 
-impl FunctionDescription {
+impl FunctionDescription<'_> {
     fn full_name(&self) -> String {
         if let Some(cls_name) = self.cls_name {
             format!("{}.{}()", cls_name, self.func_name)
@@ -204,6 +218,57 @@ impl FunctionDescription {
         }
     }
 
+    /// Sorts the `args` and `kwargs` of a Python call into `output`, according to this function
+    /// definition.
+    ///
+    /// `output` must have one entry for each positional parameter followed by one entry for each
+    /// keyword-only parameter. After a successful call, the entries of all required parameters
+    /// are `Some`, while the entries of optional parameters which were not provided are `None`.
+    ///
+    /// `V` and `K` control whether surplus positional and keyword arguments are collected (with
+    /// [`TupleVarargs`] and [`DictVarkeywords`]) or rejected (with [`NoVarargs`] and
+    /// [`NoVarkeywords`]). The collected arguments are returned.
+    ///
+    /// Unexpected, duplicate or missing arguments cause a `TypeError` with the same message as a
+    /// `#[pyfunction]` with this signature would raise.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `output` has the wrong length.
+    pub(crate) fn extract_arguments<'py, V, K>(
+        &self,
+        args: &'py PyTuple,
+        kwargs: Option<&'py PyDict>,
+        output: &mut [Option<&'py PyAny>],
+    ) -> PyResult<(V::Varargs, K::Varkeywords)>
+    where
+        V: VarargsHandler<'py>,
+        K: VarkeywordsHandler<'py>,
+    {
+        self.assert_output_len(output);
+        // Safety: `args` is a tuple and `kwargs` is a dict or NULL
+        unsafe {
+            self.extract_arguments_tuple_dict::<V, K>(
+                args.py(),
+                args.as_ptr(),
+                kwargs.map_or(std::ptr::null_mut(), |kwargs| kwargs.as_ptr()),
+                output,
+            )
+        }
+    }
+
+    pub(crate) fn assert_output_len(&self, output: &[Option<&PyAny>]) {
+        let expected = self.positional_parameter_names.len() + self.keyword_only_parameters.len();
+        assert_eq!(
+            output.len(),
+            expected,
+            "{} has {} parameters, but the output has length {}",
+            self.full_name(),
+            expected,
+            output.len()
+        );
+    }
+
     /// Equivalent of `extract_arguments_tuple_dict` which uses the Python C-API "fastcall" convention.
     ///
     /// # Safety
@@ -557,18 +622,20 @@ pub trait VarargsHandler<'py> {
     fn handle_varargs_fastcall(
         py: Python<'py>,
         varargs: &[Option<&PyAny>],
-        function_description: &FunctionDescription,
+        function_description: &FunctionDescription<'_>,
     ) -> PyResult<Self::Varargs>;
     /// Called by `FunctionDescription::extract_arguments_tuple_dict` with the original tuple.
     ///
     /// Additional arguments are those in the tuple slice starting from `function_description.positional_parameter_names.len()`.
     fn handle_varargs_tuple(
         args: &'py PyTuple,
-        function_description: &FunctionDescription,
+        function_description: &FunctionDescription<'_>,
     ) -> PyResult<Self::Varargs>;
 }
 
 /// Marker struct which indicates varargs are not allowed.
+///
+/// Surplus positional arguments cause a `TypeError`.
 pub struct NoVarargs;
 
 impl<'py> VarargsHandler<'py> for NoVarargs {
@@ -578,7 +645,7 @@ impl<'py> VarargsHandler<'py> for NoVarargs {
     fn handle_varargs_fastcall(
         _py: Python<'py>,
         varargs: &[Option<&PyAny>],
-        function_description: &FunctionDescription,
+        function_description: &FunctionDescription<'_>,
     ) -> PyResult<Self::Varargs> {
         let extra_arguments = varargs.len();
         if extra_arguments > 0 {
@@ -592,7 +659,7 @@ impl<'py> VarargsHandler<'py> for NoVarargs {
     #[inline]
     fn handle_varargs_tuple(
         args: &'py PyTuple,
-        function_description: &FunctionDescription,
+        function_description: &FunctionDescription<'_>,
     ) -> PyResult<Self::Varargs> {
         let positional_parameter_count = function_description.positional_parameter_names.len();
         let provided_args_count = args.len();
@@ -605,6 +672,8 @@ impl<'py> VarargsHandler<'py> for NoVarargs {
 }
 
 /// Marker struct which indicates varargs should be collected into a `PyTuple`.
+///
+/// The tuple is empty if there were no surplus positional arguments.
 pub struct TupleVarargs;
 
 impl<'py> VarargsHandler<'py> for TupleVarargs {
@@ -613,7 +682,7 @@ impl<'py> VarargsHandler<'py> for TupleVarargs {
     fn handle_varargs_fastcall(
         py: Python<'py>,
         varargs: &[Option<&PyAny>],
-        _function_description: &FunctionDescription,
+        _function_description: &FunctionDescription<'_>,
     ) -> PyResult<Self::Varargs> {
         Ok(PyTuple::new(py, varargs))
     }
@@ -621,7 +690,7 @@ impl<'py> VarargsHandler<'py> for TupleVarargs {
     #[inline]
     fn handle_varargs_tuple(
         args: &'py PyTuple,
-        function_description: &FunctionDescription,
+        function_description: &FunctionDescription<'_>,
     ) -> PyResult<Self::Varargs> {
         let positional_parameters = function_description.positional_parameter_names.len();
         Ok(args.get_slice(positional_parameters, args.len()))
@@ -631,15 +700,19 @@ impl<'py> VarargsHandler<'py> for TupleVarargs {
 /// A trait used to control whether to accept varkeywords in FunctionDescription::extract_argument_(method) functions.
 pub trait VarkeywordsHandler<'py> {
     type Varkeywords: Default;
+    /// Called by the `FunctionDescription::extract_arguments` functions with each keyword
+    /// argument which does not match a parameter.
     fn handle_varkeyword(
         varkeywords: &mut Self::Varkeywords,
         name: &'py PyAny,
         value: &'py PyAny,
-        function_description: &FunctionDescription,
+        function_description: &FunctionDescription<'_>,
     ) -> PyResult<()>;
 }
 
 /// Marker struct which indicates unknown keywords are not permitted.
+///
+/// Unknown keyword arguments cause a `TypeError`.
 pub struct NoVarkeywords;
 
 impl<'py> VarkeywordsHandler<'py> for NoVarkeywords {
@@ -649,13 +722,15 @@ impl<'py> VarkeywordsHandler<'py> for NoVarkeywords {
         _varkeywords: &mut Self::Varkeywords,
         name: &'py PyAny,
         _value: &'py PyAny,
-        function_description: &FunctionDescription,
+        function_description: &FunctionDescription<'_>,
     ) -> PyResult<()> {
         Err(function_description.unexpected_keyword_argument(name))
     }
 }
 
 /// Marker struct which indicates unknown keywords should be collected into a `PyDict`.
+///
+/// The dict is `None` if there were no unknown keyword arguments.
 pub struct DictVarkeywords;
 
 impl<'py> VarkeywordsHandler<'py> for DictVarkeywords {
@@ -665,7 +740,7 @@ impl<'py> VarkeywordsHandler<'py> for DictVarkeywords {
         varkeywords: &mut Self::Varkeywords,
         name: &'py PyAny,
         value: &'py PyAny,
-        _function_description: &FunctionDescription,
+        _function_description: &FunctionDescription<'_>,
     ) -> PyResult<()> {
         varkeywords
             .get_or_insert_with(|| PyDict::new(name.py()))
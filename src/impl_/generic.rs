@@ -0,0 +1,60 @@
+#[cfg(feature = "experimental-inspect")]
+use crate::inspect::types::{ModuleName, TypeInfo};
+use crate::types::PyType;
+#[cfg(feature = "experimental-inspect")]
+use crate::PyTypeInfo;
+use crate::{intern, PyAny, PyObject, PyResult};
+#[cfg(feature = "experimental-inspect")]
+use std::borrow::Cow;
+
+/// Implements `__class_getitem__` for a `#[pyclass(generic)]`, so that `cls[item]` is a
+/// `types.GenericAlias`, like the subscripted builtin containers.
+///
+/// `types.GenericAlias` was added in Python 3.9, so on older versions `cls[item]` is `cls` itself,
+/// which is still usable in annotations.
+///
+/// The class has no type variables, so `item` isn't checked against any `__parameters__`.
+pub fn class_getitem(cls: &PyType, item: &PyAny) -> PyResult<PyObject> {
+    let py = cls.py();
+    let types = py.import(intern!(py, "types"))?;
+    if !types.hasattr(intern!(py, "GenericAlias"))? {
+        return Ok(cls.into());
+    }
+    types
+        .getattr(intern!(py, "GenericAlias"))?
+        .call1((cls, item))
+        .map(Into::into)
+}
+
+/// The type of a `#[pyclass(generic)]` under `experimental-inspect`, a class taking a single type
+/// variable which isn't known, so that it is hinted as `Cls[Any]`.
+#[cfg(feature = "experimental-inspect")]
+pub fn type_info<T: PyTypeInfo>() -> TypeInfo {
+    TypeInfo::Class {
+        module: T::MODULE.map_or(ModuleName::CurrentModule, |module| {
+            ModuleName::Module(Cow::from(module))
+        }),
+        name: Cow::from(T::NAME),
+        type_vars: vec![TypeInfo::Any],
+    }
+}
+
+/// Implements `IntoPy::type_output` for a `#[pyclass(generic)]` with [`type_info`].
+#[cfg(feature = "experimental-inspect")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! pyclass_generic_type_output {
+    ($ty:ty) => {
+        fn type_output() -> $crate::inspect::types::TypeInfo {
+            $crate::impl_::generic::type_info::<$ty>()
+        }
+    };
+}
+
+/// Without `experimental-inspect`, `IntoPy` has no `type_output` to implement.
+#[cfg(not(feature = "experimental-inspect"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! pyclass_generic_type_output {
+    ($ty:ty) => {};
+}
@@ -0,0 +1,49 @@
+use crate::{IntoPy, PyObject, Python};
+
+/// The operands of a reflected operator method generated by `#[pyclass(ops(...))]`, such as
+/// `__radd__`, with the left-hand operand first.
+///
+/// `Operands(lhs, rhs).add(py)` returns `lhs + rhs` when the left-hand operand implements `Add`
+/// for the class, and `NotImplemented` otherwise, so that Python raises its usual `TypeError`.
+pub struct Operands<L, R>(pub L, pub R);
+
+macro_rules! reflected_op {
+    ($trait_:ident, $method:ident, $reflected:ident, $fallback:ident) => {
+        pub trait $reflected {
+            fn $method(self, py: Python<'_>) -> PyObject;
+        }
+
+        impl<L, R> $reflected for Operands<L, R>
+        where
+            L: std::ops::$trait_<R>,
+            L::Output: IntoPy<PyObject>,
+        {
+            #[inline]
+            fn $method(self, py: Python<'_>) -> PyObject {
+                std::ops::$trait_::$method(self.0, self.1).into_py(py)
+            }
+        }
+
+        pub trait $fallback {
+            fn $method(self, py: Python<'_>) -> PyObject;
+        }
+
+        impl<L, R> $fallback for &'_ Operands<L, R> {
+            #[inline]
+            fn $method(self, py: Python<'_>) -> PyObject {
+                py.NotImplemented()
+            }
+        }
+    };
+}
+
+reflected_op!(Add, add, ReflectedAdd, NoReflectedAdd);
+reflected_op!(Sub, sub, ReflectedSub, NoReflectedSub);
+reflected_op!(Mul, mul, ReflectedMul, NoReflectedMul);
+reflected_op!(Div, div, ReflectedDiv, NoReflectedDiv);
+reflected_op!(Rem, rem, ReflectedRem, NoReflectedRem);
+reflected_op!(BitAnd, bitand, ReflectedBitAnd, NoReflectedBitAnd);
+reflected_op!(BitOr, bitor, ReflectedBitOr, NoReflectedBitOr);
+reflected_op!(BitXor, bitxor, ReflectedBitXor, NoReflectedBitXor);
+reflected_op!(Shl, shl, ReflectedShl, NoReflectedShl);
+reflected_op!(Shr, shr, ReflectedShr, NoReflectedShr);
@@ -0,0 +1,109 @@
+use crate::exceptions::{PyTypeError, PyValueError};
+use crate::pyclass_init::PyClassInitializer;
+use crate::types::{PyDict, PyType};
+use crate::{ffi, intern, FromPyObject, IntoPy, PyAny, PyClass, PyErr, PyObject, PyResult};
+
+/// Implements `__reduce__` for `#[pyclass(pickle)]`, returning the class's `_pyo3_unpickle`
+/// classmethod with the state from `__getstate__` as its argument.
+pub fn reduce(slf: &PyAny) -> PyResult<PyObject> {
+    let py = slf.py();
+    let state = slf.call_method0(intern!(py, "__getstate__"))?;
+    let unpickle = slf.get_type().getattr(intern!(py, "_pyo3_unpickle"))?;
+    Ok((unpickle, (state,)).into_py(py))
+}
+
+/// Creates an object of `cls`, which may be a Python subclass of `T`, holding `value`.
+pub fn unpickle<T: PyClass>(
+    cls: &PyType,
+    value: impl Into<PyClassInitializer<T>>,
+) -> PyResult<PyObject> {
+    let py = cls.py();
+    if !cls.is_subclass_of::<T>()? {
+        return Err(PyTypeError::new_err(format!(
+            "cannot unpickle a '{}' object as '{}'",
+            T::NAME,
+            cls.name()?
+        )));
+    }
+    unsafe {
+        let cell = value
+            .into()
+            .create_cell_from_subtype(py, cls.as_type_ptr())?;
+        Ok(PyObject::from_owned_ptr(py, cell as *mut ffi::PyObject))
+    }
+}
+
+/// Extracts a field of a `#[pyclass(pickle)]` struct from the dict returned by `__getstate__`.
+pub fn extract_state_item<'py, T>(
+    state: &'py PyDict,
+    class_name: &str,
+    field_name: &str,
+) -> PyResult<T>
+where
+    T: FromPyObject<'py>,
+{
+    match state.get_item(field_name) {
+        Some(item) => super::frompyobject::extract_struct_field(item, class_name, field_name),
+        None => Err(PyValueError::new_err(format!(
+            "cannot unpickle '{}': the state is missing field '{}'",
+            class_name, field_name
+        ))),
+    }
+}
+
+/// The error for unpickling a `#[pyclass(pickle)]` enum from a name which is not a variant.
+pub fn unknown_variant(class_name: &str, name: &str) -> PyErr {
+    PyValueError::new_err(format!(
+        "cannot unpickle '{}': '{}' is not a variant",
+        class_name, name
+    ))
+}
+
+/// The conversions of a `#[pyclass(pickle(serde))]` value to and from its state.
+pub struct SerdeState<T> {
+    pub serialize: fn(crate::Python<'_>, &T) -> PyResult<PyObject>,
+    pub deserialize: fn(&PyAny) -> PyResult<T>,
+}
+
+/// Expands to the [`SerdeState`] of a `#[pyclass(pickle(serde))]` class.
+#[cfg(all(feature = "serde", feature = "serde_json"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! pickle_serde_state {
+    () => {
+        $crate::impl_::pickle::SerdeState {
+            serialize: $crate::impl_::pickle::serialize_state,
+            deserialize: $crate::impl_::pickle::deserialize_state,
+        }
+    };
+}
+
+/// Without the `serde` and `serde_json` features, `#[pyclass(pickle(serde))]` is a compile error
+/// in the crate which uses it, rather than an unresolved path in the generated code.
+#[cfg(not(all(feature = "serde", feature = "serde_json")))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! pickle_serde_state {
+    () => {
+        ::std::compile_error!(
+            "`#[pyclass(pickle(serde))]` requires the `serde` and `serde_json` features of pyo3"
+        )
+    };
+}
+
+/// Implements `__getstate__` for `#[pyclass(pickle(serde))]`, serializing the object to JSON.
+#[cfg(all(feature = "serde", feature = "serde_json"))]
+pub fn serialize_state<T: serde::Serialize>(
+    py: crate::Python<'_>,
+    value: &T,
+) -> PyResult<PyObject> {
+    let bytes = serde_json::to_vec(value).map_err(|err| PyValueError::new_err(err.to_string()))?;
+    Ok(crate::types::PyBytes::new(py, &bytes).into())
+}
+
+/// Converts the state returned by `__getstate__` back to a `#[pyclass(pickle(serde))]` value.
+#[cfg(all(feature = "serde", feature = "serde_json"))]
+pub fn deserialize_state<T: serde::de::DeserializeOwned>(state: &PyAny) -> PyResult<T> {
+    let bytes: &crate::types::PyBytes = state.downcast()?;
+    serde_json::from_slice(bytes.as_bytes()).map_err(|err| PyValueError::new_err(err.to_string()))
+}
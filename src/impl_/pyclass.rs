@@ -199,9 +199,36 @@ pub trait PyClassImpl: Sized + 'static {
         None
     }
 
+    /// #[pyclass(metaclass=...)]
+    #[inline]
+    fn metaclass(_py: Python<'_>) -> Option<*mut ffi::PyTypeObject> {
+        None
+    }
+
     fn lazy_type_object() -> &'static LazyTypeObject<Self>;
 }
 
+/// Expands to the type object of the metaclass given with `#[pyclass(metaclass = ...)]`.
+#[cfg(Py_3_12)]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! pyclass_metaclass {
+    ($py:expr, $metaclass:ty) => {
+        ::std::option::Option::Some(<$metaclass as $crate::PyTypeInfo>::type_object_raw($py))
+    };
+}
+
+/// Before Python 3.12, types created from a spec are always instances of `type`, so
+/// `#[pyclass(metaclass = ...)]` is a compile error in the crate which uses it.
+#[cfg(not(Py_3_12))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! pyclass_metaclass {
+    ($py:expr, $metaclass:ty) => {
+        ::std::compile_error!("`#[pyclass(metaclass = ...)]` requires Python 3.12 or later")
+    };
+}
+
 /// Runtime helper to build a class docstring from the `doc` and `text_signature`.
 ///
 /// This is done at runtime because the class text signature is collected via dtolnay
@@ -237,6 +264,8 @@ pub struct PyClassItemsIter {
     /// Items from the `#[pymethods]` macro with inventory
     #[cfg(feature = "multiple-pymethods")]
     pymethods_items: Box<dyn Iterator<Item = &'static PyClassItems>>,
+    /// Items from the `#[pymethods(group = ...)]` blocks listed in `#[pyclass(pymethods_groups(...))]`
+    pymethods_groups: &'static [fn() -> &'static PyClassItems],
 }
 
 impl PyClassItemsIter {
@@ -251,8 +280,23 @@ impl PyClassItemsIter {
             idx: 0,
             pyclass_items,
             pymethods_items,
+            pymethods_groups: &[],
         }
     }
+
+    pub fn with_pymethods_groups(
+        mut self,
+        pymethods_groups: &'static [fn() -> &'static PyClassItems],
+    ) -> Self {
+        self.pymethods_groups = pymethods_groups;
+        self
+    }
+
+    fn next_group(&mut self) -> Option<&'static PyClassItems> {
+        let (group, rest) = self.pymethods_groups.split_first()?;
+        self.pymethods_groups = rest;
+        Some(group())
+    }
 }
 
 impl Iterator for PyClassItemsIter {
@@ -269,8 +313,8 @@ impl Iterator for PyClassItemsIter {
                 self.idx += 1;
                 Some(self.pymethods_items)
             }
-            // Termination clause
-            _ => None,
+            // Termination clause, after the `#[pymethods]` groups
+            _ => self.next_group(),
         }
     }
 
@@ -281,8 +325,8 @@ impl Iterator for PyClassItemsIter {
                 self.idx += 1;
                 Some(self.pyclass_items)
             }
-            // Termination clause
-            _ => self.pymethods_items.next(),
+            // Termination clause, after the `#[pymethods]` groups
+            _ => self.pymethods_items.next().or_else(|| self.next_group()),
         }
     }
 }
@@ -1003,6 +1047,10 @@ impl<T> PyClassNewTextSignature<T> for &'_ PyClassImplCollector<T> {
     }
 }
 
+// Marks a class whose `__class_getitem__` is defined, either by the `generic` option or in
+// `#[pymethods]`, so that defining it twice is a conflicting implementation.
+pub trait PyClassGetitem<T> {}
+
 // Thread checkers
 
 #[doc(hidden)]
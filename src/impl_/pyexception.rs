@@ -0,0 +1,74 @@
+use crate::{
+    exceptions::PyBaseException, sync::GILOnceCell, types::PyType, Py, PyAny, PyErr,
+    PyErrArguments, PyObject, PyResult, Python,
+};
+
+/// The exception types created for a `#[derive(PyException)]` enum.
+///
+/// The first type is the base exception, followed by one subclass per variant.
+pub struct ExceptionTypes(GILOnceCell<Vec<Py<PyType>>>);
+
+impl ExceptionTypes {
+    pub const fn new() -> Self {
+        Self(GILOnceCell::new())
+    }
+
+    /// Returns the exception types, creating them on first use.
+    ///
+    /// Docs are nul-terminated, as produced by the macros' doc handling; empty docs are omitted.
+    pub fn get_or_init<'py>(
+        &'py self,
+        py: Python<'py>,
+        base: &PyType,
+        module: &str,
+        name: &str,
+        doc: &str,
+        variants: &[(&str, &str)],
+    ) -> &'py [Py<PyType>] {
+        self.0.get_or_init(py, || {
+            let new_type = |name: &str, doc: &str, base: &PyType| {
+                let doc = doc.trim_end_matches('\0');
+                PyErr::new_type(
+                    py,
+                    &format!("{}.{}", module, name),
+                    if doc.is_empty() { None } else { Some(doc) },
+                    Some(base),
+                    None,
+                )
+                .expect("Failed to initialize new exception type.")
+            };
+
+            let base = new_type(name, doc, base);
+            let mut types = Vec::with_capacity(variants.len() + 1);
+            for (name, doc) in variants {
+                types.push(new_type(name, doc, base.as_ref(py)));
+            }
+            types.insert(0, base);
+            types
+        })
+    }
+}
+
+/// Creates a `PyErr` whose exception instance is built by `f` once the GIL is held.
+///
+/// If `f` fails, the error it returns is used instead.
+pub fn new_err<F>(f: F) -> PyErr
+where
+    F: for<'py> FnOnce(Python<'py>) -> PyResult<&'py PyAny> + Send + Sync + 'static,
+{
+    PyErr::new::<PyBaseException, _>(ExceptionInstance(f))
+}
+
+struct ExceptionInstance<F>(F);
+
+impl<F> PyErrArguments for ExceptionInstance<F>
+where
+    F: for<'py> FnOnce(Python<'py>) -> PyResult<&'py PyAny> + Send + Sync,
+{
+    fn arguments(self, py: Python<'_>) -> PyObject {
+        match (self.0)(py) {
+            Ok(exception) => exception.into(),
+            Err(err) => err.into_value(py).into(),
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use crate::gil::LockGIL;
 use crate::impl_::panic::PanicTrap;
 use crate::internal_tricks::extract_c_string;
+use crate::types::PyString;
 use crate::{
     ffi, IntoPy, Py, PyAny, PyCell, PyClass, PyErr, PyObject, PyResult, PyTraverseError, PyVisit,
     Python,
@@ -287,6 +288,18 @@ where
     retval
 }
 
+/// Returns whether the type of `slf` overrides the method `name` of `T`, used by the dispatchers
+/// generated for `#[pyo3(overridable)]` methods.
+pub fn is_overridden<T: PyClass>(slf: &PyCell<T>, name: &PyString) -> PyResult<bool> {
+    let py = slf.py();
+    let ty = slf.get_type();
+    let base = T::type_object(py);
+    if ty.is(base) {
+        return Ok(false);
+    }
+    Ok(!ty.getattr(name)?.is(base.getattr(name)?))
+}
+
 pub(crate) struct PyMethodDefDestructor {
     // These members are just to avoid leaking CStrings when possible
     #[allow(dead_code)]
@@ -0,0 +1,48 @@
+use crate::{exceptions::PyTypeError, types::PyString, IntoPy, Py, PyAny, PyResult};
+
+/// Implemented by `#[pytrait]` for `dyn Trait`, describing the Python methods which
+/// `PyImpl<dyn Trait>` calls.
+pub trait PyTrait {
+    /// The name of the trait, used in error messages.
+    const NAME: &'static str;
+    /// The Python names of the trait methods which have no default implementation.
+    const REQUIRED_METHODS: &'static [&'static str];
+}
+
+/// Checks that `obj` has all the methods required by the trait `T`.
+pub fn check_methods<T: ?Sized + PyTrait>(obj: &PyAny) -> PyResult<()> {
+    for &method in T::REQUIRED_METHODS {
+        if !has_method(obj, method) {
+            return Err(PyTypeError::new_err(format!(
+                "'{}' object cannot be converted to '{}': it has no method '{}'",
+                obj.get_type().name()?,
+                T::NAME,
+                method
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Returns whether `obj` has a callable attribute `name`.
+pub fn has_method<N: IntoPy<Py<PyString>>>(obj: &PyAny, name: N) -> bool {
+    obj.getattr(name)
+        .map(|method| method.is_callable())
+        .unwrap_or(false)
+}
+
+/// Unwraps the result of calling a Python method for a trait method which cannot return errors.
+pub fn unwrap_result<T>(result: PyResult<T>, trait_name: &str, method: &str) -> T {
+    match result {
+        Ok(value) => value,
+        Err(err) => failed(err, trait_name, method),
+    }
+}
+
+#[cold]
+fn failed(err: crate::PyErr, trait_name: &str, method: &str) -> ! {
+    panic!(
+        "Python implementation of `{}::{}` failed: {}",
+        trait_name, method, err
+    )
+}
@@ -103,6 +103,8 @@
 //! [`Decimal`] type.
 //! - [`serde`]: Allows implementing [serde]'s [`Serialize`] and [`Deserialize`] traits for
 //! [`Py`]`<T>` for all `T` that implement [`Serialize`] and [`Deserialize`].
+//! - `serde_json`: With the `serde` feature, enables `#[pyclass(pickle(serde))]`, which pickles a
+//! class as the JSON of its [`Serialize`] implementation.
 //!
 //! ## Unstable features
 //!
@@ -295,11 +297,14 @@
 //! [Rust from Python]: https://github.com/PyO3/pyo3#using-rust-from-python
 //! [Features chapter of the guide]: https://pyo3.rs/latest/features.html#features-reference "Features Reference - PyO3 user guide"
 //! [`Ungil`]: crate::marker::Ungil
+pub use crate::callable::{PyCallable, PyCallableArgs};
 pub use crate::class::*;
 pub use crate::conversion::{
     AsPyPointer, FromPyObject, FromPyPointer, IntoPy, PyTryFrom, PyTryInto, ToPyObject,
 };
-pub use crate::err::{PyDowncastError, PyErr, PyErrArguments, PyResult};
+pub use crate::err::{
+    PyDowncastError, PyErr, PyErrArguments, PyErrSnapshot, PyResult, PyResultExt,
+};
 pub use crate::gil::GILPool;
 #[cfg(not(PyPy))]
 pub use crate::gil::{prepare_freethreaded_python, with_embedded_python_interpreter};
@@ -308,6 +313,7 @@ pub use crate::marker::Python;
 pub use crate::pycell::{PyCell, PyRef, PyRefMut};
 pub use crate::pyclass::PyClass;
 pub use crate::pyclass_init::PyClassInitializer;
+pub use crate::pytrait::PyImpl;
 pub use crate::type_object::PyTypeInfo;
 pub use crate::types::PyAny;
 pub use crate::version::PythonVersionInfo;
@@ -390,6 +396,7 @@ pub use inventory; // Re-exported for `#[pyclass]` and `#[pymethods]` with `mult
 mod internal_tricks;
 
 pub mod buffer;
+mod callable;
 #[doc(hidden)]
 pub mod callback;
 pub mod conversion;
@@ -413,6 +420,8 @@ pub mod prelude;
 pub mod pycell;
 pub mod pyclass;
 pub mod pyclass_init;
+mod pytrait;
+pub mod signature;
 
 pub mod type_object;
 pub mod types;
@@ -430,7 +439,7 @@ pub mod once_cell {
 pub use crate::conversions::*;
 
 #[cfg(feature = "macros")]
-pub use pyo3_macros::{pyfunction, pymethods, pymodule, FromPyObject};
+pub use pyo3_macros::{pyfunction, pymethods, pymodule, pytrait, FromPyObject, PyException};
 
 /// A proc macro used to expose Rust structs and fieldless enums as Python objects.
 ///
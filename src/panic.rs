@@ -1,7 +1,11 @@
 //! Helper to convert Rust panics to Python exceptions.
 use crate::exceptions::PyBaseException;
-use crate::PyErr;
+use crate::sync::GILOnceCell;
+use crate::types::PyDict;
+use crate::{PyAny, PyErr, PyObject, PyResult, Python};
 use std::any::Any;
+use std::cell::RefCell;
+use std::panic::Location;
 
 pyo3_exception!(
     "
@@ -10,6 +14,10 @@ The exception raised when Rust code called from Python panics.
 Like SystemExit, this exception is derived from BaseException so that
 it will typically propagate all the way through the stack and cause the
 Python interpreter to exit.
+
+If [`install_panic_hook`] has been called, the exception has `location` and
+`backtrace` attributes describing where the panic happened, and its traceback
+contains entries for the Rust frames which panicked.
 ",
     PanicException,
     PyBaseException
@@ -21,12 +29,272 @@ impl PanicException {
     /// Attempts to format the error in the same way panic does.
     #[cold]
     pub(crate) fn from_panic_payload(payload: Box<dyn Any + Send + 'static>) -> PyErr {
-        if let Some(string) = payload.downcast_ref::<String>() {
-            Self::new_err((string.clone(),))
+        let message = if let Some(string) = payload.downcast_ref::<String>() {
+            string.clone()
         } else if let Some(s) = payload.downcast_ref::<&str>() {
-            Self::new_err((s.to_string(),))
+            s.to_string()
         } else {
-            Self::new_err(("panic from Rust code",))
+            "panic from Rust code".to_string()
+        };
+
+        match PanicDetails::take(&*payload) {
+            Some(details) => Python::with_gil(|py| {
+                details
+                    .into_exception(py, &message)
+                    .unwrap_or_else(|_| Self::new_err((message,)))
+            }),
+            None => Self::new_err((message,)),
+        }
+    }
+}
+
+/// Installs a panic hook which records where panics happen, so that a [`PanicException`]
+/// created from the panic can report it.
+///
+/// With the hook installed, a `PanicException` has two extra attributes:
+///
+/// - `location`: the `file:line:column` of the panic, as in Rust's panic message.
+/// - `backtrace`: the formatted Rust backtrace of the panic, or `None` if it is not available.
+///
+/// In addition, the exception's traceback contains an entry for each Rust frame between the
+/// panic and the point where PyO3 caught it, so that Python tracebacks and crash reporters show
+/// the Rust code which failed. The frames can only be named if the binary contains debug info.
+///
+/// Backtraces are only captured when enabled with the `RUST_BACKTRACE` or `RUST_LIB_BACKTRACE`
+/// environment variables, and require Rust 1.65 or greater. The previously installed panic hook is
+/// still called, so panic messages keep being printed as before. Calling this function more than
+/// once has no further effect.
+///
+/// # Example
+///
+/// ```rust
+/// use pyo3::prelude::*;
+///
+/// #[pymodule]
+/// fn my_module(_py: Python<'_>, _m: &PyModule) -> PyResult<()> {
+///     pyo3::panic::install_panic_hook();
+///     Ok(())
+/// }
+/// ```
+pub fn install_panic_hook() {
+    static INSTALL: std::sync::Once = std::sync::Once::new();
+    INSTALL.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            PanicDetails::record(info.payload(), info.location());
+            previous(info);
+        }));
+    });
+}
+
+thread_local! {
+    static LAST_PANIC: RefCell<Option<PanicDetails>> = RefCell::new(None);
+}
+
+/// The details of a panic, recorded by the hook installed with [`install_panic_hook`].
+struct PanicDetails {
+    /// The address of the panic message, which identifies the payload caught by PyO3.
+    message: Option<*const u8>,
+    location: Option<String>,
+    backtrace: Option<String>,
+    /// The Rust frames which panicked, innermost first, as `(function, file, line)`.
+    frames: Vec<(String, String, u32)>,
+}
+
+impl PanicDetails {
+    fn record(payload: &(dyn Any + Send), location: Option<&Location<'_>>) {
+        #[cfg(std_backtrace)]
+        #[allow(clippy::incompatible_msrv)] // only compiled with Rust 1.65 or greater
+        let backtrace = {
+            use std::backtrace::{Backtrace, BacktraceStatus};
+            let backtrace = Backtrace::capture();
+            if backtrace.status() == BacktraceStatus::Captured {
+                Some(backtrace.to_string())
+            } else {
+                None
+            }
+        };
+        #[cfg(not(std_backtrace))]
+        let backtrace: Option<String> = None;
+
+        let mut frames = backtrace
+            .as_deref()
+            .map(panicking_frames)
+            .unwrap_or_default();
+        if frames.is_empty() {
+            if let Some(location) = location {
+                frames.push((
+                    "<rust>".to_string(),
+                    location.file().to_string(),
+                    location.line(),
+                ));
+            }
+        }
+
+        let details = PanicDetails {
+            message: message_address(payload),
+            location: location.map(ToString::to_string),
+            backtrace,
+            frames,
+        };
+        // Panics while the thread-local is being destroyed cannot be recorded
+        let _ = LAST_PANIC.try_with(|last| *last.borrow_mut() = Some(details));
+    }
+
+    /// Takes the details of the last panic on this thread, if they belong to `payload`.
+    ///
+    /// The details are cleared in any case: they belong to another panic when the messages don't
+    /// match, such as a panic caught by user code before this one was resumed with
+    /// `resume_unwind`, which doesn't call the hook.
+    fn take(payload: &(dyn Any + Send)) -> Option<Self> {
+        let message = message_address(payload);
+        LAST_PANIC
+            .try_with(|last| last.borrow_mut().take())
+            .ok()
+            .flatten()
+            .filter(|details| message.is_some() && details.message == message)
+    }
+
+    fn into_exception(self, py: Python<'_>, message: &str) -> PyResult<PyErr> {
+        let exception = py.get_type::<PanicException>().call1((message,))?;
+        exception.setattr(crate::intern!(py, "location"), self.location)?;
+        exception.setattr(crate::intern!(py, "backtrace"), self.backtrace)?;
+        add_rust_frames(py)?.call1((exception, self.frames))?;
+        Ok(PyErr::from_value(exception))
+    }
+}
+
+/// Returns the address of the message of a panic payload.
+///
+/// The payload seen by the panic hook is moved into the box caught by `catch_unwind` without
+/// copying the message, so the address identifies the panic.
+fn message_address(payload: &(dyn Any + Send)) -> Option<*const u8> {
+    if let Some(string) = payload.downcast_ref::<String>() {
+        Some(string.as_ptr())
+    } else {
+        payload.downcast_ref::<&str>().map(|s| s.as_ptr())
+    }
+}
+
+/// Extracts the frames between the panic machinery and the `catch_unwind` which caught the
+/// panic from a formatted backtrace.
+fn panicking_frames(backtrace: &str) -> Vec<(String, String, u32)> {
+    let mut frames: Vec<(String, Option<(String, u32)>)> = Vec::new();
+    for line in backtrace.lines() {
+        let line = line.trim();
+        if let Some(location) = line.strip_prefix("at ") {
+            // `at path/to/file.rs:line:column`
+            let mut parts = location.rsplitn(3, ':');
+            let (_column, line, file) = (parts.next(), parts.next(), parts.next());
+            if let (Some(frame), Some(line), Some(file)) = (frames.last_mut(), line, file) {
+                if frame.1.is_none() {
+                    frame.1 = line.parse().ok().map(|line| (file.to_string(), line));
+                }
+            }
+        } else if let Some((index, name)) = line.split_once(": ") {
+            // `index: function`
+            if index.parse::<usize>().is_ok() {
+                frames.push((name.to_string(), None));
+            }
         }
     }
+
+    let end = frames
+        .iter()
+        .position(|(name, _)| {
+            name.starts_with("std::panicking::try")
+                || name.starts_with("std::panicking::catch_unwind")
+                || name.starts_with("std::panic::catch_unwind")
+                || name == "__rust_try"
+        })
+        .unwrap_or(frames.len());
+    let start = frames[..end]
+        .iter()
+        .rposition(|(name, _)| {
+            name.contains("panicking::")
+                || name.contains("__rust_end_short_backtrace")
+                || name == "rust_begin_unwind"
+        })
+        .map_or(0, |index| index + 1);
+
+    frames.truncate(end);
+    frames
+        .drain(start..)
+        .filter(|(name, _)| !name.starts_with("pyo3::impl_::trampoline"))
+        .filter_map(|(name, location)| location.map(|(file, line)| (name, file, line)))
+        .collect()
+}
+
+/// Returns a Python function which prepends synthesized traceback entries to an exception.
+///
+/// Each entry is created by raising an exception from code compiled with the Rust frame's
+/// file name and line, renamed after the Rust function where supported.
+fn add_rust_frames(py: Python<'_>) -> PyResult<&PyAny> {
+    static ADD_RUST_FRAMES: GILOnceCell<PyObject> = GILOnceCell::new();
+    ADD_RUST_FRAMES
+        .get_or_try_init(py, || {
+            let globals = PyDict::new(py);
+            py.run(
+                r#"
+def add_rust_frames(exc, frames):
+    tb = exc.__traceback__
+    for name, filename, lineno in frames:
+        code = compile("\n" * (max(lineno, 1) - 1) + "raise Exception", filename, "exec")
+        if hasattr(code, "replace"):
+            code = code.replace(co_name=name)
+        try:
+            exec(code, {})
+        except Exception as e:
+            frame_tb = e.__traceback__.tb_next
+        frame_tb.tb_next = tb
+        tb = frame_tb
+    exc.__traceback__ = tb
+"#,
+                Some(globals),
+                None,
+            )?;
+            Ok::<_, PyErr>(globals.get_item("add_rust_frames").unwrap().into())
+        })
+        .map(|add_rust_frames| add_rust_frames.as_ref(py))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::panicking_frames;
+
+    #[test]
+    fn test_panicking_frames() {
+        let backtrace = "   0: pyo3::panic::install_panic_hook::{{closure}}
+             at ./src/panic.rs:80:13
+   1: std::panicking::rust_panic_with_hook
+             at /rustc/abc/library/std/src/panicking.rs:735:13
+   2: std::panicking::begin_panic::<&str>
+             at /rustc/abc/library/std/src/panicking.rs:751:5
+   3: my_crate::inner
+             at ./src/lib.rs:10:5
+   4: my_crate::outer
+             at ./src/lib.rs:20:9
+   5: pyo3::impl_::trampoline::trampoline::{{closure}}
+             at ./src/impl_/trampoline.rs:180:54
+   6: std::panicking::try::do_call
+             at /rustc/abc/library/std/src/panicking.rs:552:40
+   7: __rust_try
+   8: main
+";
+        assert_eq!(
+            panicking_frames(backtrace),
+            [
+                (
+                    "my_crate::inner".to_string(),
+                    "./src/lib.rs".to_string(),
+                    10
+                ),
+                (
+                    "my_crate::outer".to_string(),
+                    "./src/lib.rs".to_string(),
+                    20
+                ),
+            ]
+        );
+    }
 }
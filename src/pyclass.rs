@@ -5,11 +5,19 @@ use crate::{
 };
 use std::{cmp::Ordering, os::raw::c_int};
 
+mod builder;
+mod context_manager;
+mod copy;
 mod create_type_object;
 mod gc;
 
+pub use self::builder::PyClassBuilder;
+pub use self::context_manager::{
+    BindSelf, IntoEnterTarget, PyAsyncContextManager, PyContextManager,
+};
+pub use self::copy::PyDeepCopy;
 pub(crate) use self::create_type_object::{create_type_object, PyClassTypeObject};
-pub use self::gc::{PyTraverseError, PyVisit};
+pub use self::gc::{PyTraverse, PyTraverseError, PyVisit};
 
 /// Types that can be used as Python classes.
 ///
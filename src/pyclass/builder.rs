@@ -0,0 +1,712 @@
+use crate::{
+    exceptions::{PyRuntimeError, PyTypeError},
+    ffi,
+    impl_::{
+        pymethods::{PyGetter, PyGetterDef, PyMethodDefType},
+        trampoline,
+    },
+    pyclass::create_type_object::{PyClassTypeObject, PyTypeBuilder},
+    pyclass_init::new_native_object,
+    sync::GILOnceCell,
+    type_object::get_tp_free,
+    types::{PyDict, PyString, PyTuple, PyType},
+    IntoPy, Py, PyAny, PyObject, PyResult, Python, ToPyObject,
+};
+use std::{
+    any::Any,
+    cell::{Ref, RefCell, RefMut},
+    collections::HashMap,
+    os::raw::c_void,
+    ptr,
+    sync::{Arc, Mutex},
+};
+
+type Constructor =
+    Box<dyn Fn(&PyTuple, Option<&PyDict>) -> PyResult<Box<dyn Any + Send>> + Send + Sync>;
+type Function = Box<
+    dyn Fn(Option<&PyAny>, &PyTuple, Option<&PyDict>) -> PyResult<*mut ffi::PyObject> + Send + Sync,
+>;
+type InstanceData = RefCell<Option<Box<dyn Any + Send>>>;
+
+/// Builds a Python class at runtime, without `#[pyclass]`.
+///
+/// This is useful when the classes to expose are only known at runtime, for example when they
+/// are generated from a schema. The class is assembled from Rust closures:
+///
+/// - [`constructor`](Self::constructor) creates the Rust value stored in each instance, which can
+///   be any `Any + Send` type. Closures can access it with [`PyClassBuilder::borrow_data`] and
+///   [`PyClassBuilder::borrow_data_mut`].
+/// - [`method`](Self::method), [`classmethod`](Self::classmethod) and
+///   [`staticmethod`](Self::staticmethod) add methods. Methods with names of special methods,
+///   such as `__repr__`, `__len__` or `__getitem__`, fill the corresponding type slots.
+/// - [`getter`](Self::getter) and [`setter`](Self::setter) add properties.
+///
+/// Classes created by the builder can be subclassed from Python. They are never deallocated.
+///
+/// # Examples
+///
+/// ```rust
+/// use pyo3::prelude::*;
+/// use pyo3::pyclass::PyClassBuilder;
+///
+/// struct Counter {
+///     count: u64,
+/// }
+///
+/// Python::with_gil(|py| -> PyResult<()> {
+///     let counter = PyClassBuilder::new("Counter")
+///         .module("counters")
+///         .doc("Counts things.")
+///         .constructor(|args, _kwargs| {
+///             let (count,): (u64,) = args.extract()?;
+///             Ok(Counter { count })
+///         })
+///         .method("increment", None, |slf, _args, _kwargs| {
+///             let mut counter = PyClassBuilder::borrow_data_mut::<Counter>(slf)?;
+///             counter.count += 1;
+///             Ok(counter.count)
+///         })
+///         .getter("count", None, |slf| {
+///             Ok(PyClassBuilder::borrow_data::<Counter>(slf)?.count)
+///         })
+///         .build(py)?;
+///
+///     pyo3::py_run!(py, counter, r#"
+///         c = counter(41)
+///         assert c.increment() == 42
+///         assert c.count == 42
+///         assert counter.__module__ == "counters"
+///     "#);
+///     Ok(())
+/// })
+/// # .unwrap();
+/// ```
+pub struct PyClassBuilder {
+    name: String,
+    module: Option<String>,
+    base: Option<Py<PyType>>,
+    doc: Option<String>,
+    constructor: Option<Constructor>,
+    methods: Vec<MethodData>,
+    properties: Vec<PropertyData>,
+}
+
+impl PyClassBuilder {
+    /// Starts building a class with the given name.
+    pub fn new(name: &str) -> Self {
+        PyClassBuilder {
+            name: name.to_owned(),
+            module: None,
+            base: None,
+            doc: None,
+            constructor: None,
+            methods: Vec::new(),
+            properties: Vec::new(),
+        }
+    }
+
+    /// Sets the module of the class, which defaults to `builtins`.
+    pub fn module(mut self, module: &str) -> Self {
+        self.module = Some(module.to_owned());
+        self
+    }
+
+    /// Sets the base class, which defaults to `object`.
+    ///
+    /// The base class must be a native type, such as `object` or `Exception`; it cannot be a
+    /// class created by `PyClassBuilder`. With the `abi3` feature, the base class must be `object`.
+    pub fn base(mut self, base: &PyType) -> Self {
+        self.base = Some(base.into());
+        self
+    }
+
+    /// Sets the docstring of the class.
+    pub fn doc(mut self, doc: &str) -> Self {
+        self.doc = Some(doc.to_owned());
+        self
+    }
+
+    /// Sets the constructor of the class, which is called with the arguments of `__new__` and
+    /// returns the Rust value to store in the new instance.
+    ///
+    /// Without a constructor, the class cannot be instantiated.
+    pub fn constructor<F, T>(mut self, constructor: F) -> Self
+    where
+        F: Fn(&PyTuple, Option<&PyDict>) -> PyResult<T> + Send + Sync + 'static,
+        T: Any + Send,
+    {
+        self.constructor = Some(Box::new(move |args, kwargs| {
+            constructor(args, kwargs).map(|data| Box::new(data) as Box<dyn Any + Send>)
+        }));
+        self
+    }
+
+    /// Adds an instance method.
+    ///
+    /// The closure is called with the instance, the positional arguments and the keyword
+    /// arguments.
+    pub fn method<F, R>(self, name: &str, doc: Option<&str>, method: F) -> Self
+    where
+        F: Fn(&PyAny, &PyTuple, Option<&PyDict>) -> PyResult<R> + Send + Sync + 'static,
+        R: IntoPy<PyObject>,
+    {
+        self.add_method(
+            name,
+            doc,
+            MethodKind::Instance,
+            move |slf: Option<&PyAny>, args, kwargs| method(slf.unwrap(), args, kwargs),
+        )
+    }
+
+    /// Adds a class method, which is called with the class as its first argument.
+    pub fn classmethod<F, R>(self, name: &str, doc: Option<&str>, method: F) -> Self
+    where
+        F: Fn(&PyType, &PyTuple, Option<&PyDict>) -> PyResult<R> + Send + Sync + 'static,
+        R: IntoPy<PyObject>,
+    {
+        self.add_method(
+            name,
+            doc,
+            MethodKind::Class,
+            move |cls: Option<&PyAny>, args, kwargs| method(cls.unwrap().downcast()?, args, kwargs),
+        )
+    }
+
+    /// Adds a static method, which is called without the instance or class.
+    pub fn staticmethod<F, R>(self, name: &str, doc: Option<&str>, method: F) -> Self
+    where
+        F: Fn(&PyTuple, Option<&PyDict>) -> PyResult<R> + Send + Sync + 'static,
+        R: IntoPy<PyObject>,
+    {
+        self.add_method(
+            name,
+            doc,
+            MethodKind::Static,
+            move |_: Option<&PyAny>, args, kwargs| method(args, kwargs),
+        )
+    }
+
+    /// Adds the getter of a property.
+    pub fn getter<F, R>(mut self, name: &str, doc: Option<&str>, getter: F) -> Self
+    where
+        F: Fn(&PyAny) -> PyResult<R> + Send + Sync + 'static,
+        R: IntoPy<PyObject>,
+    {
+        let function: Function = Box::new(move |slf, _args, _kwargs| {
+            let slf = slf.unwrap();
+            getter(slf).map(|value| value.into_py(slf.py()).into_ptr())
+        });
+        let property = self.property(name);
+        if property.doc.is_none() {
+            property.doc = doc.map(ToOwned::to_owned);
+        }
+        property.getter = Some(function);
+        self
+    }
+
+    /// Adds the setter of a property, which is called with the instance and the new value.
+    pub fn setter<F>(mut self, name: &str, setter: F) -> Self
+    where
+        F: Fn(&PyAny, &PyAny) -> PyResult<()> + Send + Sync + 'static,
+    {
+        let function: Function = Box::new(move |slf, args, _kwargs| {
+            let slf = slf.unwrap();
+            let (value,): (&PyAny,) = args.extract()?;
+            setter(slf, value).map(|()| slf.py().None().into_ptr())
+        });
+        self.property(name).setter = Some(function);
+        self
+    }
+
+    /// Creates the class.
+    pub fn build(self, py: Python<'_>) -> PyResult<&PyType> {
+        let base: &PyType = match &self.base {
+            Some(base) => base.as_ref(py),
+            None => py.get_type::<PyAny>(),
+        };
+        let base_ptr = base.as_type_ptr();
+        // Safety: `base_ptr` is a valid type object
+        if unsafe { find_class(py, base_ptr) }.is_some() {
+            return Err(PyTypeError::new_err(
+                "cannot extend a class created by PyClassBuilder",
+            ));
+        }
+        #[cfg(Py_LIMITED_API)]
+        if base_ptr != ptr::addr_of_mut!(ffi::PyBaseObject_Type) {
+            return Err(PyTypeError::new_err(
+                "subclassing native types is not possible with the `abi3` feature",
+            ));
+        }
+        if base
+            .getattr(crate::intern!(py, "__itemsize__"))?
+            .extract::<usize>()?
+            != 0
+        {
+            return Err(PyTypeError::new_err(format!(
+                "cannot extend variable-sized type '{}'",
+                base.name()?
+            )));
+        }
+
+        // The instance data is stored after the layout of the base
+        let base_size: usize = base
+            .getattr(crate::intern!(py, "__basicsize__"))?
+            .extract()?;
+        let align = std::mem::align_of::<InstanceData>();
+        let offset = (base_size + align - 1) / align * align;
+
+        let mut builder = PyTypeBuilder::default();
+        // Safety: these are the correct slot types, and the class' layout matches `offset`
+        unsafe {
+            builder = builder
+                .slot(ffi::Py_tp_base, base_ptr)
+                .slot(ffi::Py_tp_dealloc, class_dealloc as *mut c_void);
+            if self.constructor.is_some() {
+                builder = builder.slot(ffi::Py_tp_new, class_new as *mut c_void);
+            }
+        }
+        let type_object = builder.set_is_basetype(true).build(
+            py,
+            &self.name,
+            self.module.as_deref(),
+            offset + std::mem::size_of::<InstanceData>(),
+        )?;
+
+        let ty: &PyType = type_object.type_object.clone_ref(py).into_ref(py);
+        classes(py).insert(
+            ty.as_type_ptr() as usize,
+            Arc::new(ClassData {
+                base: base.into(),
+                offset,
+                constructor: self.constructor,
+                _type_object: type_object,
+            }),
+        );
+
+        if let Some(doc) = &self.doc {
+            ty.setattr(crate::intern!(py, "__doc__"), doc)?;
+        }
+        for mut method in self.methods {
+            method.qualname = format!("{}.{}", self.name, method.name);
+            let name = PyString::new(py, &method.name);
+            ty.setattr(name, MethodObject::create(py, Arc::new(method), None)?)?;
+        }
+        let property = py.import("builtins")?.getattr("property")?;
+        for mut data in self.properties {
+            let qualname = format!("{}.{}", self.name, data.name);
+            let accessor = |function: Option<Function>| {
+                function
+                    .map(|function| {
+                        let method = MethodData {
+                            name: data.name.clone(),
+                            qualname: qualname.clone(),
+                            doc: None,
+                            kind: MethodKind::Instance,
+                            function,
+                        };
+                        MethodObject::create(py, Arc::new(method), None)
+                    })
+                    .transpose()
+            };
+            let getter = accessor(data.getter.take())?;
+            let setter = accessor(data.setter.take())?;
+            ty.setattr(
+                PyString::new(py, &data.name),
+                property.call1((getter, setter, py.None(), data.doc))?,
+            )?;
+        }
+        Ok(ty)
+    }
+
+    /// Borrows the Rust value stored in an instance of a class created by `PyClassBuilder`.
+    ///
+    /// Fails if `obj` is not such an instance, if the value is not a `T`, or if the value is
+    /// currently mutably borrowed.
+    pub fn borrow_data<T: Any>(obj: &PyAny) -> PyResult<Ref<'_, T>> {
+        let data = instance_data(obj)?
+            .try_borrow()
+            .map_err(|_| PyRuntimeError::new_err("Already mutably borrowed"))?;
+        check_data_type::<T>(obj, &data)?;
+        Ok(Ref::map(data, |data| {
+            data.as_deref()
+                .and_then(<dyn Any + Send>::downcast_ref)
+                .unwrap()
+        }))
+    }
+
+    /// Mutably borrows the Rust value stored in an instance of a class created by
+    /// `PyClassBuilder`.
+    ///
+    /// Fails if `obj` is not such an instance, if the value is not a `T`, or if the value is
+    /// currently borrowed.
+    pub fn borrow_data_mut<T: Any>(obj: &PyAny) -> PyResult<RefMut<'_, T>> {
+        let data = instance_data(obj)?
+            .try_borrow_mut()
+            .map_err(|_| PyRuntimeError::new_err("Already borrowed"))?;
+        check_data_type::<T>(obj, &data)?;
+        Ok(RefMut::map(data, |data| {
+            data.as_deref_mut()
+                .and_then(<dyn Any + Send>::downcast_mut)
+                .unwrap()
+        }))
+    }
+
+    fn add_method<F, R>(mut self, name: &str, doc: Option<&str>, kind: MethodKind, f: F) -> Self
+    where
+        F: Fn(Option<&PyAny>, &PyTuple, Option<&PyDict>) -> PyResult<R> + Send + Sync + 'static,
+        R: IntoPy<PyObject>,
+    {
+        self.methods.push(MethodData {
+            name: name.to_owned(),
+            qualname: String::new(),
+            doc: doc.map(ToOwned::to_owned),
+            kind,
+            function: Box::new(move |slf, args, kwargs| {
+                f(slf, args, kwargs).map(|value| value.into_py(args.py()).into_ptr())
+            }),
+        });
+        self
+    }
+
+    fn property(&mut self, name: &str) -> &mut PropertyData {
+        let index = match self.properties.iter().position(|p| p.name == name) {
+            Some(index) => index,
+            None => {
+                self.properties.push(PropertyData {
+                    name: name.to_owned(),
+                    doc: None,
+                    getter: None,
+                    setter: None,
+                });
+                self.properties.len() - 1
+            }
+        };
+        &mut self.properties[index]
+    }
+}
+
+struct PropertyData {
+    name: String,
+    doc: Option<String>,
+    getter: Option<Function>,
+    setter: Option<Function>,
+}
+
+/// The data of a class created by `PyClassBuilder`, needed by its `__new__` and deallocation.
+struct ClassData {
+    base: Py<PyType>,
+    /// The offset of the `InstanceData` in instances of the class
+    offset: usize,
+    constructor: Option<Constructor>,
+    _type_object: PyClassTypeObject,
+}
+
+/// Returns the classes created by `PyClassBuilder`, by the address of their type object.
+fn classes(py: Python<'_>) -> std::sync::MutexGuard<'_, HashMap<usize, Arc<ClassData>>> {
+    static CLASSES: GILOnceCell<Mutex<HashMap<usize, Arc<ClassData>>>> = GILOnceCell::new();
+    CLASSES
+        .get_or_init(py, Default::default)
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Finds the class created by `PyClassBuilder` which `ty` is or derives from.
+///
+/// # Safety
+/// `ty` must be a valid type object.
+unsafe fn find_class(py: Python<'_>, mut ty: *mut ffi::PyTypeObject) -> Option<Arc<ClassData>> {
+    let classes = classes(py);
+    while !ty.is_null() {
+        if let Some(class) = classes.get(&(ty as usize)) {
+            return Some(class.clone());
+        }
+        ty = get_tp_base(ty);
+    }
+    None
+}
+
+unsafe fn get_tp_base(ty: *mut ffi::PyTypeObject) -> *mut ffi::PyTypeObject {
+    #[cfg(not(Py_LIMITED_API))]
+    {
+        (*ty).tp_base
+    }
+
+    #[cfg(Py_LIMITED_API)]
+    {
+        // PyType_GetSlot only supports static types from Python 3.10, but classes created by
+        // `PyClassBuilder` and their subclasses are all heap types
+        #[cfg(not(Py_3_10))]
+        if ffi::PyType_GetFlags(ty) & ffi::Py_TPFLAGS_HEAPTYPE == 0 {
+            return ptr::null_mut();
+        }
+        ffi::PyType_GetSlot(ty, ffi::Py_tp_base) as _
+    }
+}
+
+unsafe fn instance_data_ptr(obj: *mut ffi::PyObject, offset: usize) -> *mut InstanceData {
+    (obj as *mut u8).add(offset) as *mut InstanceData
+}
+
+fn instance_data(obj: &PyAny) -> PyResult<&InstanceData> {
+    // Safety: `obj` is a valid object, and the data of the class is at `offset` in its instances
+    unsafe {
+        match find_class(obj.py(), obj.get_type_ptr()) {
+            Some(class) => Ok(&*instance_data_ptr(obj.as_ptr(), class.offset)),
+            None => Err(PyTypeError::new_err(format!(
+                "'{}' object was not created by a PyClassBuilder class",
+                obj.get_type().name()?
+            ))),
+        }
+    }
+}
+
+fn check_data_type<T: Any>(obj: &PyAny, data: &Option<Box<dyn Any + Send>>) -> PyResult<()> {
+    if data.as_deref().map_or(false, <dyn Any + Send>::is::<T>) {
+        Ok(())
+    } else {
+        Err(PyTypeError::new_err(format!(
+            "data of '{}' object is not a `{}`",
+            obj.get_type().name()?,
+            std::any::type_name::<T>()
+        )))
+    }
+}
+
+unsafe extern "C" fn class_new(
+    subtype: *mut ffi::PyTypeObject,
+    args: *mut ffi::PyObject,
+    kwargs: *mut ffi::PyObject,
+) -> *mut ffi::PyObject {
+    trampoline::newfunc(subtype, args, kwargs, class_new_impl)
+}
+
+unsafe fn class_new_impl(
+    py: Python<'_>,
+    subtype: *mut ffi::PyTypeObject,
+    args: *mut ffi::PyObject,
+    kwargs: *mut ffi::PyObject,
+) -> PyResult<*mut ffi::PyObject> {
+    let class = find_class(py, subtype).expect("PyClassBuilder class not registered");
+    let obj = new_native_object(py, class.base.as_ptr() as _, subtype)?;
+    let data = instance_data_ptr(obj, class.offset);
+    ptr::write(data, RefCell::new(None));
+    // Dropping `obj` on error deallocates the new object
+    let obj = PyObject::from_owned_ptr(py, obj);
+
+    let constructor = class.constructor.as_ref().unwrap();
+    let value = constructor(
+        py.from_borrowed_ptr(args),
+        py.from_borrowed_ptr_or_opt(kwargs),
+    )?;
+    *(*data).borrow_mut() = Some(value);
+    Ok(obj.into_ptr())
+}
+
+unsafe extern "C" fn class_dealloc(obj: *mut ffi::PyObject) {
+    trampoline::dealloc(obj, class_dealloc_impl)
+}
+
+unsafe fn class_dealloc_impl(py: Python<'_>, obj: *mut ffi::PyObject) {
+    let class = find_class(py, ffi::Py_TYPE(obj)).expect("PyClassBuilder class not registered");
+    ptr::drop_in_place(instance_data_ptr(obj, class.offset));
+
+    let base = class.base.as_ptr() as *mut ffi::PyTypeObject;
+    if base == ptr::addr_of_mut!(ffi::PyBaseObject_Type) {
+        return get_tp_free(ffi::Py_TYPE(obj))(obj as _);
+    }
+
+    #[cfg(not(Py_LIMITED_API))]
+    {
+        if let Some(dealloc) = (*base).tp_dealloc {
+            dealloc(obj);
+        } else {
+            get_tp_free(ffi::Py_TYPE(obj))(obj as _);
+        }
+    }
+
+    #[cfg(Py_LIMITED_API)]
+    unreachable!("subclassing native types is not possible with the `abi3` feature");
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum MethodKind {
+    Instance,
+    Class,
+    Static,
+}
+
+struct MethodData {
+    name: String,
+    qualname: String,
+    doc: Option<String>,
+    kind: MethodKind,
+    function: Function,
+}
+
+/// A method of a class created by `PyClassBuilder`, which binds to instances or classes like
+/// Python functions do.
+#[repr(C)]
+struct MethodObject {
+    ob_base: ffi::PyObject,
+    method: *const MethodData,
+    /// The instance or class which the method is bound to, or null
+    bound: *mut ffi::PyObject,
+}
+
+impl MethodObject {
+    fn create<'py>(
+        py: Python<'py>,
+        method: Arc<MethodData>,
+        bound: Option<&PyAny>,
+    ) -> PyResult<&'py PyAny> {
+        let ty = method_type(py)?;
+        // Safety: `ty` has the layout of `MethodObject`
+        unsafe {
+            let obj = ffi::PyType_GenericAlloc(ty.as_type_ptr(), 0);
+            if obj.is_null() {
+                return Err(crate::PyErr::fetch(py));
+            }
+            let method_obj = obj as *mut MethodObject;
+            (*method_obj).method = Arc::into_raw(method);
+            (*method_obj).bound =
+                bound.map_or(ptr::null_mut(), |bound| ffi::_Py_NewRef(bound.as_ptr()));
+            Ok(py.from_owned_ptr(obj))
+        }
+    }
+
+    unsafe fn method<'a>(slf: *mut ffi::PyObject) -> &'a MethodData {
+        &*(*(slf as *mut MethodObject)).method
+    }
+
+    unsafe fn bound<'py>(py: Python<'py>, slf: *mut ffi::PyObject) -> Option<&'py PyAny> {
+        py.from_borrowed_ptr_or_opt((*(slf as *mut MethodObject)).bound)
+    }
+}
+
+fn method_type(py: Python<'_>) -> PyResult<&PyType> {
+    static METHOD_TYPE: GILOnceCell<PyClassTypeObject> = GILOnceCell::new();
+    static NAME: PyGetterDef = PyGetterDef::new("__name__\0", PyGetter(method_name), "\0");
+    static QUALNAME: PyGetterDef =
+        PyGetterDef::new("__qualname__\0", PyGetter(method_qualname), "\0");
+    static DOC: PyGetterDef = PyGetterDef::new("__doc__\0", PyGetter(method_doc), "\0");
+
+    METHOD_TYPE
+        .get_or_try_init(py, || {
+            let mut builder = PyTypeBuilder::default();
+            for getter in [&NAME, &QUALNAME, &DOC] {
+                builder.pymethod_def(&PyMethodDefType::Getter(getter.clone()));
+            }
+            // Safety: these are the correct slot types
+            unsafe {
+                builder
+                    .slot(ffi::Py_tp_dealloc, method_dealloc as *mut c_void)
+                    .slot(ffi::Py_tp_call, method_call as *mut c_void)
+                    .slot(ffi::Py_tp_descr_get, method_descr_get as *mut c_void)
+                    .slot(ffi::Py_tp_repr, method_repr as *mut c_void)
+                    .build(py, "pyo3_method", None, std::mem::size_of::<MethodObject>())
+            }
+        })
+        .map(|type_object| type_object.type_object.as_ref(py))
+}
+
+unsafe fn method_name(py: Python<'_>, slf: *mut ffi::PyObject) -> PyResult<*mut ffi::PyObject> {
+    Ok(PyString::new(py, &MethodObject::method(slf).name).into_ptr())
+}
+
+unsafe fn method_qualname(py: Python<'_>, slf: *mut ffi::PyObject) -> PyResult<*mut ffi::PyObject> {
+    Ok(PyString::new(py, &MethodObject::method(slf).qualname).into_ptr())
+}
+
+unsafe fn method_doc(py: Python<'_>, slf: *mut ffi::PyObject) -> PyResult<*mut ffi::PyObject> {
+    Ok(MethodObject::method(slf).doc.to_object(py).into_ptr())
+}
+
+unsafe extern "C" fn method_dealloc(slf: *mut ffi::PyObject) {
+    trampoline::dealloc(slf, method_dealloc_impl)
+}
+
+unsafe fn method_dealloc_impl(_py: Python<'_>, slf: *mut ffi::PyObject) {
+    let method_obj = slf as *mut MethodObject;
+    drop(Arc::from_raw((*method_obj).method));
+    ffi::Py_XDECREF((*method_obj).bound);
+    get_tp_free(ffi::Py_TYPE(slf))(slf as _);
+}
+
+unsafe extern "C" fn method_call(
+    slf: *mut ffi::PyObject,
+    args: *mut ffi::PyObject,
+    kwargs: *mut ffi::PyObject,
+) -> *mut ffi::PyObject {
+    trampoline::ternaryfunc(slf, args, kwargs, method_call_impl)
+}
+
+unsafe fn method_call_impl(
+    py: Python<'_>,
+    slf: *mut ffi::PyObject,
+    args: *mut ffi::PyObject,
+    kwargs: *mut ffi::PyObject,
+) -> PyResult<*mut ffi::PyObject> {
+    let method = MethodObject::method(slf);
+    let args: &PyTuple = py.from_borrowed_ptr(args);
+    let kwargs: Option<&PyDict> = py.from_borrowed_ptr_or_opt(kwargs);
+    match (MethodObject::bound(py, slf), method.kind) {
+        (bound @ Some(_), _) => (method.function)(bound, args, kwargs),
+        (None, MethodKind::Static) => (method.function)(None, args, kwargs),
+        // Called through the class, e.g. `Class.method(instance)`
+        (None, _) => match args.len() {
+            0 => Err(PyTypeError::new_err(format!(
+                "{}() needs an argument",
+                method.qualname
+            ))),
+            len => (method.function)(Some(args.get_item(0)?), args.get_slice(1, len), kwargs),
+        },
+    }
+}
+
+unsafe extern "C" fn method_descr_get(
+    slf: *mut ffi::PyObject,
+    obj: *mut ffi::PyObject,
+    ty: *mut ffi::PyObject,
+) -> *mut ffi::PyObject {
+    trampoline::descrgetfunc(slf, obj, ty, method_descr_get_impl)
+}
+
+unsafe fn method_descr_get_impl(
+    py: Python<'_>,
+    slf: *mut ffi::PyObject,
+    obj: *mut ffi::PyObject,
+    ty: *mut ffi::PyObject,
+) -> PyResult<*mut ffi::PyObject> {
+    let bound = match MethodObject::method(slf).kind {
+        MethodKind::Instance if !obj.is_null() && obj != ffi::Py_None() => obj,
+        MethodKind::Class if !ty.is_null() => ty,
+        MethodKind::Class => ffi::Py_TYPE(obj) as *mut ffi::PyObject,
+        _ => ptr::null_mut(),
+    };
+    if bound.is_null() || MethodObject::bound(py, slf).is_some() {
+        return Ok(ffi::_Py_NewRef(slf));
+    }
+    // Safety: `slf` holds a strong reference to the method data
+    let method = {
+        let method = (*(slf as *mut MethodObject)).method;
+        Arc::increment_strong_count(method);
+        Arc::from_raw(method)
+    };
+    MethodObject::create(py, method, Some(py.from_borrowed_ptr(bound))).map(|obj| obj.into_ptr())
+}
+
+unsafe extern "C" fn method_repr(slf: *mut ffi::PyObject) -> *mut ffi::PyObject {
+    trampoline::reprfunc(slf, method_repr_impl)
+}
+
+unsafe fn method_repr_impl(
+    py: Python<'_>,
+    slf: *mut ffi::PyObject,
+) -> PyResult<*mut ffi::PyObject> {
+    let method = MethodObject::method(slf);
+    let repr = match MethodObject::bound(py, slf) {
+        Some(bound) => format!("<bound method {} of {}>", method.qualname, bound.repr()?),
+        None => format!("<method {}>", method.qualname),
+    };
+    Ok(PyString::new(py, &repr).into_ptr())
+}
@@ -0,0 +1,108 @@
+use crate::{IntoPy, PyAny, PyErr, PyObject, PyResult, Python};
+
+/// Classes usable in a Python `with` block.
+///
+/// `#[pyclass(context_manager)]` generates `__enter__` and `__exit__` calling these methods, so a
+/// Rust resource such as a lock guard or a transaction can be acquired on entering the block and
+/// released on leaving it.
+///
+/// ```rust
+/// use pyo3::exceptions::PyRuntimeError;
+/// use pyo3::prelude::*;
+/// use pyo3::pyclass::{BindSelf, PyContextManager};
+///
+/// #[pyclass(context_manager)]
+/// struct Transaction {
+///     #[pyo3(get)]
+///     log: Vec<String>,
+///     pending: Option<Vec<String>>,
+/// }
+///
+/// #[pymethods]
+/// impl Transaction {
+///     fn write(&mut self, entry: String) -> PyResult<()> {
+///         match &mut self.pending {
+///             Some(pending) => {
+///                 pending.push(entry);
+///                 Ok(())
+///             }
+///             None => Err(PyRuntimeError::new_err("no transaction in progress")),
+///         }
+///     }
+/// }
+///
+/// impl PyContextManager for Transaction {
+///     // `with Transaction() as transaction:` binds the object itself
+///     type Target = BindSelf;
+///
+///     fn enter(&mut self) -> PyResult<BindSelf> {
+///         if self.pending.is_some() {
+///             return Err(PyRuntimeError::new_err("a transaction is already in progress"));
+///         }
+///         self.pending = Some(Vec::new());
+///         Ok(BindSelf)
+///     }
+///
+///     fn exit(&mut self, error: Option<PyErr>) -> PyResult<bool> {
+///         let pending = self.pending.take().unwrap_or_default();
+///         // commit only if the block succeeded, and let its error propagate otherwise
+///         if error.is_none() {
+///             self.log.extend(pending);
+///         }
+///         Ok(false)
+///     }
+/// }
+/// ```
+pub trait PyContextManager {
+    /// The value bound by `with ... as value`: any value convertible to a Python object, or
+    /// [`BindSelf`] to bind the object being entered.
+    type Target: IntoEnterTarget;
+
+    /// Called on entering the `with` block.
+    fn enter(&mut self) -> PyResult<Self::Target>;
+
+    /// Called on leaving the `with` block, with the exception raised by the block if any.
+    ///
+    /// Returning `true` suppresses the exception, and `false` lets it propagate.
+    fn exit(&mut self, error: Option<PyErr>) -> PyResult<bool>;
+}
+
+/// A [`PyContextManager::Target`] binding the object being entered, as `with open(path) as file:`
+/// does.
+#[derive(Debug, Clone, Copy)]
+pub struct BindSelf;
+
+/// Values returned by [`PyContextManager::enter`], converted to the value bound by
+/// `with ... as value`.
+pub trait IntoEnterTarget {
+    /// Converts `self` to the value bound by `with ... as value`, given the object being entered.
+    fn into_enter_target(self, slf: &PyAny) -> PyObject;
+}
+
+impl<T: IntoPy<PyObject>> IntoEnterTarget for T {
+    fn into_enter_target(self, slf: &PyAny) -> PyObject {
+        self.into_py(slf.py())
+    }
+}
+
+impl IntoEnterTarget for BindSelf {
+    fn into_enter_target(self, slf: &PyAny) -> PyObject {
+        slf.into()
+    }
+}
+
+/// Classes usable in a Python `async with` block.
+///
+/// `#[pyclass(context_manager(async))]` generates `__aenter__` and `__aexit__` calling these
+/// methods. Both return an awaitable, such as a coroutine of the Python code driving the resource
+/// or a future converted by an async runtime integration. The result of awaiting `aenter` is bound
+/// by `async with ... as value`, and the result of awaiting `aexit` says whether to suppress the
+/// exception raised by the block.
+pub trait PyAsyncContextManager {
+    /// Called on entering the `async with` block, returning the awaitable to enter it.
+    fn aenter(&mut self, py: Python<'_>) -> PyResult<PyObject>;
+
+    /// Called on leaving the `async with` block, with the exception raised by the block if any,
+    /// returning the awaitable to leave it.
+    fn aexit(&mut self, py: Python<'_>, error: Option<PyErr>) -> PyResult<PyObject>;
+}
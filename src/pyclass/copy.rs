@@ -0,0 +1,143 @@
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::hash::{BuildHasher, Hash};
+
+use crate::types::PyDict;
+use crate::{FromPyObject, Py, PyResult};
+
+/// Types which can be deep copied for `copy.deepcopy`.
+///
+/// `#[pyclass(clone)]` generates `__deepcopy__` from this trait, which every field of the struct
+/// must implement, unless it is marked with `#[pyo3(deepcopy_skip)]` to be copied with [`Clone`]
+/// instead. It is implemented for [`Py<T>`], by calling `copy.deepcopy`, for the standard
+/// containers of other types implementing it, and for the primitive types and [`String`], which
+/// hold no Python objects.
+///
+/// ```rust
+/// use pyo3::prelude::*;
+/// use pyo3::pyclass::PyDeepCopy;
+/// use pyo3::types::PyDict;
+///
+/// #[derive(Clone)]
+/// struct Callbacks {
+///     on_change: Option<PyObject>,
+///     on_close: Option<PyObject>,
+/// }
+///
+/// impl PyDeepCopy for Callbacks {
+///     fn deep_copy(&self, memo: &PyDict) -> PyResult<Self> {
+///         Ok(Callbacks {
+///             on_change: self.on_change.deep_copy(memo)?,
+///             on_close: self.on_close.deep_copy(memo)?,
+///         })
+///     }
+/// }
+///
+/// #[pyclass(clone)]
+/// #[derive(Clone)]
+/// struct Button {
+///     callbacks: Callbacks,
+/// }
+/// ```
+pub trait PyDeepCopy: Sized {
+    /// Returns a copy of `self` which shares no Python object with it.
+    ///
+    /// `memo` is the dict of the `copy.deepcopy` call in progress, which must be passed along so
+    /// that an object referenced several times is only copied once.
+    fn deep_copy(&self, memo: &PyDict) -> PyResult<Self>;
+}
+
+/// Calls `copy.deepcopy(self, memo)`, so that an object shared between fields is copied once.
+impl<T> PyDeepCopy for Py<T>
+where
+    for<'py> Py<T>: FromPyObject<'py>,
+{
+    fn deep_copy(&self, memo: &PyDict) -> PyResult<Self> {
+        let py = memo.py();
+        py.import(intern!(py, "copy"))?
+            .getattr(intern!(py, "deepcopy"))?
+            .call1((self.clone_ref(py), memo))?
+            .extract()
+    }
+}
+
+impl<T: PyDeepCopy> PyDeepCopy for Box<T> {
+    fn deep_copy(&self, memo: &PyDict) -> PyResult<Self> {
+        (**self).deep_copy(memo).map(Box::new)
+    }
+}
+
+impl<T: PyDeepCopy> PyDeepCopy for Option<T> {
+    fn deep_copy(&self, memo: &PyDict) -> PyResult<Self> {
+        self.as_ref().map(|value| value.deep_copy(memo)).transpose()
+    }
+}
+
+impl<T: PyDeepCopy> PyDeepCopy for Vec<T> {
+    fn deep_copy(&self, memo: &PyDict) -> PyResult<Self> {
+        self.iter().map(|value| value.deep_copy(memo)).collect()
+    }
+}
+
+impl<T: PyDeepCopy> PyDeepCopy for VecDeque<T> {
+    fn deep_copy(&self, memo: &PyDict) -> PyResult<Self> {
+        self.iter().map(|value| value.deep_copy(memo)).collect()
+    }
+}
+
+/// Deep copies the values of the map, as Python objects can't be used as keys of a Rust map.
+impl<K, V, S> PyDeepCopy for HashMap<K, V, S>
+where
+    K: Clone + Eq + Hash,
+    V: PyDeepCopy,
+    S: BuildHasher + Default,
+{
+    fn deep_copy(&self, memo: &PyDict) -> PyResult<Self> {
+        self.iter()
+            .map(|(key, value)| Ok((key.clone(), value.deep_copy(memo)?)))
+            .collect()
+    }
+}
+
+/// Deep copies the values of the map, as Python objects can't be used as keys of a Rust map.
+impl<K: Clone + Ord, V: PyDeepCopy> PyDeepCopy for BTreeMap<K, V> {
+    fn deep_copy(&self, memo: &PyDict) -> PyResult<Self> {
+        self.iter()
+            .map(|(key, value)| Ok((key.clone(), value.deep_copy(memo)?)))
+            .collect()
+    }
+}
+
+macro_rules! deep_copy_by_clone {
+    ($($ty:ty),*) => {
+        $(
+            impl PyDeepCopy for $ty {
+                #[inline]
+                fn deep_copy(&self, _memo: &PyDict) -> PyResult<Self> {
+                    Ok(self.clone())
+                }
+            }
+        )*
+    };
+}
+
+deep_copy_by_clone!(
+    (),
+    bool,
+    char,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    f32,
+    f64,
+    String,
+    &'static str
+);
@@ -32,10 +32,13 @@ where
     T: PyClass,
 {
     unsafe {
+        let base = T::BaseType::type_object_raw(py);
         PyTypeBuilder::default()
             .type_doc(T::doc(py)?)
             .offsets(T::dict_offset(), T::weaklist_offset())
-            .slot(ffi::Py_tp_base, T::BaseType::type_object_raw(py))
+            .metaclass(class_metaclass::<T>(py, base)?)
+            .inherit_type_new(is_metaclass_without_data::<T>(base))
+            .slot(ffi::Py_tp_base, base)
             .slot(ffi::Py_tp_dealloc, tp_dealloc::<T> as *mut c_void)
             .set_is_basetype(T::IS_BASETYPE)
             .set_is_mapping(T::IS_MAPPING)
@@ -45,10 +48,46 @@ where
     }
 }
 
+/// The metaclass of a `#[pyclass]`: the one given with `#[pyclass(metaclass = ...)]`, which must be
+/// a subclass of the metaclass of the base class, or else the metaclass of the base class.
+unsafe fn class_metaclass<T: PyClass>(
+    py: Python<'_>,
+    base: *mut ffi::PyTypeObject,
+) -> PyResult<*mut ffi::PyTypeObject> {
+    let base_metaclass = ffi::Py_TYPE(base.cast());
+    match T::metaclass(py) {
+        Some(metaclass) if ffi::PyType_IsSubtype(metaclass, base_metaclass) == 0 => {
+            Err(PyTypeError::new_err(format!(
+                "the metaclass of `#[pyclass]` {} must be a subclass of the metaclass of its base class",
+                T::NAME
+            )))
+        }
+        Some(metaclass) => Ok(metaclass),
+        None => Ok(base_metaclass),
+    }
+}
+
+/// Whether `T` is a metaclass which doesn't add any data to the classes it creates, so that
+/// `type.__new__` can create them, such as the classes defined in Python with it.
+unsafe fn is_metaclass_without_data<T: PyClass>(base: *mut ffi::PyTypeObject) -> bool {
+    #[cfg(not(Py_LIMITED_API))]
+    {
+        ffi::PyType_IsSubtype(base, std::ptr::addr_of_mut!(ffi::PyType_Type)) != 0
+            && std::mem::size_of::<T::Layout>() == (*base).tp_basicsize as usize
+    }
+
+    // Native types, `type` included, can't be extended with the limited API
+    #[cfg(Py_LIMITED_API)]
+    {
+        let _ = base;
+        false
+    }
+}
+
 type PyTypeBuilderCleanup = Box<dyn Fn(&PyTypeBuilder, *mut ffi::PyTypeObject)>;
 
 #[derive(Default)]
-struct PyTypeBuilder {
+pub(super) struct PyTypeBuilder {
     slots: Vec<ffi::PyType_Slot>,
     method_defs: Vec<ffi::PyMethodDef>,
     getset_builders: HashMap<&'static str, GetSetDefBuilder>,
@@ -59,6 +98,9 @@ struct PyTypeBuilder {
     is_mapping: bool,
     is_sequence: bool,
     has_new: bool,
+    inherits_type_new: bool,
+    /// `None` for `type`, the default metaclass.
+    metaclass: Option<*mut ffi::PyTypeObject>,
     has_dealloc: bool,
     has_getitem: bool,
     has_setitem: bool,
@@ -116,12 +158,12 @@ impl PyTypeBuilder {
 
     /// # Safety
     /// The given pointer must be of the correct type for the given slot
-    unsafe fn slot<T>(mut self, slot: c_int, pfunc: *mut T) -> Self {
+    pub(super) unsafe fn slot<T>(mut self, slot: c_int, pfunc: *mut T) -> Self {
         self.push_slot(slot, pfunc);
         self
     }
 
-    fn pymethod_def(&mut self, def: &PyMethodDefType) {
+    pub(super) fn pymethod_def(&mut self, def: &PyMethodDefType) {
         match def {
             PyMethodDefType::Getter(getter) => {
                 self.getset_builders
@@ -213,13 +255,27 @@ impl PyTypeBuilder {
         Ok(getset_destructors)
     }
 
-    fn set_is_basetype(mut self, is_basetype: bool) -> Self {
+    pub(super) fn set_is_basetype(mut self, is_basetype: bool) -> Self {
         if is_basetype {
             self.class_flags |= ffi::Py_TPFLAGS_BASETYPE;
         }
         self
     }
 
+    fn metaclass(mut self, metaclass: *mut ffi::PyTypeObject) -> Self {
+        #[allow(unused_unsafe)]
+        let type_type = unsafe { std::ptr::addr_of_mut!(ffi::PyType_Type) };
+        if metaclass != type_type {
+            self.metaclass = Some(metaclass);
+        }
+        self
+    }
+
+    fn inherit_type_new(mut self, inherits_type_new: bool) -> Self {
+        self.inherits_type_new = inherits_type_new;
+        self
+    }
+
     fn set_is_mapping(mut self, is_mapping: bool) -> Self {
         self.is_mapping = is_mapping;
         self
@@ -329,11 +385,11 @@ impl PyTypeBuilder {
         self
     }
 
-    fn build(
+    pub(super) fn build(
         mut self,
         py: Python<'_>,
-        name: &'static str,
-        module_name: Option<&'static str>,
+        name: &str,
+        module_name: Option<&str>,
         basicsize: usize,
     ) -> PyResult<PyClassTypeObject> {
         // `c_ulong` and `c_uint` have the same size
@@ -342,7 +398,7 @@ impl PyTypeBuilder {
 
         let getset_destructors = self.finalize_methods_and_properties()?;
 
-        if !self.has_new {
+        if !self.has_new && !self.inherits_type_new {
             // Safety: This is the correct slot type for Py_tp_new
             unsafe { self.push_slot(ffi::Py_tp_new, no_constructor_defined as *mut c_void) }
         }
@@ -383,9 +439,13 @@ impl PyTypeBuilder {
             slots: self.slots.as_mut_ptr(),
         };
 
-        // Safety: We've correctly setup the PyType_Spec at this point
-        let type_object: Py<PyType> =
-            unsafe { Py::from_owned_ptr_or_err(py, ffi::PyType_FromSpec(&mut spec))? };
+        let type_object: Py<PyType> = match self.metaclass {
+            // Safety: We've correctly setup the PyType_Spec at this point
+            None => unsafe { Py::from_owned_ptr_or_err(py, ffi::PyType_FromSpec(&mut spec))? },
+            Some(metaclass) => unsafe {
+                create_type_with_metaclass(py, name, metaclass, &mut spec)?
+            },
+        };
 
         for cleanup in std::mem::take(&mut self.cleanup) {
             cleanup(&self, type_object.as_ref(py).as_type_ptr());
@@ -398,6 +458,51 @@ impl PyTypeBuilder {
     }
 }
 
+/// Creates the type of `spec` as an instance of `metaclass`.
+///
+/// # Safety
+/// `metaclass` must be a subclass of `type`, and `spec` must be correctly set up.
+unsafe fn create_type_with_metaclass(
+    py: Python<'_>,
+    name: &str,
+    metaclass: *mut ffi::PyTypeObject,
+    spec: &mut ffi::PyType_Spec,
+) -> PyResult<Py<PyType>> {
+    // The type is created without calling the metaclass, which can't initialize any data of its
+    // own in it.
+    let metaclass_type: &PyType = py.from_borrowed_ptr(metaclass.cast());
+    let basicsize = crate::intern!(py, "__basicsize__");
+    if !metaclass_type
+        .getattr(basicsize)?
+        .eq(PyType::new::<PyType>(py).getattr(basicsize)?)?
+    {
+        return Err(PyTypeError::new_err(format!(
+            "the metaclass of `#[pyclass]` {} can't add data to the classes it creates: it must be `frozen` and have no fields",
+            name
+        )));
+    }
+
+    #[cfg(Py_3_12)]
+    {
+        Py::from_owned_ptr_or_err(
+            py,
+            ffi::PyType_FromMetaclass(metaclass, ptr::null_mut(), spec, ptr::null_mut()),
+        )
+    }
+
+    // Before Python 3.12, types created from a spec are always instances of `type`. The
+    // `metaclass` option is a compile error there, so this is only reached by a class which
+    // implements `PyClassImpl::metaclass` by hand.
+    #[cfg(not(Py_3_12))]
+    {
+        let _ = spec;
+        Err(PyTypeError::new_err(format!(
+            "the metaclass of `#[pyclass]` {} needs Python 3.12 or later",
+            name
+        )))
+    }
+}
+
 fn py_class_qualified_name(module_name: Option<&str>, class_name: &str) -> PyResult<*mut c_char> {
     Ok(CString::new(format!(
         "{}.{}",
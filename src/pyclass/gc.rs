@@ -1,6 +1,7 @@
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::os::raw::{c_int, c_void};
 
-use crate::{ffi, AsPyPointer, Python};
+use crate::{ffi, AsPyPointer, Py, Python};
 
 /// Error returned by a `__traverse__` visitor implementation.
 #[repr(transparent)]
@@ -46,3 +47,176 @@ impl<'p> PyVisit<'p> {
         }
     }
 }
+
+/// Types holding Python objects which the garbage collector should know about.
+///
+/// `#[pyclass(traverse)]` generates `__traverse__` and `__clear__` from this trait, which every
+/// field of the struct must implement, unless it is marked with `#[pyo3(traverse_skip)]`. It is
+/// implemented for [`Py<T>`], for the standard containers of other types implementing it, and for
+/// the primitive types and [`String`], which hold no Python objects. Implement it for your own
+/// types held in fields.
+///
+/// ```rust
+/// use pyo3::prelude::*;
+/// use pyo3::pyclass::{PyTraverse, PyTraverseError, PyVisit};
+///
+/// struct Callbacks {
+///     on_change: Option<PyObject>,
+///     on_close: Option<PyObject>,
+/// }
+///
+/// impl PyTraverse for Callbacks {
+///     fn traverse(&self, visit: &PyVisit<'_>) -> Result<(), PyTraverseError> {
+///         self.on_change.traverse(visit)?;
+///         self.on_close.traverse(visit)
+///     }
+///
+///     fn clear(&mut self) {
+///         self.on_change.clear();
+///         self.on_close.clear();
+///     }
+/// }
+///
+/// #[pyclass(traverse)]
+/// struct Button {
+///     callbacks: Callbacks,
+/// }
+/// ```
+pub trait PyTraverse {
+    /// Calls `visit` with each Python object held by `self`.
+    fn traverse(&self, visit: &PyVisit<'_>) -> Result<(), PyTraverseError>;
+
+    /// Drops the Python objects held by `self` where possible, to break reference cycles.
+    ///
+    /// A [`Py<T>`] can't be dropped without replacing it, so the default implementation, which is
+    /// used by `Py<T>` and the types holding no Python objects, does nothing. Hold objects which
+    /// can be part of a cycle in an [`Option`] or a collection to let `__clear__` drop them, which
+    /// `#[pyclass(traverse)]` requires for the fields of a class which isn't frozen.
+    fn clear(&mut self) {}
+}
+
+impl<T> PyTraverse for Py<T> {
+    fn traverse(&self, visit: &PyVisit<'_>) -> Result<(), PyTraverseError> {
+        visit.call(self)
+    }
+}
+
+impl<T: PyTraverse + ?Sized> PyTraverse for Box<T> {
+    fn traverse(&self, visit: &PyVisit<'_>) -> Result<(), PyTraverseError> {
+        (**self).traverse(visit)
+    }
+
+    fn clear(&mut self) {
+        (**self).clear()
+    }
+}
+
+impl<T: PyTraverse> PyTraverse for Option<T> {
+    fn traverse(&self, visit: &PyVisit<'_>) -> Result<(), PyTraverseError> {
+        match self {
+            Some(value) => value.traverse(visit),
+            None => Ok(()),
+        }
+    }
+
+    fn clear(&mut self) {
+        *self = None;
+    }
+}
+
+impl<T: PyTraverse> PyTraverse for [T] {
+    fn traverse(&self, visit: &PyVisit<'_>) -> Result<(), PyTraverseError> {
+        self.iter().try_for_each(|value| value.traverse(visit))
+    }
+
+    fn clear(&mut self) {
+        self.iter_mut().for_each(PyTraverse::clear)
+    }
+}
+
+impl<T: PyTraverse, const N: usize> PyTraverse for [T; N] {
+    fn traverse(&self, visit: &PyVisit<'_>) -> Result<(), PyTraverseError> {
+        self[..].traverse(visit)
+    }
+
+    fn clear(&mut self) {
+        self[..].clear()
+    }
+}
+
+impl<T: PyTraverse> PyTraverse for Vec<T> {
+    fn traverse(&self, visit: &PyVisit<'_>) -> Result<(), PyTraverseError> {
+        self[..].traverse(visit)
+    }
+
+    fn clear(&mut self) {
+        Vec::clear(self)
+    }
+}
+
+impl<T: PyTraverse> PyTraverse for VecDeque<T> {
+    fn traverse(&self, visit: &PyVisit<'_>) -> Result<(), PyTraverseError> {
+        self.iter().try_for_each(|value| value.traverse(visit))
+    }
+
+    fn clear(&mut self) {
+        VecDeque::clear(self)
+    }
+}
+
+/// Visits the values of the map, as Python objects can't be used as keys of a Rust map.
+impl<K, V: PyTraverse, S> PyTraverse for HashMap<K, V, S> {
+    fn traverse(&self, visit: &PyVisit<'_>) -> Result<(), PyTraverseError> {
+        self.values().try_for_each(|value| value.traverse(visit))
+    }
+
+    fn clear(&mut self) {
+        HashMap::clear(self)
+    }
+}
+
+/// Visits the values of the map, as Python objects can't be used as keys of a Rust map.
+impl<K, V: PyTraverse> PyTraverse for BTreeMap<K, V> {
+    fn traverse(&self, visit: &PyVisit<'_>) -> Result<(), PyTraverseError> {
+        self.values().try_for_each(|value| value.traverse(visit))
+    }
+
+    fn clear(&mut self) {
+        BTreeMap::clear(self)
+    }
+}
+
+macro_rules! traverse_nothing {
+    ($($ty:ty),*) => {
+        $(
+            impl PyTraverse for $ty {
+                #[inline]
+                fn traverse(&self, _visit: &PyVisit<'_>) -> Result<(), PyTraverseError> {
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+traverse_nothing!(
+    (),
+    bool,
+    char,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    f32,
+    f64,
+    String,
+    &'static str
+);
@@ -40,49 +40,54 @@ impl<T: PyTypeInfo> PyObjectInit<T> for PyNativeTypeInitializer<T> {
         py: Python<'_>,
         subtype: *mut PyTypeObject,
     ) -> PyResult<*mut ffi::PyObject> {
-        unsafe fn inner(
-            py: Python<'_>,
-            type_object: *mut PyTypeObject,
-            subtype: *mut PyTypeObject,
-        ) -> PyResult<*mut ffi::PyObject> {
-            // HACK (due to FIXME below): PyBaseObject_Type's tp_new isn't happy with NULL arguments
-            let is_base_object = type_object == std::ptr::addr_of_mut!(ffi::PyBaseObject_Type);
-            if is_base_object {
-                let alloc = get_tp_alloc(subtype).unwrap_or(ffi::PyType_GenericAlloc);
-                let obj = alloc(subtype, 0);
-                return if obj.is_null() {
+        let type_object = T::type_object_raw(py);
+        new_native_object(py, type_object, subtype)
+    }
+
+    private_impl! {}
+}
+
+/// Creates a new object of `subtype` using the `__new__` of its native base `type_object`.
+///
+/// # Safety
+/// - `subtype` must be a valid pointer to a subtype of `type_object`.
+pub(crate) unsafe fn new_native_object(
+    py: Python<'_>,
+    type_object: *mut PyTypeObject,
+    subtype: *mut PyTypeObject,
+) -> PyResult<*mut ffi::PyObject> {
+    // HACK (due to FIXME below): PyBaseObject_Type's tp_new isn't happy with NULL arguments
+    let is_base_object = type_object == std::ptr::addr_of_mut!(ffi::PyBaseObject_Type);
+    if is_base_object {
+        let alloc = get_tp_alloc(subtype).unwrap_or(ffi::PyType_GenericAlloc);
+        let obj = alloc(subtype, 0);
+        return if obj.is_null() {
+            Err(PyErr::fetch(py))
+        } else {
+            Ok(obj)
+        };
+    }
+
+    #[cfg(Py_LIMITED_API)]
+    unreachable!("subclassing native types is not possible with the `abi3` feature");
+
+    #[cfg(not(Py_LIMITED_API))]
+    {
+        match (*type_object).tp_new {
+            // FIXME: Call __new__ with actual arguments
+            Some(newfunc) => {
+                let obj = newfunc(subtype, std::ptr::null_mut(), std::ptr::null_mut());
+                if obj.is_null() {
                     Err(PyErr::fetch(py))
                 } else {
                     Ok(obj)
-                };
-            }
-
-            #[cfg(Py_LIMITED_API)]
-            unreachable!("subclassing native types is not possible with the `abi3` feature");
-
-            #[cfg(not(Py_LIMITED_API))]
-            {
-                match (*type_object).tp_new {
-                    // FIXME: Call __new__ with actual arguments
-                    Some(newfunc) => {
-                        let obj = newfunc(subtype, std::ptr::null_mut(), std::ptr::null_mut());
-                        if obj.is_null() {
-                            Err(PyErr::fetch(py))
-                        } else {
-                            Ok(obj)
-                        }
-                    }
-                    None => Err(crate::exceptions::PyTypeError::new_err(
-                        "base type without tp_new",
-                    )),
                 }
             }
+            None => Err(crate::exceptions::PyTypeError::new_err(
+                "base type without tp_new",
+            )),
         }
-        let type_object = T::type_object_raw(py);
-        inner(py, type_object, subtype)
     }
-
-    private_impl! {}
 }
 
 /// Initializer for our `#[pyclass]` system.
@@ -0,0 +1,107 @@
+use crate::impl_::pytrait::{check_methods, PyTrait};
+use crate::{FromPyObject, IntoPy, PyAny, PyObject, PyResult, Python, ToPyObject};
+use std::fmt;
+use std::marker::PhantomData;
+
+/// A Python object implementing a Rust trait.
+///
+/// For a trait annotated with [`#[pytrait]`](macro@crate::pytrait), `PyImpl<dyn Trait>`
+/// implements `Trait` by calling the Python methods of the same names on the wrapped object,
+/// converting the arguments with [`IntoPy`] (or [`ToPyObject`] for references) and the return
+/// values with [`FromPyObject`].
+///
+/// Extracting a `PyImpl<dyn Trait>` fails with a `TypeError` unless the object has a method for
+/// each trait method without a default implementation. Trait methods with a default
+/// implementation use it when the object has no method of that name.
+///
+/// Errors raised by the Python methods are returned by trait methods returning `Result<T, E>`
+/// with `E: From<PyErr>`. Other trait methods panic if the Python method raises an exception or
+/// its return value cannot be converted.
+///
+/// # Examples
+///
+/// ```rust
+/// use pyo3::prelude::*;
+/// use pyo3::PyImpl;
+///
+/// #[pyo3::pytrait]
+/// trait Greeter {
+///     fn greet(&self, name: &str) -> PyResult<String>;
+/// }
+///
+/// #[pyfunction]
+/// fn greet_all(greeter: PyImpl<dyn Greeter>, names: Vec<String>) -> PyResult<Vec<String>> {
+///     names.iter().map(|name| greeter.greet(name)).collect()
+/// }
+///
+/// Python::with_gil(|py| {
+///     let greet_all = wrap_pyfunction!(greet_all, py).unwrap();
+///     pyo3::py_run!(py, greet_all, r#"
+///         class Polite:
+///             def greet(self, name):
+///                 return f"Good morning, {name}."
+///
+///         assert greet_all(Polite(), ["Ada"]) == ["Good morning, Ada."]
+///     "#);
+/// });
+/// ```
+pub struct PyImpl<T: ?Sized> {
+    object: PyObject,
+    marker: PhantomData<fn() -> Box<T>>,
+}
+
+impl<T: ?Sized + PyTrait> PyImpl<T> {
+    /// Wraps `object`, checking that it has the methods required by the trait.
+    pub fn new(object: &PyAny) -> PyResult<Self> {
+        check_methods::<T>(object)?;
+        Ok(PyImpl {
+            object: object.into(),
+            marker: PhantomData,
+        })
+    }
+}
+
+impl<T: ?Sized> PyImpl<T> {
+    /// Returns the underlying Python object.
+    pub fn as_ref<'py>(&'py self, py: Python<'py>) -> &'py PyAny {
+        self.object.as_ref(py)
+    }
+
+    /// Returns the underlying Python object.
+    pub fn into_inner(self) -> PyObject {
+        self.object
+    }
+}
+
+impl<'source, T: ?Sized + PyTrait> FromPyObject<'source> for PyImpl<T> {
+    fn extract(obj: &'source PyAny) -> PyResult<Self> {
+        PyImpl::new(obj)
+    }
+}
+
+impl<T: ?Sized> ToPyObject for PyImpl<T> {
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        self.object.clone_ref(py)
+    }
+}
+
+impl<T: ?Sized> IntoPy<PyObject> for PyImpl<T> {
+    fn into_py(self, _py: Python<'_>) -> PyObject {
+        self.object
+    }
+}
+
+impl<T: ?Sized> Clone for PyImpl<T> {
+    fn clone(&self) -> Self {
+        PyImpl {
+            object: self.object.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for PyImpl<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("PyImpl").field(&self.object).finish()
+    }
+}
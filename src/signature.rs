@@ -0,0 +1,574 @@
+//! Parsing the arguments of Python calls at runtime.
+//!
+//! `#[pyfunction]` and `#[pymethods]` sort the arguments they receive into parameters according
+//! to their Rust signature. Functions whose signature is only known at runtime, such as closures
+//! wrapped with [`PyCFunction::new_closure`](crate::types::PyCFunction::new_closure), can do the
+//! same with a [`Signature`], to support positional-only and keyword-only parameters, defaults,
+//! `*args` and `**kwargs` with the same error messages as `#[pyfunction]`.
+//!
+//! Parsing happens in two steps: [`Signature::extract_arguments`] sorts the arguments into one
+//! slot per parameter, then [`extract_required`], [`extract_optional`] and
+//! [`extract_with_default`] convert each slot to a Rust value, naming the parameter in any
+//! `TypeError`. Arguments passed with the "fastcall" convention, as a vector of positional
+//! arguments and keyword values with a tuple of keyword names, are sorted by
+//! `Signature::extract_arguments_fastcall` instead, which isn't available with the `abi3` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use pyo3::prelude::*;
+//! use pyo3::signature::{extract_required, extract_with_default, Signature};
+//! use pyo3::types::PyCFunction;
+//!
+//! # fn main() -> PyResult<()> {
+//! // Equivalent to the Python signature `greet(name, /, *, greeting="Hello")`
+//! let signature = Signature::new("greet")
+//!     .positional_only("name", true)
+//!     .keyword_only("greeting", false);
+//!
+//! Python::with_gil(|py| {
+//!     let greet = PyCFunction::new_closure(py, Some("greet"), None, move |args, kwargs| {
+//!         let mut output = [None; 2];
+//!         signature.extract_arguments(args, kwargs, &mut output)?;
+//!         let name: &str = extract_required(output[0], "name")?;
+//!         let greeting: &str = extract_with_default(output[1], "greeting", || "Hello")?;
+//!         PyResult::Ok(format!("{}, {}!", greeting, name))
+//!     })?;
+//!
+//!     pyo3::py_run!(py, greet, r#"
+//!         assert greet("World") == "Hello, World!"
+//!         assert greet("Ferris", greeting="Hi") == "Hi, Ferris!"
+//!
+//!         try:
+//!             greet()
+//!         except TypeError as e:
+//!             assert str(e) == "greet() missing 1 required positional argument: 'name'"
+//!     "#);
+//!     Ok(())
+//! })
+//! # }
+//! ```
+
+use crate::exceptions::PyTypeError;
+use crate::impl_::extract_argument::{
+    argument_extraction_error, DictVarkeywords, FunctionDescription,
+    KeywordOnlyParameterDescription, NoVarargs, NoVarkeywords, TupleVarargs,
+};
+use crate::types::{PyDict, PyTuple};
+#[cfg(not(Py_LIMITED_API))]
+use crate::{ffi, AsPyPointer, Python};
+use crate::{FromPyObject, PyAny, PyResult};
+
+/// Evaluates `$extract` with `V` and `K` set to the handlers of the surplus arguments of the
+/// signature, and converts what they collected to a tuple and an optional dict.
+macro_rules! extract_with_handlers {
+    ($signature:expr, $py:expr, |$V:ident, $K:ident| $extract:expr) => {
+        match ($signature.varargs, $signature.varkeywords) {
+            (false, false) => {
+                type $V = NoVarargs;
+                type $K = NoVarkeywords;
+                let ((), ()) = $extract?;
+                Ok((PyTuple::empty($py), None))
+            }
+            (true, false) => {
+                type $V = TupleVarargs;
+                type $K = NoVarkeywords;
+                let (varargs, ()) = $extract?;
+                Ok((varargs, None))
+            }
+            (false, true) => {
+                type $V = NoVarargs;
+                type $K = DictVarkeywords;
+                let ((), varkeywords) = $extract?;
+                Ok((PyTuple::empty($py), varkeywords))
+            }
+            (true, true) => {
+                type $V = TupleVarargs;
+                type $K = DictVarkeywords;
+                $extract
+            }
+        }
+    };
+}
+
+/// The signature of a function, built at runtime.
+///
+/// Parameters are added in the order of a Python signature: positional-only parameters, then
+/// positional-or-keyword parameters, then keyword-only parameters. Whether surplus arguments are
+/// accepted is set with [`varargs`](Signature::varargs) and
+/// [`varkeywords`](Signature::varkeywords).
+#[derive(Clone, Debug)]
+pub struct Signature {
+    cls_name: Option<String>,
+    func_name: String,
+    positional_parameter_names: Vec<String>,
+    positional_only_parameters: usize,
+    required_positional_parameters: usize,
+    keyword_only_parameters: Vec<(String, bool)>,
+    varargs: bool,
+    varkeywords: bool,
+}
+
+impl Signature {
+    /// Creates the signature of a function named `func_name`, without any parameters.
+    pub fn new(func_name: impl Into<String>) -> Self {
+        Signature {
+            cls_name: None,
+            func_name: func_name.into(),
+            positional_parameter_names: Vec::new(),
+            positional_only_parameters: 0,
+            required_positional_parameters: 0,
+            keyword_only_parameters: Vec::new(),
+            varargs: false,
+            varkeywords: false,
+        }
+    }
+
+    /// Sets the name of the class the function is defined on, which is used in error messages.
+    pub fn class_name(mut self, cls_name: impl Into<String>) -> Self {
+        self.cls_name = Some(cls_name.into());
+        self
+    }
+
+    /// Adds a positional-only parameter, which must be provided if `required` is true.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a positional-or-keyword parameter was already added, or if a required parameter
+    /// follows an optional one.
+    pub fn positional_only(mut self, name: impl Into<String>, required: bool) -> Self {
+        assert_eq!(
+            self.positional_only_parameters,
+            self.positional_parameter_names.len(),
+            "positional-only parameters must come before other positional parameters"
+        );
+        self = self.positional(name, required);
+        self.positional_only_parameters += 1;
+        self
+    }
+
+    /// Adds a positional-or-keyword parameter, which must be provided if `required` is true.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a required parameter follows an optional one.
+    pub fn positional(mut self, name: impl Into<String>, required: bool) -> Self {
+        let name = name.into();
+        if required {
+            assert_eq!(
+                self.required_positional_parameters,
+                self.positional_parameter_names.len(),
+                "required parameter '{}' follows an optional parameter",
+                name
+            );
+            self.required_positional_parameters += 1;
+        }
+        self.positional_parameter_names.push(name);
+        self
+    }
+
+    /// Adds a keyword-only parameter, which must be provided if `required` is true.
+    pub fn keyword_only(mut self, name: impl Into<String>, required: bool) -> Self {
+        self.keyword_only_parameters.push((name.into(), required));
+        self
+    }
+
+    /// Collects surplus positional arguments, like `*args`, instead of rejecting them.
+    pub fn varargs(mut self) -> Self {
+        self.varargs = true;
+        self
+    }
+
+    /// Collects unknown keyword arguments, like `**kwargs`, instead of rejecting them.
+    pub fn varkeywords(mut self) -> Self {
+        self.varkeywords = true;
+        self
+    }
+
+    /// Returns the number of parameters, which is the length of the output of
+    /// [`extract_arguments`](Signature::extract_arguments).
+    pub fn len(&self) -> usize {
+        self.positional_parameter_names.len() + self.keyword_only_parameters.len()
+    }
+
+    /// Returns true if the signature has no parameters.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Sorts the `args` and `kwargs` of a Python call into `output`, according to this signature.
+    ///
+    /// `output` must have one entry for each positional parameter followed by one entry for each
+    /// keyword-only parameter. After a successful call, the entries of all required parameters
+    /// are `Some`, while the entries of optional parameters which were not provided are `None`.
+    ///
+    /// Returns the surplus positional arguments, which are empty unless the signature has
+    /// [`varargs`](Signature::varargs), and the unknown keyword arguments, which are `None` unless
+    /// the signature has [`varkeywords`](Signature::varkeywords).
+    ///
+    /// Unexpected, duplicate or missing arguments cause a `TypeError` with the same message as a
+    /// `#[pyfunction]` with this signature would raise.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `output` has the wrong length.
+    pub fn extract_arguments<'py>(
+        &self,
+        args: &'py PyTuple,
+        kwargs: Option<&'py PyDict>,
+        output: &mut [Option<&'py PyAny>],
+    ) -> PyResult<(&'py PyTuple, Option<&'py PyDict>)> {
+        self.with_description(|description| {
+            extract_with_handlers!(self, args.py(), |V, K| {
+                description.extract_arguments::<V, K>(args, kwargs, output)
+            })
+        })
+    }
+
+    /// Equivalent of [`extract_arguments`](Signature::extract_arguments) for arguments passed with
+    /// the Python "fastcall" convention, as received by a `METH_FASTCALL | METH_KEYWORDS` function
+    /// or a vectorcall.
+    ///
+    /// `args` holds the positional arguments followed by the values of the keyword arguments,
+    /// whose names are given by `kwnames`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `output` has the wrong length, or if `kwnames` is longer than `args`.
+    #[cfg(not(Py_LIMITED_API))]
+    pub fn extract_arguments_fastcall<'py>(
+        &self,
+        py: Python<'py>,
+        args: &[&'py PyAny],
+        kwnames: Option<&'py PyTuple>,
+        output: &mut [Option<&'py PyAny>],
+    ) -> PyResult<(&'py PyTuple, Option<&'py PyDict>)> {
+        let num_kwargs = kwnames.map_or(0, PyTuple::len);
+        assert!(
+            num_kwargs <= args.len(),
+            "expected at least {} arguments for {} keyword names, got {}",
+            num_kwargs,
+            num_kwargs,
+            args.len()
+        );
+        let nargs = (args.len() - num_kwargs) as ffi::Py_ssize_t;
+        let kwnames = kwnames.map_or(std::ptr::null_mut(), PyTuple::as_ptr);
+        self.with_description(|description| {
+            description.assert_output_len(output);
+            extract_with_handlers!(self, py, |V, K| {
+                // Safety: `&PyAny` has the same memory layout as `*mut ffi::PyObject`, and `args`
+                // holds the positional arguments followed by one value for each name in `kwnames`
+                unsafe {
+                    description.extract_arguments_fastcall::<V, K>(
+                        py,
+                        args.as_ptr().cast(),
+                        nargs,
+                        kwnames,
+                        output,
+                    )
+                }
+            })
+        })
+    }
+
+    /// Calls `f` with the description of this signature used by `#[pyfunction]`.
+    fn with_description<R>(&self, f: impl FnOnce(&FunctionDescription<'_>) -> R) -> R {
+        let positional_parameter_names: Vec<&str> = self
+            .positional_parameter_names
+            .iter()
+            .map(String::as_str)
+            .collect();
+        let keyword_only_parameters: Vec<KeywordOnlyParameterDescription<'_>> = self
+            .keyword_only_parameters
+            .iter()
+            .map(|(name, required)| KeywordOnlyParameterDescription {
+                name,
+                required: *required,
+            })
+            .collect();
+        f(&FunctionDescription {
+            cls_name: self.cls_name.as_deref(),
+            func_name: &self.func_name,
+            positional_parameter_names: &positional_parameter_names,
+            positional_only_parameters: self.positional_only_parameters,
+            required_positional_parameters: self.required_positional_parameters,
+            keyword_only_parameters: &keyword_only_parameters,
+        })
+    }
+}
+
+/// Extracts the argument for a required parameter.
+///
+/// The slots of required parameters are always filled by
+/// [`Signature::extract_arguments`], so `None` only occurs if the parameter is not
+/// actually required; it raises a `TypeError`.
+pub fn extract_required<'py, T>(arg: Option<&'py PyAny>, arg_name: &str) -> PyResult<T>
+where
+    T: FromPyObject<'py>,
+{
+    match arg {
+        Some(arg) => extract(arg, arg_name),
+        None => Err(PyTypeError::new_err(format!(
+            "missing required argument '{}'",
+            arg_name
+        ))),
+    }
+}
+
+/// Extracts the argument for an optional parameter, returning `None` if it was not provided or
+/// was `None`.
+pub fn extract_optional<'py, T>(arg: Option<&'py PyAny>, arg_name: &str) -> PyResult<Option<T>>
+where
+    T: FromPyObject<'py>,
+{
+    match arg {
+        Some(arg) if !arg.is_none() => extract(arg, arg_name).map(Some),
+        _ => Ok(None),
+    }
+}
+
+/// Extracts the argument for an optional parameter, calling `default` if it was not provided.
+pub fn extract_with_default<'py, T>(
+    arg: Option<&'py PyAny>,
+    arg_name: &str,
+    default: impl FnOnce() -> T,
+) -> PyResult<T>
+where
+    T: FromPyObject<'py>,
+{
+    match arg {
+        Some(arg) => extract(arg, arg_name),
+        None => Ok(default()),
+    }
+}
+
+fn extract<'py, T>(arg: &'py PyAny, arg_name: &str) -> PyResult<T>
+where
+    T: FromPyObject<'py>,
+{
+    arg.extract()
+        .map_err(|err| argument_extraction_error(arg.py(), arg_name, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{IntoPyDict, PyCFunction, PyDict, PyTuple};
+    use crate::{py_run, Python, ToPyObject};
+
+    fn signature() -> Signature {
+        Signature::new("method")
+            .class_name("Example")
+            .positional_only("a", true)
+            .positional("b", false)
+            .keyword_only("c", true)
+    }
+
+    #[test]
+    fn test_extract_arguments() {
+        Python::with_gil(|py| {
+            let signature = signature().varargs().varkeywords();
+            assert_eq!(signature.len(), 3);
+
+            let args = PyTuple::new(py, [1, 2, 3]);
+            let kwargs = [("c", 4), ("d", 5)].into_py_dict(py);
+            let mut output = [None; 3];
+            let (varargs, varkeywords) = signature
+                .extract_arguments(args, Some(kwargs), &mut output)
+                .unwrap();
+            let output: Vec<i32> = output
+                .iter()
+                .map(|arg| extract_required(*arg, "arg").unwrap())
+                .collect();
+            assert_eq!(output, [1, 2, 4]);
+            assert_eq!(varargs.extract::<Vec<i32>>().unwrap(), [3]);
+            assert_eq!(
+                varkeywords
+                    .unwrap()
+                    .get_item("d")
+                    .unwrap()
+                    .extract::<i32>()
+                    .unwrap(),
+                5
+            );
+
+            let args = PyTuple::new(py, [1]);
+            let kwargs = [("c", 4)].into_py_dict(py);
+            let mut output = [None; 3];
+            let (varargs, varkeywords) = signature
+                .extract_arguments(args, Some(kwargs), &mut output)
+                .unwrap();
+            assert!(output[1].is_none());
+            assert!(varargs.is_empty());
+            assert!(varkeywords.is_none());
+        })
+    }
+
+    #[test]
+    fn test_extract_arguments_errors() {
+        Python::with_gil(|py| {
+            let signature = signature();
+            let check = |args: &PyTuple, kwargs: Option<&PyDict>, message: &str| {
+                let mut output = [None; 3];
+                let err = signature
+                    .extract_arguments(args, kwargs, &mut output)
+                    .unwrap_err();
+                assert_eq!(err.to_string(), message);
+            };
+            let kwargs = [("c", 3)].into_py_dict(py);
+
+            check(
+                PyTuple::new(py, [1, 2, 3]),
+                Some(kwargs),
+                "TypeError: Example.method() takes from 1 to 2 positional arguments but 3 were given",
+            );
+            check(
+                PyTuple::new(py, [1]),
+                None,
+                "TypeError: Example.method() missing 1 required keyword argument: 'c'",
+            );
+            check(
+                PyTuple::empty(py),
+                Some(kwargs),
+                "TypeError: Example.method() missing 1 required positional argument: 'a'",
+            );
+            check(
+                PyTuple::new(py, [1, 2]),
+                Some([("c", 3), ("b", 2)].into_py_dict(py)),
+                "TypeError: Example.method() got multiple values for argument 'b'",
+            );
+            check(
+                PyTuple::new(py, [1]),
+                Some([("c", 3), ("d", 4)].into_py_dict(py)),
+                "TypeError: Example.method() got an unexpected keyword argument 'd'",
+            );
+        })
+    }
+
+    #[test]
+    #[cfg(not(Py_LIMITED_API))]
+    fn test_extract_arguments_fastcall() {
+        Python::with_gil(|py| {
+            let all_args = signature().varargs().varkeywords();
+            let args: Vec<&PyAny> = [1, 2, 3, 4, 5]
+                .iter()
+                .map(|value| value.to_object(py).into_ref(py))
+                .collect();
+            let kwnames = PyTuple::new(py, ["c", "d"]);
+            let mut output = [None; 3];
+            let (varargs, varkeywords) = all_args
+                .extract_arguments_fastcall(py, &args, Some(kwnames), &mut output)
+                .unwrap();
+            let output: Vec<i32> = output
+                .iter()
+                .map(|arg| extract_required(*arg, "arg").unwrap())
+                .collect();
+            assert_eq!(output, [1, 2, 4]);
+            assert_eq!(varargs.extract::<Vec<i32>>().unwrap(), [3]);
+            assert_eq!(
+                varkeywords
+                    .unwrap()
+                    .get_item("d")
+                    .unwrap()
+                    .extract::<i32>()
+                    .unwrap(),
+                5
+            );
+
+            let mut output = [None; 3];
+            let err = signature()
+                .extract_arguments_fastcall(py, &args[..1], None, &mut output)
+                .unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "TypeError: Example.method() missing 1 required keyword argument: 'c'"
+            );
+        })
+    }
+
+    #[test]
+    #[should_panic(expected = "Example.method() has 3 parameters, but the output has length 2")]
+    fn test_extract_arguments_wrong_output_len() {
+        Python::with_gil(|py| {
+            let mut output = [None; 2];
+            let _ = signature().extract_arguments(PyTuple::empty(py), None, &mut output);
+        })
+    }
+
+    #[test]
+    #[should_panic(expected = "required parameter 'b' follows an optional parameter")]
+    fn test_required_after_optional() {
+        let _ = Signature::new("f")
+            .positional("a", false)
+            .positional("b", true);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "positional-only parameters must come before other positional parameters"
+    )]
+    fn test_positional_only_after_positional() {
+        let _ = Signature::new("f")
+            .positional("a", true)
+            .positional_only("b", true);
+    }
+
+    #[test]
+    fn test_extract_values() {
+        Python::with_gil(|py| {
+            let value = 1.to_object(py).into_ref(py);
+            let none = py.None().into_ref(py);
+
+            assert_eq!(extract_required::<i32>(Some(value), "x").unwrap(), 1);
+            assert_eq!(
+                extract_required::<i32>(None, "x").unwrap_err().to_string(),
+                "TypeError: missing required argument 'x'"
+            );
+            assert_eq!(
+                extract_required::<String>(Some(value), "x")
+                    .unwrap_err()
+                    .to_string(),
+                "TypeError: argument 'x': 'int' object cannot be converted to 'PyString'"
+            );
+
+            assert_eq!(extract_optional::<i32>(Some(value), "x").unwrap(), Some(1));
+            assert_eq!(extract_optional::<i32>(Some(none), "x").unwrap(), None);
+            assert_eq!(extract_optional::<i32>(None, "x").unwrap(), None);
+
+            assert_eq!(extract_with_default(Some(value), "x", || 2).unwrap(), 1);
+            assert_eq!(extract_with_default(None, "x", || 2).unwrap(), 2);
+        })
+    }
+
+    #[test]
+    fn test_closure_with_signature() {
+        // Names only known at runtime
+        let sep = String::from("sep");
+        let signature = Signature::new("join").varargs().keyword_only(sep, false);
+
+        Python::with_gil(|py| {
+            let join = PyCFunction::new_closure(py, Some("join"), None, move |args, kwargs| {
+                let mut output = [None];
+                let (items, _) = signature.extract_arguments(args, kwargs, &mut output)?;
+                let sep: String = extract_with_default(output[0], "sep", || " ".to_owned())?;
+                let items: Vec<String> = items.extract()?;
+                PyResult::Ok(items.join(&sep))
+            })
+            .unwrap();
+            py_run!(
+                py,
+                join,
+                r#"
+assert join("a", "b") == "a b"
+assert join("a", "b", sep="-") == "a-b"
+try:
+    join("a", separator="-")
+except TypeError as e:
+    assert str(e) == "join() got an unexpected keyword argument 'separator'"
+else:
+    assert False
+"#
+            );
+        })
+    }
+}
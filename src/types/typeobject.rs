@@ -7,6 +7,10 @@ pub struct PyType(PyAny);
 
 pyobject_native_type_core!(PyType, pyobject_native_static_type_object!(ffi::PyType_Type), #checkfunction=ffi::PyType_Check);
 
+// Classes extending `type` are metaclasses, whose instances are heap types.
+#[cfg(not(Py_LIMITED_API))]
+pyobject_native_type_sized!(PyType, ffi::PyHeapTypeObject);
+
 impl PyType {
     /// Creates a new type object.
     #[inline]
@@ -702,3 +702,140 @@ mod return_not_implemented {
         _test_inplace_binary_operator("**=", "ipow");
     }
 }
+
+#[pyclass(ops(add, sub, mul(Self, f64), div(f64), neg, add_assign, mul_assign(f64)))]
+#[derive(Clone, Debug, PartialEq)]
+struct Vector {
+    #[pyo3(get)]
+    x: f64,
+    #[pyo3(get)]
+    y: f64,
+}
+
+#[pymethods]
+impl Vector {
+    #[new]
+    fn new(x: f64, y: f64) -> Self {
+        Vector { x, y }
+    }
+
+    fn __rsub__(&self, other: (f64, f64)) -> Self {
+        Vector::new(other.0 - self.x, other.1 - self.y)
+    }
+}
+
+impl std::ops::Add for Vector {
+    type Output = Vector;
+    fn add(self, other: Vector) -> Vector {
+        Vector::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl std::ops::Sub for Vector {
+    type Output = Vector;
+    fn sub(self, other: Vector) -> Vector {
+        Vector::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+/// The dot product.
+impl std::ops::Mul for Vector {
+    type Output = f64;
+    fn mul(self, other: Vector) -> f64 {
+        self.x * other.x + self.y * other.y
+    }
+}
+
+impl std::ops::Mul<f64> for Vector {
+    type Output = Vector;
+    fn mul(self, factor: f64) -> Vector {
+        Vector::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl std::ops::Mul<Vector> for f64 {
+    type Output = Vector;
+    fn mul(self, vector: Vector) -> Vector {
+        vector * self
+    }
+}
+
+impl std::ops::Div<f64> for Vector {
+    type Output = Vector;
+    fn div(self, divisor: f64) -> Vector {
+        Vector::new(self.x / divisor, self.y / divisor)
+    }
+}
+
+impl std::ops::Neg for Vector {
+    type Output = Vector;
+    fn neg(self) -> Vector {
+        Vector::new(-self.x, -self.y)
+    }
+}
+
+impl std::ops::AddAssign for Vector {
+    fn add_assign(&mut self, other: Vector) {
+        self.x += other.x;
+        self.y += other.y;
+    }
+}
+
+impl std::ops::MulAssign<f64> for Vector {
+    fn mul_assign(&mut self, factor: f64) {
+        self.x *= factor;
+        self.y *= factor;
+    }
+}
+
+#[test]
+fn generated_ops() {
+    Python::with_gil(|py| {
+        let vector = py.get_type::<Vector>();
+        py_run!(
+            py,
+            vector,
+            r#"
+def xy(v):
+    return (v.x, v.y)
+
+a = vector(1, 2)
+b = vector(3, 5)
+assert xy(a + b) == (4, 7)
+assert xy(b - a) == (2, 3)
+assert a * b == 13
+assert xy(a * 2) == (2, 4)
+assert xy(2 * a) == (2, 4)
+assert xy(b / 2) == (1.5, 2.5)
+assert xy(-a) == (-1, -2)
+assert xy(a) == (1, 2)
+
+# `__rsub__` from `#[pymethods]` shares the slot with the generated `__sub__`
+assert xy((10, 10) - a) == (9, 8)
+
+c = a
+c += b
+assert c is a and xy(a) == (4, 7)
+c *= 0.5
+assert c is a and xy(a) == (2, 3.5)
+
+# other operand types get `NotImplemented`, so Python raises `TypeError`
+assert a.__add__("x") is NotImplemented
+assert a.__rtruediv__(2) is NotImplemented
+import operator
+for op, lhs, rhs in [(operator.add, a, 1), (operator.add, 1, a), (operator.truediv, 2, a), (operator.truediv, a, a)]:
+    try:
+        op(lhs, rhs)
+    except TypeError:
+        pass
+    else:
+        assert False
+
+# an in-place operator that can't take the operand falls back to the binary operator
+d = a
+d *= a
+assert d == 2 * 2 + 3.5 * 3.5 and xy(a) == (2, 3.5)
+"#
+        );
+    });
+}
@@ -590,3 +590,122 @@ fn drop_unsendable_elsewhere() {
         capture.borrow_mut(py).uninstall(py);
     });
 }
+
+#[pyclass(eq, ord, hash, frozen)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Version {
+    major: u32,
+    minor: u32,
+}
+
+#[pyclass(eq)]
+#[derive(PartialEq)]
+struct Point {
+    x: f64,
+    y: f64,
+}
+
+#[test]
+fn eq_ord_hash_from_rust_impls() {
+    Python::with_gil(|py| {
+        let v1_2 = Py::new(py, Version { major: 1, minor: 2 }).unwrap();
+        let v1_2_again = Py::new(py, Version { major: 1, minor: 2 }).unwrap();
+        let v2_0 = Py::new(py, Version { major: 2, minor: 0 }).unwrap();
+        py_run!(
+            py,
+            v1_2 v1_2_again v2_0,
+            r#"
+assert v1_2 == v1_2_again
+assert v1_2 != v2_0
+assert v1_2 < v2_0 and v1_2 <= v2_0 and v1_2 <= v1_2_again
+assert v2_0 > v1_2 and v2_0 >= v1_2
+assert sorted([v2_0, v1_2]) == [v1_2, v2_0]
+assert hash(v1_2) == hash(v1_2_again)
+assert len({v1_2, v1_2_again, v2_0}) == 2
+
+assert v1_2 != (1, 2)
+try:
+    v1_2 < (1, 2)
+except TypeError:
+    pass
+else:
+    assert False
+"#
+        );
+
+        let origin = Py::new(py, Point { x: 0.0, y: 0.0 }).unwrap();
+        let nan = Py::new(py, Point { x: f64::NAN, y: 0.0 }).unwrap();
+        py_run!(
+            py,
+            origin nan,
+            r#"
+assert origin == origin
+assert nan != nan
+assert origin != 0
+try:
+    origin < origin
+except TypeError:
+    pass
+else:
+    assert False
+try:
+    hash(origin)
+except TypeError:
+    pass
+else:
+    assert False
+"#
+        );
+    });
+}
+
+#[pyclass(str, repr = "Coordinate({x}, {y:.1}) {{{name:?}}}")]
+struct Coordinate {
+    x: i32,
+    y: f64,
+    name: String,
+}
+
+impl std::fmt::Display for Coordinate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at ({}, {})", self.name, self.x, self.y)
+    }
+}
+
+#[pyclass(repr = "Pair({0}, {1}, {0})")]
+struct Pair(i32, &'static str);
+
+#[pyclass(repr)]
+#[derive(Debug)]
+struct Debugged {
+    #[allow(dead_code)]
+    values: Vec<u8>,
+}
+
+#[test]
+fn str_repr_from_rust_impls() {
+    Python::with_gil(|py| {
+        let coordinate = Py::new(
+            py,
+            Coordinate {
+                x: 1,
+                y: 2.25,
+                name: "home".to_owned(),
+            },
+        )
+        .unwrap();
+        let pair = Py::new(py, Pair(1, "a")).unwrap();
+        let debugged = Py::new(py, Debugged { values: vec![1, 2] }).unwrap();
+        py_run!(
+            py,
+            coordinate pair debugged,
+            r#"
+assert str(coordinate) == "home at (1, 2.25)"
+assert repr(coordinate) == 'Coordinate(1, 2.2) {"home"}'
+assert repr(pair) == "Pair(1, a, 1)"
+assert str(pair) == repr(pair)
+assert repr(debugged) == "Debugged { values: [1, 2] }"
+"#
+        );
+    });
+}
@@ -0,0 +1,226 @@
+#![cfg(feature = "macros")]
+
+use pyo3::exceptions::{PyException, PyIndexError, PyTypeError};
+use pyo3::prelude::*;
+use pyo3::py_run;
+use pyo3::pyclass::PyClassBuilder;
+use pyo3::types::{PyTuple, PyType};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+mod common;
+
+struct Point {
+    x: f64,
+    y: f64,
+}
+
+fn point_class(py: Python<'_>) -> &PyType {
+    PyClassBuilder::new("Point")
+        .module("geometry")
+        .doc("A point in the plane.")
+        .constructor(|args, kwargs| {
+            let (x, y): (f64, f64) = args.extract()?;
+            let scale: f64 = match kwargs.and_then(|kwargs| kwargs.get_item("scale")) {
+                Some(scale) => scale.extract()?,
+                None => 1.0,
+            };
+            Ok(Point {
+                x: x * scale,
+                y: y * scale,
+            })
+        })
+        .method(
+            "norm",
+            Some("Returns the distance from the origin."),
+            |slf, _, _| {
+                let point = PyClassBuilder::borrow_data::<Point>(slf)?;
+                Ok(point.x.hypot(point.y))
+            },
+        )
+        .method("__repr__", None, |slf, _, _| {
+            let point = PyClassBuilder::borrow_data::<Point>(slf)?;
+            Ok(format!("Point({}, {})", point.x, point.y))
+        })
+        .classmethod("origin", None, |cls, _, _| {
+            cls.call1((0.0, 0.0)).map(PyObject::from)
+        })
+        .staticmethod("dimensions", None, |_, _| Ok(2))
+        .getter("x", Some("The x coordinate."), |slf| {
+            Ok(PyClassBuilder::borrow_data::<Point>(slf)?.x)
+        })
+        .setter("x", |slf, value| {
+            PyClassBuilder::borrow_data_mut::<Point>(slf)?.x = value.extract()?;
+            Ok(())
+        })
+        .getter("y", None, |slf| {
+            Ok(PyClassBuilder::borrow_data::<Point>(slf)?.y)
+        })
+        .build(py)
+        .unwrap()
+}
+
+#[test]
+fn test_builder_class() {
+    Python::with_gil(|py| {
+        let point = point_class(py);
+        py_assert!(py, point, "point.__name__ == 'Point'");
+        py_assert!(py, point, "point.__module__ == 'geometry'");
+        py_assert!(py, point, "point.__doc__ == 'A point in the plane.'");
+
+        py_assert!(py, point, "point(3, 4).norm() == 5.0");
+        py_assert!(py, point, "point.norm(point(3, 4)) == 5.0");
+        py_assert!(py, point, "point(1, 2, scale=2).y == 4.0");
+        py_assert!(py, point, "repr(point(1, 2.5)) == 'Point(1, 2.5)'");
+        py_assert!(py, point, "point.origin().norm() == 0.0");
+        py_assert!(py, point, "point(1, 2).origin().x == 0.0");
+        py_assert!(
+            py,
+            point,
+            "point.dimensions() == point(1, 2).dimensions() == 2"
+        );
+
+        py_assert!(py, point, "point.norm.__name__ == 'norm'");
+        py_assert!(py, point, "point.norm.__qualname__ == 'Point.norm'");
+        py_assert!(
+            py,
+            point,
+            "point.norm.__doc__ == 'Returns the distance from the origin.'"
+        );
+        py_assert!(py, point, "point.x.__doc__ == 'The x coordinate.'");
+
+        let p = point.call1((1.0, 2.0)).unwrap();
+        py_run!(py, p, "p.x = 5\nassert p.x == 5.0");
+        assert_eq!(PyClassBuilder::borrow_data::<Point>(p).unwrap().x, 5.0);
+        py_expect_exception!(py, p, "p.y = 5", PyAttributeError);
+        py_expect_exception!(py, point, "point('a', 'b')", PyTypeError);
+        py_expect_exception!(py, point, "point.norm()", PyTypeError);
+    });
+}
+
+#[test]
+fn test_builder_subclass() {
+    Python::with_gil(|py| {
+        let point = point_class(py);
+        py_run!(
+            py,
+            point,
+            r#"
+class Point3D(point):
+    def __new__(cls, x, y, z):
+        self = super().__new__(cls, x, y)
+        self.z = z
+        return self
+
+    def norm(self):
+        return (super().norm() ** 2 + self.z ** 2) ** 0.5
+
+p = Point3D(2, 3, 6)
+assert p.norm() == 7.0
+assert isinstance(p, point)
+"#
+        );
+    });
+}
+
+#[test]
+fn test_builder_slots() {
+    Python::with_gil(|py| {
+        let sequence = PyClassBuilder::new("Sequence")
+            .constructor(|args, _| args.extract::<(Vec<i64>,)>().map(|(items,)| items))
+            .method("__len__", None, |slf, _, _| {
+                Ok(PyClassBuilder::borrow_data::<Vec<i64>>(slf)?.len())
+            })
+            .method("__getitem__", None, |slf, args, _| {
+                let (index,): (usize,) = args.extract()?;
+                PyClassBuilder::borrow_data::<Vec<i64>>(slf)?
+                    .get(index)
+                    .copied()
+                    .ok_or_else(|| PyIndexError::new_err("index out of range"))
+            })
+            .build(py)
+            .unwrap();
+
+        py_assert!(py, sequence, "len(sequence([1, 2, 3])) == 3");
+        py_assert!(py, sequence, "sequence([1, 2, 3])[1] == 2");
+        py_assert!(py, sequence, "list(sequence([1, 2, 3])) == [1, 2, 3]");
+    });
+}
+
+#[test]
+fn test_builder_exception_base() {
+    Python::with_gil(|py| {
+        let error = PyClassBuilder::new("ParseError")
+            .base(py.get_type::<PyException>())
+            .constructor(|args, _| args.extract::<(usize,)>().map(|(line,)| line))
+            .getter("line", None, |slf| {
+                Ok(*PyClassBuilder::borrow_data::<usize>(slf)?)
+            })
+            .build(py)
+            .unwrap();
+
+        py_run!(
+            py,
+            error,
+            r#"
+try:
+    raise error(12)
+except Exception as e:
+    assert e.line == 12
+    e.extra = "attributes are allowed"
+"#
+        );
+    });
+}
+
+#[test]
+fn test_builder_data_errors() {
+    Python::with_gil(|py| {
+        let point = point_class(py);
+        let p = point.call1((1.0, 2.0)).unwrap();
+
+        let err = PyClassBuilder::borrow_data::<String>(p).err().unwrap();
+        assert!(err.is_instance_of::<PyTypeError>(py));
+
+        let _borrowed = PyClassBuilder::borrow_data_mut::<Point>(p).unwrap();
+        assert!(PyClassBuilder::borrow_data::<Point>(p).is_err());
+
+        let tuple = PyTuple::empty(py);
+        let err = PyClassBuilder::borrow_data::<Point>(tuple).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "TypeError: 'tuple' object was not created by a PyClassBuilder class"
+        );
+
+        let err = PyClassBuilder::new("Sub")
+            .base(point)
+            .build(py)
+            .unwrap_err();
+        assert!(err.is_instance_of::<PyTypeError>(py));
+
+        let no_constructor = PyClassBuilder::new("NoConstructor").build(py).unwrap();
+        py_expect_exception!(py, no_constructor, "no_constructor()", PyTypeError);
+    });
+}
+
+#[test]
+fn test_builder_drops_data() {
+    struct Tracked(Arc<AtomicUsize>);
+
+    impl Drop for Tracked {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    let drops = Arc::new(AtomicUsize::new(0));
+    Python::with_gil(|py| {
+        let drops = drops.clone();
+        let tracked = PyClassBuilder::new("Tracked")
+            .constructor(move |_, _| Ok(Tracked(drops.clone())))
+            .build(py)
+            .unwrap();
+        py_run!(py, tracked, "t = tracked(); del t");
+    });
+    assert_eq!(drops.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,93 @@
+#![cfg(feature = "macros")]
+
+use pyo3::prelude::*;
+use pyo3::py_run;
+use pyo3::types::PyType;
+
+mod common;
+
+#[pyclass(generic)]
+struct MyVec {
+    items: Vec<PyObject>,
+}
+
+#[pymethods]
+impl MyVec {
+    #[new]
+    fn new() -> Self {
+        MyVec { items: Vec::new() }
+    }
+
+    fn __len__(&self) -> usize {
+        self.items.len()
+    }
+}
+
+#[test]
+fn test_generic() {
+    Python::with_gil(|py| {
+        let my_vec = py.get_type::<MyVec>();
+        if py.version_info() >= (3, 9) {
+            py_run!(
+                py,
+                my_vec,
+                r#"
+import types, typing
+alias = my_vec[int]
+assert isinstance(alias, types.GenericAlias)
+assert typing.get_origin(alias) is my_vec
+assert typing.get_args(alias) == (int,)
+assert typing.get_args(my_vec[str, int]) == (str, int)
+assert repr(alias) == "MyVec[int]"
+assert len(alias()) == 0
+# the class has no type variables, so any arguments are accepted
+assert not hasattr(my_vec, "__parameters__")
+assert alias.__parameters__ == ()
+"#
+            );
+        } else {
+            py_run!(py, my_vec, "assert my_vec[int] is my_vec");
+        }
+    });
+}
+
+#[pyclass]
+struct Registry;
+
+#[pymethods]
+impl Registry {
+    #[classmethod]
+    fn __class_getitem__(cls: &PyType, key: &str) -> PyResult<String> {
+        Ok(format!("{}[{}]", cls.name()?, key))
+    }
+}
+
+#[test]
+fn test_class_getitem_method() {
+    Python::with_gil(|py| {
+        let registry = py.get_type::<Registry>();
+        py_run!(
+            py,
+            registry,
+            "assert registry['users'] == 'Registry[users]'"
+        );
+    });
+}
+
+#[cfg(feature = "experimental-inspect")]
+#[test]
+fn test_generic_type_output() {
+    use pyo3::inspect::types::{ModuleName, TypeInfo};
+
+    let type_output = <MyVec as IntoPy<PyObject>>::type_output();
+    assert_eq!(
+        type_output,
+        TypeInfo::Class {
+            module: ModuleName::CurrentModule,
+            name: "MyVec".into(),
+            type_vars: vec![TypeInfo::Any],
+        }
+    );
+    assert_eq!(type_output.to_string(), "MyVec[Any]");
+    assert_eq!(<Registry as IntoPy<PyObject>>::type_output(), TypeInfo::Any);
+}
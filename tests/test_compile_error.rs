@@ -9,6 +9,20 @@ fn test_compile_errors() {
     t.compile_fail("tests/ui/invalid_property_args.rs");
     t.compile_fail("tests/ui/invalid_proto_pymethods.rs");
     t.compile_fail("tests/ui/invalid_pyclass_args.rs");
+    t.compile_fail("tests/ui/invalid_pyclass_clone.rs");
+    t.compile_fail("tests/ui/invalid_pyclass_comparison.rs");
+    t.compile_fail("tests/ui/invalid_pyclass_context_manager.rs");
+    t.compile_fail("tests/ui/invalid_pyclass_dataclass.rs");
+    t.compile_fail("tests/ui/invalid_pyclass_dataclass_bounds.rs");
+    t.compile_fail("tests/ui/invalid_pyclass_gc.rs");
+    t.compile_fail("tests/ui/invalid_pyclass_generic.rs");
+    #[cfg(not(Py_3_12))]
+    t.compile_fail("tests/ui/invalid_pyclass_metaclass.rs");
+    t.compile_fail("tests/ui/invalid_pyclass_ops.rs");
+    t.compile_fail("tests/ui/invalid_pyclass_pickle.rs");
+    #[cfg(not(all(feature = "serde", feature = "serde_json")))]
+    t.compile_fail("tests/ui/invalid_pyclass_pickle_serde.rs");
+    t.compile_fail("tests/ui/invalid_pyclass_str_repr.rs");
     t.compile_fail("tests/ui/invalid_pyclass_enum.rs");
     t.compile_fail("tests/ui/invalid_pyclass_item.rs");
     t.compile_fail("tests/ui/invalid_pyfunction_signatures.rs");
@@ -22,10 +36,14 @@ fn test_compile_errors() {
     t.compile_fail("tests/ui/pyclass_send.rs");
     t.compile_fail("tests/ui/invalid_argument_attributes.rs");
     t.compile_fail("tests/ui/invalid_frompy_derive.rs");
+    t.compile_fail("tests/ui/invalid_pyexception_derive.rs");
+    t.compile_fail("tests/ui/invalid_pytrait.rs");
     t.compile_fail("tests/ui/static_ref.rs");
     t.compile_fail("tests/ui/wrong_aspyref_lifetimes.rs");
     t.compile_fail("tests/ui/invalid_pyfunctions.rs");
     t.compile_fail("tests/ui/invalid_pymethods.rs");
+    t.compile_fail("tests/ui/invalid_pymethods_groups.rs");
+    t.compile_fail("tests/ui/invalid_trait_pymethods.rs");
     #[cfg(Py_LIMITED_API)]
     t.compile_fail("tests/ui/abi3_nativetype_inheritance.rs");
     t.compile_fail("tests/ui/invalid_intern_arg.rs");
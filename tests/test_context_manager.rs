@@ -0,0 +1,203 @@
+#![cfg(feature = "macros")]
+
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::py_run;
+use pyo3::pyclass::{BindSelf, PyAsyncContextManager, PyContextManager};
+
+mod common;
+
+#[pyclass(context_manager)]
+struct Transaction {
+    #[pyo3(get)]
+    log: Vec<String>,
+    pending: Option<Vec<String>>,
+}
+
+#[pymethods]
+impl Transaction {
+    fn write(&mut self, entry: String) -> PyResult<()> {
+        match &mut self.pending {
+            Some(pending) => {
+                pending.push(entry);
+                Ok(())
+            }
+            None => Err(PyRuntimeError::new_err("no transaction in progress")),
+        }
+    }
+}
+
+impl PyContextManager for Transaction {
+    type Target = usize;
+
+    fn enter(&mut self) -> PyResult<usize> {
+        if self.pending.is_some() {
+            return Err(PyRuntimeError::new_err(
+                "a transaction is already in progress",
+            ));
+        }
+        self.pending = Some(Vec::new());
+        Ok(self.log.len())
+    }
+
+    fn exit(&mut self, error: Option<PyErr>) -> PyResult<bool> {
+        let pending = self.pending.take().unwrap_or_default();
+        Python::with_gil(|py| match error {
+            None => {
+                self.log.extend(pending);
+                Ok(false)
+            }
+            // a rollback request is handled by the transaction, other errors propagate
+            Some(error) => Ok(error.is_instance_of::<PyValueError>(py)),
+        })
+    }
+}
+
+#[test]
+fn test_context_manager() {
+    Python::with_gil(|py| {
+        let transaction = Py::new(
+            py,
+            Transaction {
+                log: Vec::new(),
+                pending: None,
+            },
+        )
+        .unwrap();
+        py_run!(
+            py,
+            transaction,
+            r#"
+with transaction as start:
+    assert start == 0
+    transaction.write("a")
+    transaction.write("b")
+assert transaction.log == ["a", "b"]
+
+with transaction as start:
+    assert start == 2
+    transaction.write("c")
+    raise ValueError("rollback")
+assert transaction.log == ["a", "b"]
+
+try:
+    with transaction:
+        transaction.write("d")
+        raise KeyError("d")
+except KeyError:
+    pass
+else:
+    assert False
+assert transaction.log == ["a", "b"]
+
+try:
+    transaction.write("e")
+except RuntimeError as e:
+    assert str(e) == "no transaction in progress"
+else:
+    assert False
+
+assert transaction.__exit__(None, None, None) is False
+"#
+        );
+    });
+}
+
+#[pyclass(context_manager)]
+struct Resource {
+    #[pyo3(get)]
+    open: bool,
+}
+
+impl PyContextManager for Resource {
+    type Target = BindSelf;
+
+    fn enter(&mut self) -> PyResult<BindSelf> {
+        self.open = true;
+        Ok(BindSelf)
+    }
+
+    fn exit(&mut self, _error: Option<PyErr>) -> PyResult<bool> {
+        self.open = false;
+        Ok(false)
+    }
+}
+
+#[test]
+fn test_context_manager_bind_self() {
+    Python::with_gil(|py| {
+        let resource = Py::new(py, Resource { open: false }).unwrap();
+        py_run!(
+            py,
+            resource,
+            r#"
+with resource as r:
+    assert r is resource
+    assert r.open
+assert not resource.open
+"#
+        );
+    });
+}
+
+#[pyclass(context_manager(async))]
+struct Connection {
+    #[pyo3(get)]
+    open: bool,
+    #[pyo3(get)]
+    error: Option<String>,
+}
+
+/// Returns an awaitable resolving to `value`.
+fn ready(py: Python<'_>, value: PyObject) -> PyResult<PyObject> {
+    Ok(py
+        .import("asyncio")?
+        .call_method1("sleep", (0, value))?
+        .into())
+}
+
+impl PyAsyncContextManager for Connection {
+    fn aenter(&mut self, py: Python<'_>) -> PyResult<PyObject> {
+        self.open = true;
+        ready(py, "connected".into_py(py))
+    }
+
+    fn aexit(&mut self, py: Python<'_>, error: Option<PyErr>) -> PyResult<PyObject> {
+        self.open = false;
+        self.error = error.map(|error| error.value(py).to_string());
+        ready(py, true.into_py(py))
+    }
+}
+
+#[test]
+fn test_async_context_manager() {
+    Python::with_gil(|py| {
+        let connection = Py::new(
+            py,
+            Connection {
+                open: false,
+                error: None,
+            },
+        )
+        .unwrap();
+        py_run!(
+            py,
+            connection,
+            r#"
+import asyncio
+
+async def main(connection):
+    async with connection as status:
+        assert status == "connected"
+        assert connection.open
+    assert not connection.open and connection.error is None
+
+    async with connection:
+        raise ValueError("lost")
+    assert connection.error == "lost"
+
+asyncio.run(main(connection))
+"#
+        );
+    });
+}
@@ -0,0 +1,206 @@
+#![cfg(feature = "macros")]
+
+use pyo3::prelude::*;
+use pyo3::py_run;
+use pyo3::types::PyList;
+
+mod common;
+
+#[pyclass(clone)]
+#[derive(Clone)]
+struct Leaf {
+    #[pyo3(get, set)]
+    value: i32,
+}
+
+#[pyclass(clone)]
+#[derive(Clone)]
+struct Tree {
+    #[pyo3(get)]
+    name: String,
+    #[pyo3(get)]
+    left: Py<Leaf>,
+    #[pyo3(get)]
+    right: Option<Py<Leaf>>,
+    #[pyo3(get)]
+    children: Vec<Py<Leaf>>,
+    #[pyo3(get)]
+    data: PyObject,
+}
+
+#[pyclass(clone, frozen)]
+#[derive(Clone)]
+struct Pair(#[pyo3(get, name = "first")] Py<Leaf>, Py<Leaf>);
+
+#[pyclass(clone)]
+#[derive(Clone)]
+struct Node {
+    #[pyo3(get, set)]
+    next: PyObject,
+}
+
+/// Settings which hold no Python object, so they can be cloned instead of deep copied.
+#[derive(Clone)]
+struct Settings {
+    verbose: bool,
+}
+
+#[pyclass(clone, frozen, subclass)]
+#[derive(Clone)]
+struct Config {
+    #[pyo3(get)]
+    items: Py<PyList>,
+    #[pyo3(deepcopy_skip)]
+    settings: Settings,
+}
+
+#[pymethods]
+impl Config {
+    #[new]
+    fn new(py: Python<'_>) -> Self {
+        Config {
+            items: PyList::empty(py).into(),
+            settings: Settings { verbose: true },
+        }
+    }
+
+    #[getter]
+    fn verbose(&self) -> bool {
+        self.settings.verbose
+    }
+}
+
+#[pyclass(clone)]
+#[derive(Clone)]
+enum Mode {
+    Fast,
+    Slow,
+}
+
+#[test]
+fn test_copy() {
+    Python::with_gil(|py| {
+        let leaf = Py::new(py, Leaf { value: 1 }).unwrap();
+        let tree = Py::new(
+            py,
+            Tree {
+                name: "root".to_owned(),
+                left: leaf.clone_ref(py),
+                right: Some(leaf.clone_ref(py)),
+                children: vec![leaf],
+                data: py.eval("[1, 2]", None, None).unwrap().into(),
+            },
+        )
+        .unwrap();
+        py_run!(
+            py,
+            tree,
+            r#"
+import copy
+shallow = copy.copy(tree)
+assert shallow is not tree
+assert shallow.name == "root"
+assert shallow.left is tree.left
+assert shallow.data is tree.data
+
+deep = copy.deepcopy(tree)
+assert deep.left is not tree.left
+assert deep.left.value == 1
+# the leaf shared by the fields is copied once
+assert deep.right is deep.left
+assert deep.children[0] is deep.left
+assert deep.data is not tree.data and deep.data == [1, 2]
+
+tree.left.value = 2
+assert deep.left.value == 1
+"#
+        );
+    });
+}
+
+#[test]
+fn test_deepcopy_memo() {
+    Python::with_gil(|py| {
+        let leaf = Py::new(py, Leaf { value: 1 }).unwrap();
+        let pair = Py::new(py, Pair(leaf.clone_ref(py), leaf.clone_ref(py))).unwrap();
+        py_run!(
+            py,
+            leaf pair,
+            r#"
+import copy
+leaf_copy, pair_copy = copy.deepcopy([leaf, pair])
+assert leaf_copy is not leaf
+assert pair_copy.first is leaf_copy
+"#
+        );
+    });
+}
+
+#[test]
+fn test_deepcopy_self_reference() {
+    Python::with_gil(|py| {
+        let node = Py::new(py, Node { next: py.None() }).unwrap();
+        py_run!(
+            py,
+            node,
+            r#"
+import copy
+node.next = [node]
+deep = copy.deepcopy(node)
+assert deep is not node
+assert deep.next is not node.next
+assert deep.next[0] is deep
+"#
+        );
+    });
+}
+
+#[test]
+fn test_copy_subclass() {
+    Python::with_gil(|py| {
+        let config = py.get_type::<Config>();
+        py_run!(
+            py,
+            config,
+            r#"
+import copy
+
+class SubConfig(config):
+    pass
+
+sub = SubConfig()
+sub.items.append([1])
+sub.label = ["a"]
+
+shallow = copy.copy(sub)
+assert type(shallow) is SubConfig
+assert shallow.items is sub.items
+assert shallow.label is sub.label
+assert shallow.verbose
+
+deep = copy.deepcopy(sub)
+assert type(deep) is SubConfig
+assert deep.items == [[1]] and deep.items is not sub.items
+assert deep.items[0] is not sub.items[0]
+assert deep.label == ["a"] and deep.label is not sub.label
+assert deep.verbose
+"#
+        );
+    });
+}
+
+#[test]
+fn test_copy_enum() {
+    Python::with_gil(|py| {
+        let mode = py.get_type::<Mode>();
+        py_run!(
+            py,
+            mode,
+            r#"
+import copy
+assert copy.copy(mode.Fast) == mode.Fast
+assert copy.deepcopy(mode.Slow) == mode.Slow
+"#
+        );
+    });
+}
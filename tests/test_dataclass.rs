@@ -0,0 +1,162 @@
+#![cfg(feature = "macros")]
+
+use pyo3::prelude::*;
+use pyo3::py_run;
+
+mod common;
+
+#[pyclass(dataclass)]
+#[derive(Clone)]
+struct Point {
+    x: f64,
+    y: f64,
+    #[pyo3(name = "tag", default = "origin".to_owned())]
+    label: String,
+    #[pyo3(default)]
+    history: Vec<(f64, f64)>,
+}
+
+#[test]
+fn test_dataclass_new() {
+    Python::with_gil(|py| {
+        let point = py.get_type::<Point>();
+        py_run!(
+            py,
+            point,
+            r#"
+p = point(1.0, 2.0)
+assert (p.x, p.y, p.tag, p.history) == (1.0, 2.0, "origin", [])
+
+p = point(y=2.0, x=1.0, tag="a", history=[(0.0, 0.0)])
+assert (p.x, p.y, p.tag, p.history) == (1.0, 2.0, "a", [(0.0, 0.0)])
+
+p.x = 3.0
+assert p.x == 3.0
+
+try:
+    point(1.0)
+except TypeError:
+    pass
+else:
+    assert False
+"#
+        );
+    });
+}
+
+#[test]
+fn test_dataclass_match_args() {
+    Python::with_gil(|py| {
+        let point = py.get_type::<Point>();
+        py_run!(
+            py,
+            point,
+            r#"
+import sys
+
+assert point.__match_args__ == ("x", "y", "tag", "history")
+
+if sys.version_info >= (3, 10):
+    exec('''
+match point(1.0, 2.0):
+    case point(1.0, y, tag="origin"):
+        assert y == 2.0
+    case _:
+        assert False
+''')
+"#
+        );
+    });
+}
+
+#[test]
+fn test_dataclass_fields() {
+    Python::with_gil(|py| {
+        let point = py.get_type::<Point>();
+        py_run!(
+            py,
+            point,
+            r#"
+import dataclasses
+
+assert dataclasses.is_dataclass(point)
+fields = dataclasses.fields(point)
+assert [f.name for f in fields] == ["x", "y", "tag", "history"]
+assert fields[0].type == "f64"
+assert fields[3].type == "Vec<(f64, f64)>"
+assert fields[0].default is dataclasses.MISSING
+assert fields[2].default == "origin"
+
+p = point(1.0, 2.0, history=[(1.0, 1.0)])
+assert dataclasses.asdict(p) == {"x": 1.0, "y": 2.0, "tag": "origin", "history": [(1.0, 1.0)]}
+assert dataclasses.replace(p, x=5.0).x == 5.0
+"#
+        );
+    });
+}
+
+#[pyclass(dataclass, frozen)]
+#[derive(Clone)]
+struct Config {
+    name: String,
+    retries: u32,
+}
+
+#[test]
+fn test_dataclass_replace() {
+    Python::with_gil(|py| {
+        let config = py.get_type::<Config>();
+        py_run!(
+            py,
+            config,
+            r#"
+c = config("a", 3)
+d = c.__replace__(retries=5)
+assert (d.name, d.retries) == ("a", 5)
+assert (c.name, c.retries) == ("a", 3)
+assert c.__replace__().name == "a"
+
+try:
+    c.name = "b"
+except AttributeError:
+    pass
+else:
+    assert False
+
+try:
+    c.__replace__(timeout=1)
+except TypeError as e:
+    assert str(e) == "__replace__() got an unexpected keyword argument 'timeout'"
+else:
+    assert False
+"#
+        );
+    });
+}
+
+#[pyclass(dataclass)]
+#[derive(Clone)]
+struct Token {
+    r#type: String,
+    #[pyo3(name = "text")]
+    r#ref: String,
+}
+
+#[test]
+fn test_dataclass_keyword_names() {
+    Python::with_gil(|py| {
+        let token = py.get_type::<Token>();
+        py_run!(
+            py,
+            token,
+            r#"
+import dataclasses
+
+t = token(type="name", text="x")
+assert (t.type, t.text) == ("name", "x")
+assert [f.name for f in dataclasses.fields(token)] == ["type", "text"]
+assert t.__replace__(type="op").type == "op"
+"#
+        );
+    });
+}
@@ -190,3 +190,58 @@ fn test_rename_variant_repr_correct() {
         py_assert!(py, var1, "repr(var1) == 'RenameVariantEnum.VARIANT'");
     })
 }
+
+#[pyclass(eq, ord, hash, frozen)]
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum Priority {
+    Low = 1,
+    High = 10,
+}
+
+#[test]
+fn test_enum_eq_ord_hash() {
+    Python::with_gil(|py| {
+        let priority = py.get_type::<Priority>();
+        py_run!(
+            py,
+            priority,
+            r#"
+assert priority.Low == priority.Low
+assert priority.Low < priority.High
+assert priority.Low != 1
+assert len({priority.Low, priority.Low, priority.High}) == 2
+"#
+        );
+    })
+}
+
+#[pyclass(str, repr)]
+#[derive(Debug)]
+enum Direction {
+    North,
+    South,
+}
+
+impl std::fmt::Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Direction::North => "north",
+            Direction::South => "south",
+        })
+    }
+}
+
+#[test]
+fn test_enum_str_repr() {
+    Python::with_gil(|py| {
+        let direction = py.get_type::<Direction>();
+        py_run!(
+            py,
+            direction,
+            r#"
+assert str(direction.North) == "north"
+assert repr(direction.South) == "South"
+"#
+        );
+    })
+}
@@ -125,3 +125,170 @@ fn test_write_unraisable() {
         capture.borrow_mut(py).uninstall(py);
     });
 }
+
+/// Errors from parsing a config file.
+#[derive(Debug, PartialEq, pyo3::PyException)]
+#[pyo3(module = "config", base = exceptions::PyValueError)]
+enum ConfigError {
+    /// A required key is missing.
+    MissingKey {
+        key: String,
+    },
+    #[pyo3(name = "InvalidValueError")]
+    InvalidValue(String, i64),
+    Empty,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::MissingKey { key } => write!(f, "missing key {}", key),
+            ConfigError::InvalidValue(key, value) => {
+                write!(f, "invalid value {} for {}", value, key)
+            }
+            ConfigError::Empty => write!(f, "empty config"),
+        }
+    }
+}
+
+#[pyfunction]
+fn load_config(contents: &str) -> Result<(), ConfigError> {
+    match contents {
+        "" => Err(ConfigError::Empty),
+        "port = -1" => Err(ConfigError::InvalidValue("port".into(), -1)),
+        _ => Err(ConfigError::MissingKey { key: "host".into() }),
+    }
+}
+
+#[test]
+fn test_derive_pyexception() {
+    use pyo3::exceptions::PyExceptionEnum;
+
+    Python::with_gil(|py| {
+        let module = PyModule::new(py, "config").unwrap();
+        ConfigError::add_to_module(module).unwrap();
+        module
+            .add_function(wrap_pyfunction!(load_config, module).unwrap())
+            .unwrap();
+
+        py_run!(
+            py,
+            module,
+            r#"
+        assert issubclass(module.ConfigError, ValueError)
+        assert module.ConfigError.__module__ == "config"
+        assert module.ConfigError.__doc__ == "Errors from parsing a config file."
+        assert issubclass(module.MissingKey, module.ConfigError)
+        assert module.MissingKey.__doc__ == "A required key is missing."
+        assert issubclass(module.InvalidValueError, module.ConfigError)
+        assert issubclass(module.Empty, module.ConfigError)
+
+        try:
+            module.load_config("name = 'x'")
+        except module.MissingKey as e:
+            assert str(e) == "missing key host"
+            assert e.key == "host"
+        else:
+            assert False
+
+        try:
+            module.load_config("port = -1")
+        except module.ConfigError as e:
+            assert type(e) is module.InvalidValueError
+            assert (e._0, e._1) == ("port", -1)
+        else:
+            assert False
+        "#
+        );
+
+        let err = PyErr::from(ConfigError::InvalidValue("port".into(), -1));
+        assert!(err.is_instance(py, ConfigError::base_type_object(py)));
+        assert_eq!(
+            ConfigError::from_err(py, &err),
+            Some(ConfigError::InvalidValue("port".into(), -1))
+        );
+
+        // round trip through Python code
+        let err = module
+            .getattr("load_config")
+            .unwrap()
+            .call1(("",))
+            .unwrap_err();
+        assert_eq!(ConfigError::from_err(py, &err), Some(ConfigError::Empty));
+
+        // exceptions of other types are not converted
+        let err = exceptions::PyValueError::new_err("missing key host");
+        assert_eq!(ConfigError::from_err(py, &err), None);
+        let base = ConfigError::base_type_object(py).call1(("oops",)).unwrap();
+        assert!(base.extract::<ConfigError>().is_err());
+    });
+}
+
+fn fail_deep_in_rust() -> i32 {
+    panic!("deep {}", "failure")
+}
+
+#[pyfunction]
+fn panic_with_details() -> i32 {
+    fail_deep_in_rust()
+}
+
+#[test]
+fn test_panic_details() {
+    pyo3::panic::install_panic_hook();
+
+    Python::with_gil(|py| {
+        let panic_with_details = wrap_pyfunction!(panic_with_details)(py).unwrap();
+
+        py_run!(
+            py,
+            panic_with_details,
+            r#"
+        import traceback
+        try:
+            panic_with_details()
+        except BaseException as e:
+            assert str(e) == "deep failure"
+            assert e.location.startswith("tests/test_exceptions.rs:")
+            frames = traceback.extract_tb(e.__traceback__)
+            assert frames[-1].filename.endswith("test_exceptions.rs")
+            if e.backtrace is not None:
+                names = [frame.name for frame in frames]
+                assert any("fail_deep_in_rust" in name for name in names), names
+        else:
+            assert False
+        "#
+        );
+    });
+}
+
+#[pyfunction]
+fn resume_panic() {
+    std::panic::resume_unwind(Box::new("resumed failure".to_string()))
+}
+
+#[test]
+fn test_panic_details_not_reused() {
+    pyo3::panic::install_panic_hook();
+
+    // The details of a panic caught by user code must not be attached to a later panic
+    let caught = std::panic::catch_unwind(|| panic!("resumed failure"));
+    assert!(caught.is_err());
+
+    Python::with_gil(|py| {
+        let resume_panic = wrap_pyfunction!(resume_panic)(py).unwrap();
+        py_run!(
+            py,
+            resume_panic,
+            r#"
+        try:
+            resume_panic()
+        except BaseException as e:
+            assert str(e) == "resumed failure"
+            assert not hasattr(e, "location")
+        else:
+            assert False
+        "#
+        );
+    });
+}
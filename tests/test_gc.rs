@@ -5,6 +5,7 @@ use pyo3::class::PyVisit;
 use pyo3::prelude::*;
 use pyo3::{py_run, PyCell, PyTryInto};
 use std::cell::Cell;
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
@@ -507,6 +508,105 @@ fn drop_during_traversal_without_gil() {
     assert!(drop_called.load(Ordering::Relaxed));
 }
 
+#[allow(dead_code)]
+#[pyclass(traverse)]
+struct GeneratedTraverse {
+    cycle: Option<Py<Self>>,
+    items: Vec<PyObject>,
+    by_name: HashMap<String, PyObject>,
+    #[pyo3(traverse_skip)]
+    skipped: Option<PyObject>,
+    count: usize,
+    #[pyo3(traverse_skip)]
+    dropped: TestDropCall,
+}
+
+#[test]
+fn traverse_option_collects_cycles() {
+    let drop_called = Arc::new(AtomicBool::new(false));
+
+    Python::with_gil(|py| {
+        let inst = Py::new(
+            py,
+            GeneratedTraverse {
+                cycle: None,
+                items: Vec::new(),
+                by_name: HashMap::new(),
+                skipped: None,
+                count: 0,
+                dropped: TestDropCall {
+                    drop_called: Arc::clone(&drop_called),
+                },
+            },
+        )
+        .unwrap();
+        {
+            let mut borrow = inst.borrow_mut(py);
+            borrow.cycle = Some(inst.clone_ref(py));
+            borrow.items.push(inst.clone_ref(py).into_py(py));
+            borrow
+                .by_name
+                .insert("self".to_owned(), inst.clone_ref(py).into_py(py));
+        }
+
+        py_run!(py, inst, "import gc; assert gc.is_tracked(inst)");
+    });
+
+    for _ in 0..10 {
+        Python::with_gil(|py| {
+            py.run("import gc; gc.collect()", None, None).unwrap();
+        });
+    }
+    assert!(drop_called.load(Ordering::Relaxed));
+}
+
+#[test]
+fn traverse_option_visits_fields() {
+    Python::with_gil(|py| {
+        let obj = Py::new(
+            py,
+            GeneratedTraverse {
+                cycle: None,
+                items: vec![py.None(), py.None()],
+                by_name: vec![("a".to_owned(), py.None())].into_iter().collect(),
+                skipped: Some(py.None()),
+                count: 0,
+                dropped: TestDropCall {
+                    drop_called: Arc::new(AtomicBool::new(false)),
+                },
+            },
+        )
+        .unwrap();
+        let traverse = unsafe {
+            get_type_traverse(obj.as_ref(py).get_type().as_type_ptr())
+                .expect("GeneratedTraverse should have a traverse implementation")
+        };
+        let mut visits = 0usize;
+        unsafe {
+            traverse(
+                obj.as_ptr(),
+                count_visits,
+                &mut visits as *mut usize as *mut core::ffi::c_void,
+            )
+        };
+        // the skipped field and the `None` cycle aren't visited
+        assert_eq!(visits, 3);
+    });
+}
+
+#[pyclass(traverse, frozen)]
+struct FrozenTraverse {
+    _object: PyObject,
+}
+
+#[test]
+fn traverse_option_frozen() {
+    Python::with_gil(|py| {
+        let obj = Py::new(py, FrozenTraverse { _object: py.None() }).unwrap();
+        py_run!(py, obj, "import gc; assert gc.is_tracked(obj)");
+    });
+}
+
 // Manual traversal utilities
 
 unsafe fn get_type_traverse(tp: *mut pyo3::ffi::PyTypeObject) -> Option<pyo3::ffi::traverseproc> {
@@ -528,3 +628,12 @@ extern "C" fn visit_error(
 ) -> std::os::raw::c_int {
     -1
 }
+
+// a visitor function which counts the visited objects in `arg`
+extern "C" fn count_visits(
+    _object: *mut pyo3::ffi::PyObject,
+    arg: *mut core::ffi::c_void,
+) -> std::os::raw::c_int {
+    unsafe { *(arg as *mut usize) += 1 };
+    0
+}
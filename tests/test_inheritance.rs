@@ -168,6 +168,114 @@ except Exception as e:
 }
 
 // Subclassing builtin types is not allowed in the LIMITED API.
+#[pyclass(subclass)]
+struct Pipeline {
+    #[pyo3(get)]
+    log: Vec<String>,
+}
+
+#[pymethods]
+impl Pipeline {
+    #[new]
+    fn new() -> Self {
+        Pipeline { log: Vec::new() }
+    }
+
+    #[pyo3(overridable)]
+    fn transform(&self, value: i64) -> i64 {
+        value + 1
+    }
+
+    #[pyo3(overridable, name = "validate")]
+    fn check(&self, value: i64) -> PyResult<()> {
+        if value < 0 {
+            return Err(pyo3::exceptions::PyValueError::new_err("negative value"));
+        }
+        Ok(())
+    }
+
+    #[pyo3(overridable)]
+    fn record(&mut self, _py: Python<'_>, message: &str) {
+        self.log.push(message.to_owned());
+    }
+
+    fn run(slf: &PyCell<Self>, value: i64) -> PyResult<i64> {
+        Self::dispatch_check(slf, value)?;
+        let result = Self::dispatch_transform(slf, value)?;
+        Self::dispatch_record(slf, &format!("{} -> {}", value, result))?;
+        Ok(result)
+    }
+}
+
+#[test]
+fn overridable_methods() {
+    Python::with_gil(|py| {
+        let pipeline = py.get_type::<Pipeline>();
+        py_run!(
+            py,
+            pipeline,
+            r#"
+p = pipeline()
+assert p.run(1) == 2
+assert p.log == ["1 -> 2"]
+
+class Doubling(pipeline):
+    def transform(self, value):
+        return super().transform(value) * 2
+
+    def validate(self, value):
+        if value > 10:
+            raise ValueError("too large")
+        super().validate(value)
+
+d = Doubling()
+assert d.run(1) == 4
+assert d.log == ["1 -> 4"]
+for value in (-1, 11):
+    try:
+        d.run(value)
+    except ValueError:
+        pass
+    else:
+        assert False
+
+class Recording(Doubling):
+    def __init__(self):
+        self.messages = []
+
+    def record(self, message):
+        self.messages.append(message)
+
+r = Recording()
+assert r.run(2) == 6
+assert r.messages == ["2 -> 6"]
+assert r.log == []
+"#
+        );
+    });
+}
+
+#[test]
+fn overridable_method_bad_return_type() {
+    Python::with_gil(|py| {
+        let pipeline = py.get_type::<Pipeline>();
+        let locals = [("pipeline", pipeline)].into_py_dict(py);
+        let instance = py
+            .eval(
+                "type('Broken', (pipeline,), {'transform': lambda self, value: 'x'})()",
+                None,
+                Some(locals),
+            )
+            .unwrap();
+        let cell: &PyCell<Pipeline> = instance.downcast().unwrap();
+        let err = Pipeline::dispatch_transform(cell, 1).unwrap_err();
+        assert!(err.is_instance_of::<pyo3::exceptions::PyTypeError>(py));
+
+        let base = PyCell::new(py, Pipeline::new()).unwrap();
+        assert_eq!(Pipeline::dispatch_transform(base, 1).unwrap(), 2);
+    });
+}
+
 #[cfg(not(Py_LIMITED_API))]
 mod inheriting_native_type {
     use super::*;
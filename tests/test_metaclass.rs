@@ -0,0 +1,191 @@
+#![cfg(feature = "macros")]
+#![cfg(not(Py_LIMITED_API))]
+
+use pyo3::prelude::*;
+use pyo3::py_run;
+use pyo3::types::{PyDict, PyList, PyType};
+
+mod common;
+
+/// Lets classes be indexed by the names of their attributes.
+#[pyclass(extends = PyType, frozen, subclass)]
+struct Indexed;
+
+#[pymethods]
+impl Indexed {
+    fn __getitem__<'py>(slf: &'py PyCell<Self>, name: &str) -> PyResult<&'py PyAny> {
+        slf.getattr(name)
+    }
+
+    fn __repr__(slf: &PyCell<Self>) -> PyResult<String> {
+        let cls: &PyType = slf.downcast()?;
+        Ok(format!("<indexed class {}>", cls.name()?))
+    }
+}
+
+#[cfg(Py_3_12)]
+#[pyclass(metaclass = Indexed, subclass)]
+struct Model;
+
+#[cfg(Py_3_12)]
+#[pymethods]
+impl Model {
+    #[classattr]
+    const TABLE: &'static str = "models";
+}
+
+#[pyclass(subclass)]
+struct Table;
+
+#[pymethods]
+impl Table {
+    #[classattr]
+    fn registry(py: Python<'_>) -> Py<PyList> {
+        PyList::empty(py).into()
+    }
+
+    #[classmethod]
+    #[pyo3(signature = (**kwargs))]
+    fn __init_subclass__(cls: &PyType, kwargs: Option<&PyDict>) -> PyResult<()> {
+        let table = match kwargs.and_then(|kwargs| kwargs.get_item("table")) {
+            Some(table) => table,
+            None => cls.getattr("__name__")?.call_method0("lower")?,
+        };
+        cls.setattr("TABLE", table)?;
+        cls.getattr("registry")?.call_method1("append", (cls,))?;
+        Ok(())
+    }
+}
+
+#[cfg(Py_3_12)]
+#[pyclass(extends = Model)]
+struct RustModel;
+
+#[pyclass]
+struct Column {
+    #[pyo3(get)]
+    name: Option<String>,
+    #[pyo3(get)]
+    owner: Option<PyObject>,
+}
+
+#[pymethods]
+impl Column {
+    #[new]
+    fn new() -> Self {
+        Column {
+            name: None,
+            owner: None,
+        }
+    }
+
+    fn __set_name__(&mut self, owner: &PyType, name: &str) {
+        self.name = Some(name.to_owned());
+        self.owner = Some(owner.into());
+    }
+}
+
+#[test]
+#[cfg(Py_3_12)]
+fn test_metaclass() {
+    Python::with_gil(|py| {
+        let indexed = py.get_type::<Indexed>();
+        let model = py.get_type::<Model>();
+        let rust_model = py.get_type::<RustModel>();
+        py_run!(
+            py,
+            indexed model rust_model,
+            r#"
+assert type(model) is indexed
+assert isinstance(model, type)
+assert repr(model) == "<indexed class Model>"
+assert model["TABLE"] == "models"
+
+# subclasses get the metaclass of their base, in Rust and in Python
+assert type(rust_model) is indexed
+assert rust_model["TABLE"] == "models"
+
+class Child(model):
+    pass
+
+assert type(Child) is indexed
+assert repr(Child) == "<indexed class Child>"
+"#
+        );
+    });
+}
+
+#[test]
+fn test_metaclass_of_python_class() {
+    Python::with_gil(|py| {
+        let globals = PyDict::new(py);
+        globals
+            .set_item("indexed", py.get_type::<Indexed>())
+            .unwrap();
+        py.run(
+            r#"
+class Custom(metaclass=indexed):
+    value = 1
+
+assert type(Custom) is indexed
+assert repr(Custom) == "<indexed class Custom>"
+assert Custom["value"] == 1
+"#,
+            Some(globals),
+            None,
+        )
+        .unwrap();
+    });
+}
+
+#[test]
+#[cfg(Py_3_12)]
+fn test_metaclass_with_data() {
+    #[pyclass(extends = PyType, subclass)]
+    struct Counting {
+        #[allow(dead_code)]
+        count: usize,
+    }
+
+    #[pyclass(metaclass = Counting)]
+    struct Counted;
+
+    Python::with_gil(|py| {
+        let module = PyModule::new(py, "counted").unwrap();
+        let err = module.add_class::<Counted>().unwrap_err();
+        assert_eq!(
+            err.cause(py).unwrap().value(py).to_string(),
+            "the metaclass of `#[pyclass]` Counted can't add data to the classes it creates: it must be `frozen` and have no fields"
+        );
+    });
+}
+
+#[test]
+fn test_init_subclass_and_set_name() {
+    Python::with_gil(|py| {
+        // class bodies only see globals, so the classes are passed as such
+        let globals = PyDict::new(py);
+        globals.set_item("table", py.get_type::<Table>()).unwrap();
+        globals.set_item("column", py.get_type::<Column>()).unwrap();
+        py.run(
+            r#"
+class User(table, table="users"):
+    id = column()
+    name = column()
+
+class Group(table):
+    pass
+
+assert User.TABLE == "users"
+assert Group.TABLE == "group"
+assert table.registry[-2:] == [User, Group]
+
+assert (User.id.name, User.name.name) == ("id", "name")
+assert User.id.owner is User
+"#,
+            Some(globals),
+            None,
+        )
+        .unwrap();
+    });
+}
@@ -73,3 +73,23 @@ fn test_class_with_multiple_pymethods() {
         py_assert!(py, cls, "cls.CLASS_ATTRIBUTE == 'CLASS_ATTRIBUTE'");
     })
 }
+
+trait Greet {
+    fn greet(&self) -> String;
+}
+
+#[pymethods]
+impl Greet for PyClassWithMultiplePyMethods {
+    fn greet(&self) -> String {
+        "hello".to_owned()
+    }
+}
+
+#[test]
+fn test_trait_pymethods_block() {
+    Python::with_gil(|py| {
+        let cls = py.get_type::<PyClassWithMultiplePyMethods>();
+        py_assert!(py, cls, "cls().greet() == 'hello'");
+        py_assert!(py, cls, "cls().method() == 'method'");
+    });
+}
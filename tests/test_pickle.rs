@@ -0,0 +1,247 @@
+#![cfg(feature = "macros")]
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+mod common;
+
+#[pyclass(pickle, module = "pickle_test")]
+struct Counter {
+    #[pyo3(get, set)]
+    name: String,
+    #[pyo3(get, set)]
+    count: u32,
+}
+
+#[pymethods]
+impl Counter {
+    #[new]
+    fn new(name: String) -> Self {
+        Counter { name, count: 0 }
+    }
+}
+
+#[pyclass(pickle, frozen, subclass, module = "pickle_test")]
+struct Version(
+    #[pyo3(get, name = "major")] u32,
+    #[pyo3(get, name = "minor")] u32,
+);
+
+#[pyclass(pickle, module = "pickle_test")]
+enum Color {
+    Red,
+    #[pyo3(name = "GREEN")]
+    Green,
+}
+
+/// Registers the classes in an importable module, so that `pickle` can find them.
+fn pickle_test_module(py: Python<'_>) -> &PyDict {
+    let module = PyModule::new(py, "pickle_test").unwrap();
+    module.add_class::<Counter>().unwrap();
+    module.add_class::<Version>().unwrap();
+    module.add_class::<Color>().unwrap();
+    #[cfg(all(feature = "serde", feature = "serde_json"))]
+    module.add_class::<Document>().unwrap();
+    #[cfg(all(feature = "serde", feature = "serde_json"))]
+    module.add_class::<Measurements>().unwrap();
+    let modules = py.import("sys").unwrap().getattr("modules").unwrap();
+    modules.set_item("pickle_test", module).unwrap();
+    let locals = PyDict::new(py);
+    locals.set_item("pickle_test", module).unwrap();
+    locals
+}
+
+#[test]
+fn test_pickle_fields() {
+    Python::with_gil(|py| {
+        let locals = pickle_test_module(py);
+        py.run(
+            r#"
+import pickle
+c = pickle_test.Counter("clicks")
+c.count = 3
+assert c.__getstate__() == {"name": "clicks", "count": 3}
+
+d = pickle.loads(pickle.dumps(c))
+assert type(d) is pickle_test.Counter
+assert (d.name, d.count) == ("clicks", 3)
+
+d.__setstate__({"name": "keys", "count": 5})
+assert (d.name, d.count) == ("keys", 5)
+
+try:
+    d.__setstate__({"name": "keys"})
+except ValueError as e:
+    assert str(e) == "cannot unpickle 'Counter': the state is missing field 'count'"
+else:
+    assert False
+"#,
+            None,
+            Some(locals),
+        )
+        .unwrap();
+    });
+}
+
+#[test]
+fn test_pickle_frozen() {
+    Python::with_gil(|py| {
+        let locals = pickle_test_module(py);
+        py.run(
+            r#"
+import copy, pickle
+v = pickle.loads(pickle.dumps(pickle_test.Version._pyo3_unpickle({"major": 1, "minor": 2})))
+assert (v.major, v.minor) == (1, 2)
+assert not hasattr(v, "__setstate__")
+
+class Patched(pickle_test.Version):
+    pass
+
+p = copy.copy(Patched._pyo3_unpickle({"major": 3, "minor": 4}))
+assert type(p) is Patched
+assert (p.major, p.minor) == (3, 4)
+"#,
+            None,
+            Some(locals),
+        )
+        .unwrap();
+    });
+}
+
+#[test]
+fn test_pickle_enum() {
+    Python::with_gil(|py| {
+        let locals = pickle_test_module(py);
+        py.run(
+            r#"
+import pickle
+Color = pickle_test.Color
+assert Color.GREEN.__getstate__() == "GREEN"
+assert pickle.loads(pickle.dumps(Color.GREEN)) == Color.GREEN
+assert pickle.loads(pickle.dumps(Color.Red)) == Color.Red
+
+try:
+    Color._pyo3_unpickle("Blue")
+except ValueError as e:
+    assert str(e) == "cannot unpickle 'Color': 'Blue' is not a variant"
+else:
+    assert False
+"#,
+            None,
+            Some(locals),
+        )
+        .unwrap();
+    });
+}
+
+#[cfg(all(feature = "serde", feature = "serde_json"))]
+#[pyclass(pickle(serde), module = "pickle_test")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Document {
+    title: String,
+    tags: Vec<String>,
+}
+
+#[cfg(all(feature = "serde", feature = "serde_json"))]
+#[test]
+fn test_pickle_serde() {
+    Python::with_gil(|py| {
+        let locals = pickle_test_module(py);
+        let document = Py::new(
+            py,
+            Document {
+                title: "notes".to_owned(),
+                tags: vec!["a".to_owned()],
+            },
+        )
+        .unwrap();
+        locals.set_item("document", document).unwrap();
+        py.run(
+            r#"
+import pickle
+assert document.__getstate__() == b'{"title":"notes","tags":["a"]}'
+copied = pickle.loads(pickle.dumps(document))
+assert copied.__getstate__() == document.__getstate__()
+
+try:
+    copied.__setstate__(b"{}")
+except ValueError as e:
+    assert "missing field" in str(e)
+else:
+    assert False
+"#,
+            None,
+            Some(locals),
+        )
+        .unwrap();
+        let copied: PyRef<'_, Document> = locals.get_item("copied").unwrap().extract().unwrap();
+        assert_eq!(copied.title, "notes");
+        assert_eq!(copied.tags, ["a"]);
+    });
+}
+
+#[cfg(all(feature = "serde", feature = "serde_json"))]
+#[pyclass(pickle(serde), module = "pickle_test")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Measurements {
+    values: Vec<f64>,
+    counts: std::collections::BTreeMap<u32, u32>,
+    ranges: std::collections::BTreeMap<(u32, u32), u32>,
+}
+
+#[cfg(all(feature = "serde", feature = "serde_json"))]
+#[test]
+fn test_pickle_serde_json_limits() {
+    Python::with_gil(|py| {
+        let locals = pickle_test_module(py);
+        let measurements = |values, ranges: &[((u32, u32), u32)]| {
+            Py::new(
+                py,
+                Measurements {
+                    values,
+                    counts: [(1, 2)].iter().copied().collect(),
+                    ranges: ranges.iter().copied().collect(),
+                },
+            )
+            .unwrap()
+        };
+        locals
+            .set_item("finite", measurements(vec![1.5], &[]))
+            .unwrap();
+        locals
+            .set_item("nan", measurements(vec![f64::NAN], &[]))
+            .unwrap();
+        locals
+            .set_item("tuple_keys", measurements(vec![], &[((0, 1), 1)]))
+            .unwrap();
+        py.run(
+            r#"
+import pickle
+copied = pickle.loads(pickle.dumps(finite))
+assert copied.__getstate__() == finite.__getstate__()
+
+# NaN is written as `null`, which can't be loaded back as a float
+data = pickle.dumps(nan)
+try:
+    pickle.loads(data)
+except ValueError as e:
+    assert "invalid type: null" in str(e)
+else:
+    assert False
+
+try:
+    pickle.dumps(tuple_keys)
+except ValueError as e:
+    assert "key must be a string" in str(e)
+else:
+    assert False
+"#,
+            None,
+            Some(locals),
+        )
+        .unwrap();
+        let copied: PyRef<'_, Measurements> = locals.get_item("copied").unwrap().extract().unwrap();
+        assert_eq!(copied.values, [1.5]);
+        assert_eq!(copied.counts.get(&1), Some(&2));
+    });
+}
@@ -0,0 +1,109 @@
+#![cfg(feature = "macros")]
+
+use pyo3::prelude::*;
+use pyo3::py_run;
+
+mod common;
+
+#[pyclass(pymethods_groups(arithmetic, display))]
+struct Number(i64);
+
+#[pymethods]
+impl Number {
+    #[new]
+    fn new(value: i64) -> Self {
+        Number(value)
+    }
+}
+
+#[pymethods(group = arithmetic)]
+impl Number {
+    #[classattr]
+    const ZERO: i64 = 0;
+
+    fn __add__(&self, other: &Self) -> Self {
+        Number(self.0 + other.0)
+    }
+
+    fn __neg__(&self) -> Self {
+        Number(-self.0)
+    }
+
+    #[staticmethod]
+    fn sum(values: Vec<i64>) -> Self {
+        Number(values.iter().sum())
+    }
+}
+
+#[pymethods(group = display)]
+impl Number {
+    fn __repr__(&self) -> String {
+        format!("Number({})", self.0)
+    }
+
+    #[getter]
+    fn value(&self) -> i64 {
+        self.0
+    }
+}
+
+#[test]
+fn test_pymethods_groups() {
+    Python::with_gil(|py| {
+        let number = py.get_type::<Number>();
+        py_run!(
+            py,
+            number,
+            r#"
+assert repr(number(1) + number(2)) == "Number(3)"
+assert repr(-number(1)) == "Number(-1)"
+assert number.sum([1, 2, 3]).value == 6
+assert number.ZERO == 0
+"#
+        );
+    });
+}
+
+trait Parity {
+    fn is_even(&self) -> bool;
+}
+
+#[pyclass(pymethods_groups(parity))]
+#[derive(Clone, Copy, PartialEq)]
+enum Digit {
+    One = 1,
+    Two = 2,
+}
+
+#[pymethods(group = parity)]
+impl Parity for Digit {
+    fn is_even(&self) -> bool {
+        *self as u8 % 2 == 0
+    }
+}
+
+#[pymethods]
+impl Digit {
+    fn next(&self) -> Option<Digit> {
+        match self {
+            Digit::One => Some(Digit::Two),
+            Digit::Two => None,
+        }
+    }
+}
+
+#[test]
+fn test_trait_impl_group() {
+    Python::with_gil(|py| {
+        let digit = py.get_type::<Digit>();
+        py_run!(
+            py,
+            digit,
+            r#"
+assert not digit.One.is_even()
+assert digit.One.next().is_even()
+assert digit.Two.next() is None
+"#
+        );
+    });
+}
@@ -0,0 +1,182 @@
+#![cfg(feature = "macros")]
+
+use pyo3::prelude::*;
+use pyo3::types::{IntoPyDict, PyDict};
+use pyo3::{py_run, pytrait, PyImpl};
+
+mod common;
+
+#[pytrait]
+trait Model {
+    fn set_variables(&mut self, inputs: &[f64]);
+    fn compute(&mut self);
+    fn get_results(&self) -> Vec<f64>;
+    fn scale(&self, factor: f64) -> PyResult<f64>;
+    #[pyo3(name = "describe")]
+    fn description(&self) -> String;
+
+    fn name(&self) -> String {
+        format!("model with {} results", self.get_results().len())
+    }
+}
+
+const MODEL: &str = r#"
+class Model:
+    def __init__(self):
+        self.inputs = []
+        self.results = []
+
+    def set_variables(self, inputs):
+        self.inputs = inputs
+
+    def compute(self):
+        self.results = [x * 2 for x in self.inputs]
+
+    def get_results(self):
+        return self.results
+
+    def scale(self, factor):
+        if factor < 0:
+            raise ValueError("negative factor")
+        return sum(self.results) * factor
+
+    def describe(self):
+        return "doubles its inputs"
+"#;
+
+fn model_class(py: Python<'_>) -> &PyAny {
+    let globals = PyDict::new(py);
+    py.run(MODEL, Some(globals), None).unwrap();
+    globals.get_item("Model").unwrap()
+}
+
+#[test]
+fn test_pytrait_calls_python() {
+    Python::with_gil(|py| {
+        let model: PyImpl<dyn Model> = model_class(py).call0().unwrap().extract().unwrap();
+        let mut model: Box<dyn Model> = Box::new(model);
+
+        model.set_variables(&[1.0, 2.0, 3.0]);
+        model.compute();
+        assert_eq!(model.get_results(), [2.0, 4.0, 6.0]);
+        assert_eq!(model.scale(0.5).unwrap(), 6.0);
+        assert_eq!(model.description(), "doubles its inputs");
+        assert_eq!(model.name(), "model with 3 results");
+    });
+}
+
+#[test]
+fn test_pytrait_errors() {
+    Python::with_gil(|py| {
+        let model: PyImpl<dyn Model> = model_class(py).call0().unwrap().extract().unwrap();
+
+        let err = model.scale(-1.0).unwrap_err();
+        assert_eq!(err.to_string(), "ValueError: negative factor");
+
+        let not_a_model = py.eval("object()", None, None).unwrap();
+        let err = not_a_model.extract::<PyImpl<dyn Model>>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "TypeError: 'object' object cannot be converted to 'Model': it has no method 'set_variables'"
+        );
+    });
+}
+
+#[test]
+fn test_pytrait_default_method() {
+    Python::with_gil(|py| {
+        let class = model_class(py);
+        let locals = [("Model", class)].into_py_dict(py);
+        let named = py
+            .eval(
+                "type('NamedModel', (Model,), {'name': lambda self: 'named'})()",
+                None,
+                Some(locals),
+            )
+            .unwrap();
+        let named: PyImpl<dyn Model> = named.extract().unwrap();
+        assert_eq!(named.name(), "named");
+    });
+}
+
+#[test]
+#[should_panic(expected = "Python implementation of `Model::get_results` failed: TypeError")]
+fn test_pytrait_conversion_panic() {
+    Python::with_gil(|py| {
+        let class = model_class(py);
+        let locals = [("Model", class)].into_py_dict(py);
+        let model: PyImpl<dyn Model> = py
+            .eval(
+                "type('BadModel', (Model,), {'get_results': lambda self: 'not a list'})()",
+                None,
+                Some(locals),
+            )
+            .unwrap()
+            .extract()
+            .unwrap();
+        model.get_results();
+    });
+}
+
+#[derive(Debug)]
+enum PluginError {
+    Python(String),
+}
+
+impl From<PyErr> for PluginError {
+    fn from(err: PyErr) -> Self {
+        PluginError::Python(err.to_string())
+    }
+}
+
+#[pytrait]
+trait Plugin: Send + Sync {
+    fn r#type(&self) -> Result<String, PluginError>;
+    fn run(&self, input: String, verbose: bool) -> Result<(), PluginError>;
+}
+
+#[pyfunction]
+fn run_plugin(py: Python<'_>, plugin: PyImpl<dyn Plugin>) -> String {
+    let plugin: Box<dyn Plugin> = Box::new(plugin);
+    py.allow_threads(|| {
+        std::thread::spawn(move || match plugin.run("input".to_owned(), true) {
+            Ok(()) => plugin.r#type().unwrap(),
+            Err(PluginError::Python(msg)) => msg,
+        })
+        .join()
+        .unwrap()
+    })
+}
+
+#[test]
+fn test_pytrait_from_python() {
+    Python::with_gil(|py| {
+        let run_plugin = wrap_pyfunction!(run_plugin, py).unwrap();
+        py_run!(
+            py,
+            run_plugin,
+            r#"
+class Upper:
+    def type(self):
+        return "upper"
+
+    def run(self, input, verbose):
+        assert (input, verbose) == ("input", True)
+
+class Failing(Upper):
+    def run(self, input, verbose):
+        raise RuntimeError("failed")
+
+assert run_plugin(Upper()) == "upper"
+assert run_plugin(Failing()) == "RuntimeError: failed"
+
+try:
+    run_plugin(42)
+except TypeError as e:
+    assert "'int' object cannot be converted to 'Plugin'" in str(e)
+else:
+    assert False
+"#
+        );
+    });
+}
@@ -76,4 +76,34 @@ mod test_serde {
             )
         });
     }
+
+    #[test]
+    fn test_pyerr_snapshot_roundtrip() {
+        let snapshot = Python::with_gil(|py| {
+            py.run(
+                "raise ValueError('invalid port') from KeyError('port')",
+                None,
+                None,
+            )
+            .unwrap_err()
+            .snapshot(py)
+        });
+
+        let serialized = serde_json::to_string(&snapshot).expect("failed to serialize");
+        let deserialized: pyo3::PyErrSnapshot =
+            serde_json::from_str(&serialized).expect("failed to deserialize");
+        assert_eq!(deserialized, snapshot);
+        assert_eq!(deserialized.cause().unwrap().message(), "'port'");
+    }
+
+    #[test]
+    fn test_pyerr_snapshot_deserialize_errors() {
+        let snapshot: pyo3::PyErrSnapshot =
+            serde_json::from_str(r#"["ValueError", "bad", [], null, null, null]"#).unwrap();
+        assert_eq!(snapshot.to_string(), "ValueError: bad");
+
+        let err = serde_json::from_str::<pyo3::PyErrSnapshot>(r#"{"type_name": "ValueError"}"#)
+            .unwrap_err();
+        assert!(err.to_string().contains("missing field `message`"));
+    }
 }
@@ -0,0 +1,186 @@
+#![cfg(feature = "macros")]
+
+use pyo3::prelude::*;
+use pyo3::py_run;
+
+mod common;
+
+trait Shape {
+    type Unit;
+
+    fn area(&self) -> f64;
+    fn unit(&self) -> Self::Unit;
+    fn scale(&mut self, factor: f64);
+    fn __repr__(&self) -> String;
+    fn get_sides(&self) -> usize;
+    fn unit_square() -> Self;
+}
+
+#[pyclass]
+struct Square {
+    side: f64,
+}
+
+#[pymethods]
+impl Shape for Square {
+    type Unit = String;
+
+    /// The area of the square.
+    fn area(&self) -> f64 {
+        self.side * self.side
+    }
+
+    fn unit(&self) -> Self::Unit {
+        "m²".to_owned()
+    }
+
+    #[pyo3(name = "resize")]
+    fn scale(&mut self, factor: f64) {
+        self.side *= factor;
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Square({:?})", self.side)
+    }
+
+    #[getter]
+    fn get_sides(&self) -> usize {
+        4
+    }
+
+    #[staticmethod]
+    fn unit_square() -> Self {
+        Square { side: 1.0 }
+    }
+}
+
+#[test]
+fn test_trait_methods() {
+    Python::with_gil(|py| {
+        let square = PyCell::new(py, Square { side: 2.0 }).unwrap();
+        py_run!(
+            py,
+            square,
+            r#"
+assert square.area() == 4.0
+assert square.unit() == "m²"
+assert square.sides == 4
+assert repr(square) == "Square(2.0)"
+assert type(square).area.__doc__ == "The area of the square."
+
+square.resize(1.5)
+assert square.area() == 9.0
+assert not hasattr(square, "scale")
+
+assert repr(type(square).unit_square()) == "Square(1.0)"
+"#
+        );
+
+        // The trait methods are still usable from Rust
+        assert_eq!(Shape::area(&*square.borrow()), 9.0);
+    });
+}
+
+trait Scale<F> {
+    fn scaled(&self, factor: F) -> Self;
+}
+
+#[pyclass]
+#[derive(Clone)]
+struct Length(f64);
+
+#[pymethods]
+impl Scale<f64> for Length {
+    fn scaled(&self, factor: f64) -> Self {
+        Length(self.0 * factor)
+    }
+}
+
+#[test]
+fn test_generic_trait_methods() {
+    Python::with_gil(|py| {
+        let length = Py::new(py, Length(2.0)).unwrap();
+        let scaled: Length = length
+            .call_method1(py, "scaled", (1.5,))
+            .unwrap()
+            .extract(py)
+            .unwrap();
+        assert_eq!(scaled.0, 3.0);
+    });
+}
+
+trait Area {
+    fn area(&self) -> f64;
+}
+
+trait Describe {
+    fn describe(&self) -> String;
+    fn is_larger(&self, other: &Self) -> bool;
+}
+
+#[pyclass]
+struct Rectangle {
+    width: f64,
+    height: f64,
+}
+
+#[pyclass]
+struct Circle {
+    radius: f64,
+}
+
+impl Area for Rectangle {
+    fn area(&self) -> f64 {
+        self.width * self.height
+    }
+}
+
+impl Area for Circle {
+    fn area(&self) -> f64 {
+        std::f64::consts::PI * self.radius * self.radius
+    }
+}
+
+#[pymethods(for(Rectangle, Circle))]
+impl<T: Area> Describe for T {
+    fn describe(&self) -> String {
+        format!("shape with area {:.1}", self.area())
+    }
+
+    fn is_larger(&self, other: &T) -> bool {
+        self.area() > other.area()
+    }
+}
+
+#[test]
+fn test_blanket_trait_methods() {
+    Python::with_gil(|py| {
+        let rectangle = PyCell::new(
+            py,
+            Rectangle {
+                width: 2.0,
+                height: 3.0,
+            },
+        )
+        .unwrap();
+        let circle = PyCell::new(py, Circle { radius: 1.0 }).unwrap();
+        let small_circle = PyCell::new(py, Circle { radius: 0.5 }).unwrap();
+        py_run!(
+            py,
+            rectangle circle small_circle,
+            r#"
+assert rectangle.describe() == "shape with area 6.0"
+assert circle.describe() == "shape with area 3.1"
+assert circle.is_larger(small_circle)
+assert not small_circle.is_larger(circle)
+
+try:
+    circle.is_larger(rectangle)
+except TypeError:
+    pass
+else:
+    assert False
+"#
+        );
+    });
+}
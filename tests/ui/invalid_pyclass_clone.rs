@@ -0,0 +1,20 @@
+use pyo3::prelude::*;
+
+#[derive(Clone)]
+struct Settings {
+    verbose: bool,
+}
+
+#[pyclass(clone)]
+#[derive(Clone)]
+struct MissingDeepCopy {
+    settings: Settings,
+}
+
+#[pyclass]
+struct NotClone {
+    #[pyo3(deepcopy_skip)]
+    settings: Settings,
+}
+
+fn main() {}
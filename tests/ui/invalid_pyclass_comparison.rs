@@ -0,0 +1,23 @@
+use pyo3::prelude::*;
+
+#[pyclass(ord)]
+#[derive(PartialEq, PartialOrd)]
+struct OrdWithoutEq;
+
+#[pyclass(eq, hash)]
+#[derive(PartialEq, Hash)]
+struct MutableHash;
+
+#[pyclass(hash, frozen)]
+#[derive(Hash)]
+struct HashWithoutEq;
+
+#[pyclass(eq, ord)]
+#[derive(PartialEq)]
+struct MissingPartialOrd;
+
+#[pyclass(eq, hash, frozen)]
+#[derive(PartialEq)]
+struct MissingHash;
+
+fn main() {}
@@ -0,0 +1,9 @@
+use pyo3::prelude::*;
+
+#[pyclass(frozen, context_manager)]
+struct Frozen {}
+
+#[pyclass(context_manager(sync))]
+struct NotAsync {}
+
+fn main() {}
@@ -0,0 +1,21 @@
+use pyo3::prelude::*;
+
+#[pyclass(dataclass)]
+#[derive(Clone)]
+struct TupleStruct(i32);
+
+#[pyclass]
+struct NotADataclass {
+    #[pyo3(get, default = 1)]
+    value: i32,
+}
+
+#[pyclass(dataclass)]
+#[derive(Clone)]
+struct RequiredAfterDefault {
+    #[pyo3(default = 1)]
+    a: i32,
+    b: i32,
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use pyo3::prelude::*;
+
+struct Opaque(i32);
+
+impl<'source> FromPyObject<'source> for Opaque {
+    fn extract(ob: &'source PyAny) -> PyResult<Self> {
+        ob.extract().map(Opaque)
+    }
+}
+
+#[pyclass(dataclass, frozen)]
+struct NotClone {
+    #[pyo3(default = Opaque(0))]
+    value: Opaque,
+}
+
+fn main() {}
@@ -15,4 +15,10 @@ enum NotDrivedClass {
 #[pyclass]
 enum NoEmptyEnum {}
 
+#[pyclass(generic)]
+enum GenericEnum {
+    X,
+    Y,
+}
+
 fn main() {}
@@ -0,0 +1,26 @@
+use pyo3::prelude::*;
+
+#[pyclass]
+struct NotGc {
+    #[pyo3(traverse_skip)]
+    object: PyObject,
+}
+
+#[pyclass(traverse)]
+enum Enum {
+    A,
+}
+
+#[pyclass(traverse)]
+struct BarePy {
+    object: PyObject,
+}
+
+struct Opaque;
+
+#[pyclass(traverse)]
+struct MissingTraverse {
+    opaque: Opaque,
+}
+
+fn main() {}
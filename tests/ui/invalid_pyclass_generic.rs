@@ -0,0 +1,13 @@
+use pyo3::prelude::*;
+use pyo3::types::PyType;
+
+#[pyclass(generic)]
+struct GenericWithClassGetitem;
+
+#[pymethods]
+impl GenericWithClassGetitem {
+    #[classmethod]
+    fn __class_getitem__(_cls: &PyType, _item: &PyAny) {}
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use pyo3::prelude::*;
+use pyo3::types::PyType;
+
+#[pyclass(extends = PyType, frozen, subclass)]
+struct Meta;
+
+#[pyclass(metaclass = Meta)]
+struct NeedsPython312;
+
+fn main() {}
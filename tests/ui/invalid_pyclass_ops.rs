@@ -0,0 +1,23 @@
+use pyo3::prelude::*;
+
+#[pyclass(ops(pow))]
+#[derive(Clone)]
+struct UnknownOp {}
+
+#[pyclass(ops(add, add))]
+#[derive(Clone)]
+struct DuplicateOp {}
+
+#[pyclass(ops(neg(i32)))]
+#[derive(Clone)]
+struct UnaryOperand {}
+
+#[pyclass(ops(add_assign(i32, f64)))]
+#[derive(Clone)]
+struct InPlaceOperands {}
+
+#[pyclass(frozen, ops(add_assign))]
+#[derive(Clone)]
+struct FrozenInPlace {}
+
+fn main() {}
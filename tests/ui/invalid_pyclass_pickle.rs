@@ -0,0 +1,13 @@
+use pyo3::prelude::*;
+
+#[pyclass(pickle)]
+struct MissingGet {
+    #[pyo3(get)]
+    a: i32,
+    b: i32,
+}
+
+#[pyclass(pickle(json))]
+struct UnknownFormat {}
+
+fn main() {}
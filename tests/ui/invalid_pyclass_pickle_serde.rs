@@ -0,0 +1,9 @@
+use pyo3::prelude::*;
+
+#[pyclass(pickle(serde))]
+#[derive(Clone)]
+struct NoSerdeFeature {
+    value: i32,
+}
+
+fn main() {}
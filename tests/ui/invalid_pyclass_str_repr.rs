@@ -0,0 +1,25 @@
+use pyo3::prelude::*;
+
+#[pyclass(repr = "Point({}, {y})")]
+struct PositionalPlaceholder {
+    x: i32,
+    y: i32,
+}
+
+#[pyclass(repr = "Point({x}, {z})")]
+struct UnknownField {
+    x: i32,
+}
+
+#[pyclass(repr = "Color")]
+enum EnumTemplate {
+    Red,
+}
+
+#[pyclass(str)]
+struct MissingDisplay;
+
+#[pyclass(repr)]
+struct MissingDebug;
+
+fn main() {}
@@ -0,0 +1,34 @@
+use pyo3::PyException;
+
+#[derive(PyException)]
+struct NotAnEnum {
+    field: String,
+}
+
+#[derive(PyException)]
+enum Empty {}
+
+#[derive(PyException)]
+enum Generic<T> {
+    Variant(T),
+}
+
+#[derive(PyException)]
+#[pyo3(module = "mymodule", module = "other")]
+enum DuplicateModule {
+    Variant,
+}
+
+#[derive(PyException)]
+#[pyo3(subclass)]
+enum UnknownOption {
+    Variant,
+}
+
+#[derive(PyException)]
+enum UnknownVariantOption {
+    #[pyo3(module = "mymodule")]
+    Variant,
+}
+
+fn main() {}
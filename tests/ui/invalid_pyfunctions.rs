@@ -18,4 +18,8 @@ fn destructured_argument((a, b): (i32, i32)) {}
 #[pyfunction]
 fn function_with_required_after_option(_opt: Option<i32>, _x: i32) {}
 
+#[pyfunction]
+#[pyo3(overridable)]
+fn overridable_function() {}
+
 fn main() {}
@@ -144,6 +144,34 @@ impl MyClass {
     fn method_self_by_value(self) {}
 }
 
+struct OverridableStaticMethod {}
+
+#[pymethods]
+impl OverridableStaticMethod {
+    #[staticmethod]
+    #[pyo3(overridable)]
+    fn static_method() {}
+}
+
+#[pyclass]
+struct OverridablePyRef {}
+
+#[pymethods]
+impl OverridablePyRef {
+    #[pyo3(overridable)]
+    fn method(_slf: PyRef<'_, Self>) {}
+}
+
+struct OverridableMagicMethod {}
+
+#[pymethods]
+impl OverridableMagicMethod {
+    #[pyo3(overridable)]
+    fn __len__(&self) -> usize {
+        0
+    }
+}
+
 struct TwoNew {}
 
 #[pymethods]
@@ -170,4 +198,26 @@ impl DuplicateMethod {
     fn func_b(&self) {}
 }
 
+struct InitSubclassNotClassmethod {}
+
+#[pymethods]
+impl InitSubclassNotClassmethod {
+    fn __init_subclass__(&self) {}
+}
+
+struct SetNameStatic {}
+
+#[pymethods]
+impl SetNameStatic {
+    #[staticmethod]
+    fn __set_name__(_owner: &PyAny, _name: &str) {}
+}
+
+struct ClassGetitemInstance {}
+
+#[pymethods]
+impl ClassGetitemInstance {
+    fn __class_getitem__(&self, _item: &PyAny) {}
+}
+
 fn main() {}
@@ -0,0 +1,15 @@
+use pyo3::prelude::*;
+
+#[pyclass(pymethods_groups(extra, extra))]
+struct DuplicateGroup;
+
+#[pyclass(pymethods_groups(missing))]
+struct MissingGroup;
+
+#[pyclass]
+struct TwoGroups;
+
+#[pymethods(group = a, group = b)]
+impl TwoGroups {}
+
+fn main() {}
@@ -0,0 +1,42 @@
+use pyo3::pytrait;
+
+#[pytrait]
+trait Generic<T> {
+    fn get(&self) -> T;
+}
+
+#[pytrait]
+trait AssociatedType {
+    type Output;
+}
+
+#[pytrait]
+trait ByValue {
+    fn consume(self);
+}
+
+#[pytrait]
+trait NoReceiver {
+    fn create() -> Self;
+}
+
+#[pytrait]
+trait GenericMethod {
+    fn get<T>(&self, value: T);
+}
+
+#[pytrait]
+trait AsyncMethod {
+    async fn get(&self);
+}
+
+#[pytrait]
+trait UnknownOption {
+    #[pyo3(signature = (x))]
+    fn get(&self, x: i32);
+}
+
+#[pytrait(name = "Other")]
+trait UnknownArg {}
+
+fn main() {}
@@ -0,0 +1,53 @@
+use pyo3::prelude::*;
+
+trait Describe {
+    fn describe(&self) -> String;
+}
+
+#[pymethods]
+impl<T> Describe for T {
+    fn describe(&self) -> String {
+        String::new()
+    }
+}
+
+#[pyclass]
+struct MyClass {}
+
+trait Summarize {
+    fn summarize(&self) -> String;
+}
+
+#[pymethods(for(MyClass))]
+impl<T> Summarize for Vec<T> {
+    fn summarize(&self) -> String {
+        String::new()
+    }
+}
+
+#[pymethods(for(MyClass))]
+impl MyClass {}
+
+trait Kind {
+    const KIND: &'static str;
+}
+
+#[pymethods]
+impl Kind for MyClass {
+    #[classattr]
+    const KIND: &'static str = "";
+}
+
+trait Name {
+    fn name(&self) -> String;
+}
+
+#[pymethods]
+impl Name for MyClass {
+    #[pyo3(overridable)]
+    fn name(&self) -> String {
+        String::new()
+    }
+}
+
+fn main() {}